@@ -7,13 +7,10 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
     let DecimalCharacteristics {
         struct_name,
         big_type,
-        underlying_type,
         ..
     } = characteristics;
 
     let name_str = &struct_name.to_string();
-    let underlying_str = &underlying_type.to_string();
-    let big_str = &big_type.to_string();
 
     let module_name = string_to_ident("tests_big_ops_", &name_str);
 
@@ -23,88 +20,78 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
             T::U: TryInto<#big_type>,
         {
             fn big_mul(self, rhs: T) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get())
-                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_mul()", #big_str, #name_str))
-                        .checked_mul(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_mul()", #big_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul()", #name_str))
-                        .checked_div(
-                            T::one()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul()", #name_str))
-                        .try_into()
-                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_mul()", #underlying_str, #name_str))
-
-                )
+                self.big_mul_rounded(rhs, Rounding::Down)
             }
 
             fn big_mul_up(self, rhs: T) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get())
-                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_mul_up()", #big_str, #name_str))
-                        .checked_mul(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_mul_up()", #big_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
-                        .checked_add(T::almost_one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
-                        .checked_div(
-                            T::one()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
-                        .try_into()
-                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_mul_up()", #underlying_str, #name_str))
-                )
+                self.big_mul_rounded(rhs, Rounding::Up)
             }
 
             fn big_div(self, rhs: T) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get())
-                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div()", #big_str, #name_str))
-                        .checked_mul(
-                            T::one()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div()", #name_str))
-                        .checked_div(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div()", #big_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div()", #name_str))
-                        .try_into()
-                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div()", #underlying_str, #name_str))
-                )
+                self.big_div_rounded(rhs, Rounding::Down)
             }
 
             fn big_div_up(self, rhs: T) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get())
-                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
-                        .checked_mul(
-                            T::one()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
-                        .checked_add(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
-                                .checked_sub(#big_type::from(1u128)).unwrap()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
-                        .checked_div(
-                            rhs.get()
-                                .try_into().unwrap_or_else(|_| std::panic!("rhs value could not be converted to big type in `big_div_up`")),
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
-                        .try_into()
-                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div_up()", #underlying_str, #name_str))
-                )
+                self.big_div_rounded(rhs, Rounding::Up)
+            }
+
+            fn checked_big_mul(self, rhs: T) -> std::result::Result<Self, String> {
+                self.checked_big_mul_rounded(rhs, Rounding::Down)
+            }
+
+            fn checked_big_mul_up(self, rhs: T) -> std::result::Result<Self, String> {
+                self.checked_big_mul_rounded(rhs, Rounding::Up)
+            }
+
+            fn checked_big_div(self, rhs: T) -> std::result::Result<Self, String> {
+                self.checked_big_div_rounded(rhs, Rounding::Down)
+            }
+
+            fn checked_big_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                self.checked_big_div_rounded(rhs, Rounding::Up)
+            }
+
+            fn big_mul_rounded(self, rhs: T, rounding: Rounding) -> Self {
+                self.checked_big_mul_rounded(rhs, rounding)
+                    .unwrap_or_else(|e| std::panic!("decimal: {} in method {}::big_mul_rounded()", e, #name_str))
+            }
+
+            fn big_div_rounded(self, rhs: T, rounding: Rounding) -> Self {
+                self.checked_big_div_rounded(rhs, rounding)
+                    .unwrap_or_else(|e| std::panic!("decimal: {} in method {}::big_div_rounded()", e, #name_str))
+            }
+
+            fn checked_big_mul_rounded(self, rhs: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                let method = match rounding {
+                    Rounding::Down => "checked_big_mul",
+                    Rounding::Up => "checked_big_mul_up",
+                    Rounding::Nearest => "checked_big_mul_rounded",
+                };
+                let numerator = #big_type::try_from(self.get()).map_err(|_| format!("{}: can't convert self to big_type", method))?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| format!("{}: can't convert rhs to big_type", method))?
+                    ).ok_or_else(|| format!("{}: (self * rhs) multiplication overflow", method))?;
+                Ok(Self::new(
+                    rounding.checked_div(numerator, T::checked_one()?)
+                    .ok_or_else(|| format!("{}: ((self * rhs) / rhs::one()) division overflow", method))?
+                    .try_into().map_err(|_| format!("{}: can't convert to result", method))?
+                ))
+            }
+
+            fn checked_big_div_rounded(self, rhs: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                let method = match rounding {
+                    Rounding::Down => "checked_big_div",
+                    Rounding::Up => "checked_big_div_up",
+                    Rounding::Nearest => "checked_big_div_rounded",
+                };
+                let numerator = #big_type::try_from(self.get()).map_err(|_| format!("{}: can't convert self to big_type", method))?
+                    .checked_mul(T::checked_one()?).ok_or_else(|| format!("{}: (self * rhs::one()) multiplication overflow", method))?;
+                let denominator: #big_type = rhs.get().try_into().map_err(|_| format!("{}: can't convert rhs to big_type", method))?;
+                Ok(Self::new(
+                    rounding.checked_div(numerator, denominator)
+                    .ok_or_else(|| format!("{}: ((self * rhs::one()) / rhs) division overflow", method))?
+                    .try_into().map_err(|_| format!("{}: can't convert to result", method))?
+                ))
             }
         }
 
@@ -139,6 +126,46 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                 let b = #struct_name::new(#struct_name::one());
                 assert_eq!(a.big_div_up(b), #struct_name::new(2));
             }
+
+            #[test]
+            fn test_big_ops_rounded () {
+                let a = #struct_name::new(5);
+                let b = #struct_name::from_integer(2);
+                let c = #struct_name::new(2);
+                // 5 * 0.5 = 2.5 and 5 / 2 = 2.5 in the smallest units
+                let half = #struct_name::new(#struct_name::one::<u128>().checked_div(2).unwrap().try_into().unwrap());
+                if #struct_name::scale() > 0 {
+                    assert_eq!(a.big_mul_rounded(half, Rounding::Down), c);
+                    assert_eq!(a.big_mul_rounded(half, Rounding::Up), #struct_name::new(3));
+                    assert_eq!(a.big_mul_rounded(half, Rounding::Nearest), #struct_name::new(3));
+                }
+                assert_eq!(a.big_div_rounded(b, Rounding::Down), c);
+                assert_eq!(a.big_div_rounded(b, Rounding::Up), #struct_name::new(3));
+                assert_eq!(a.big_div_rounded(b, Rounding::Nearest), #struct_name::new(3));
+                assert_eq!(#struct_name::new(4).checked_big_div_rounded(#struct_name::from_integer(3), Rounding::Nearest), Ok(#struct_name::new(1)));
+                assert!(a.checked_big_div_rounded(#struct_name::new(0), Rounding::Nearest).is_err());
+            }
+
+            #[test]
+            fn test_checked_big_ops () {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_big_mul(b), Ok(#struct_name::new(2)));
+                assert_eq!(a.checked_big_mul_up(b), Ok(#struct_name::new(2)));
+                assert_eq!(a.checked_big_div(b), Ok(#struct_name::new(2)));
+                assert_eq!(a.checked_big_div_up(b), Ok(#struct_name::new(2)));
+            }
+
+            #[test]
+            fn test_overflow_checked_big_ops () {
+                let max = #struct_name::max_instance();
+                let two = #struct_name::from_integer(2);
+                let zero = #struct_name::new(0);
+                assert!(max.checked_big_mul(two).is_err());
+                assert!(max.checked_big_mul_up(two).is_err());
+                assert!(max.checked_big_div(zero).is_err());
+                assert!(max.checked_big_div_up(zero).is_err());
+            }
         }
     ))
 }
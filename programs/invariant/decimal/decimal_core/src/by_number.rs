@@ -17,48 +17,38 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
     proc_macro::TokenStream::from(quote!(
         impl ByNumber<#big_type> for #struct_name {
             fn big_div_by_number(self, rhs: #big_type) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get()).unwrap()
-                        .checked_mul(
-                            Self::one()
-                        ).unwrap()
-                        .checked_div(rhs).unwrap()
-                        .try_into().unwrap()
-                )
+                self.big_div_by_number_rounded(rhs, Rounding::Down)
             }
 
             fn checked_big_div_by_number(self, rhs: #big_type) -> std::result::Result<Self, String> {
-                Ok(Self::new(
-                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number: can't convert self to big_type")?
-                    .checked_mul(Self::checked_one()?).ok_or_else(|| "checked_big_div_by_number: (self * Self::one()) multiplication overflow")?
-                    .checked_div(rhs).ok_or_else(|| "checked_big_div_by_number: ((self * Self::one()) / rhs) division overflow")?
-                    .try_into().map_err(|_| "checked_big_div_by_number: can't convert to result")?
-                ))
+                self.checked_big_div_by_number_rounded(rhs, Rounding::Down)
             }
 
             fn big_div_by_number_up(self, rhs: #big_type) -> Self {
-                Self::new(
-                    #big_type::try_from(self.get()).unwrap()
-                        .checked_mul(
-                            Self::one()
-                        ).unwrap()
-                        .checked_add(
-                            rhs.checked_sub(#big_type::from(1u8)).unwrap()
-                        ).unwrap()
-                        .checked_div(rhs).unwrap()
-                        .try_into().unwrap()
-                )
+                self.big_div_by_number_rounded(rhs, Rounding::Up)
             }
 
             fn checked_big_div_by_number_up(self, rhs: #big_type) -> std::result::Result<Self, String> {
+                self.checked_big_div_by_number_rounded(rhs, Rounding::Up)
+            }
+
+            fn big_div_by_number_rounded(self, rhs: #big_type, rounding: Rounding) -> Self {
+                self.checked_big_div_by_number_rounded(rhs, rounding)
+                    .unwrap_or_else(|e| std::panic!("decimal: {} in method {}::big_div_by_number_rounded()", e, #name_str))
+            }
+
+            fn checked_big_div_by_number_rounded(self, rhs: #big_type, rounding: Rounding) -> std::result::Result<Self, String> {
+                let method = match rounding {
+                    Rounding::Down => "checked_big_div_by_number",
+                    Rounding::Up => "checked_big_div_by_number_up",
+                    Rounding::Nearest => "checked_big_div_by_number_rounded",
+                };
+                let numerator = #big_type::try_from(self.get()).map_err(|_| format!("{}: can't convert self to big_type", method))?
+                    .checked_mul(Self::checked_one()?).ok_or_else(|| format!("{}: (self * Self::one()) multiplication overflow", method))?;
                 Ok(Self::new(
-                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number_up: can't convert self to big_type")?
-                    .checked_mul(Self::checked_one()?).ok_or_else(|| "checked_big_div_by_number_up: (self * Self::one()) multiplication overflow")?
-                    .checked_add(
-                        rhs.checked_sub(#big_type::from(1u8)).ok_or_else(|| "checked_big_div_by_number_up: (rhs - 1) subtraction overflow")?
-                    ).ok_or_else(|| "checked_big_div_by_number_up: ((self * Self::one()) + (rhs - 1)) addition overflow")?
-                    .checked_div(rhs).ok_or_else(|| "checked_big_div_by_number_up: (((self * Self::one()) + (rhs - 1)) / rhs) division overflow")?
-                    .try_into().map_err(|_| "checked_big_div_by_number_up: can't convert to result")?
+                    rounding.checked_div(numerator, rhs)
+                    .ok_or_else(|| format!("{}: ((self * Self::one()) / rhs) division overflow", method))?
+                    .try_into().map_err(|_| format!("{}: can't convert to result", method))?
                 ))
             }
         }
@@ -90,6 +80,23 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                         T::one()
                     ).unwrap()
             }
+
+            fn checked_big_mul_to_value(self, rhs: T) -> std::result::Result<#big_type, String> {
+                #big_type::try_from(self.get()).map_err(|_| "checked_big_mul_to_value: can't convert self to big_type")?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_big_mul_to_value: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_mul_to_value: (self * rhs) multiplication overflow")?
+                    .checked_div(T::checked_one()?).ok_or_else(|| "checked_big_mul_to_value: ((self * rhs) / rhs::one()) division overflow".to_string())
+            }
+
+            fn checked_big_mul_to_value_up(self, rhs: T) -> std::result::Result<#big_type, String> {
+                #big_type::try_from(self.get()).map_err(|_| "checked_big_mul_to_value_up: can't convert self to big_type")?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_big_mul_to_value_up: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_mul_to_value_up: (self * rhs) multiplication overflow")?
+                    .checked_add(T::almost_one()).ok_or_else(|| "checked_big_mul_to_value_up: ((self * rhs) + rhs::almost_one()) addition overflow")?
+                    .checked_div(T::checked_one()?).ok_or_else(|| "checked_big_mul_to_value_up: (((self * rhs) + rhs::almost_one()) / rhs::one()) division overflow".to_string())
+            }
         }
 
         #[cfg(test)]
@@ -118,6 +125,16 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                 assert_eq!(a.checked_big_div_by_number_up(b), Ok(#struct_name::new(2)));
             }
 
+            #[test]
+            fn test_big_div_by_number_rounded() {
+                let a = #struct_name::new(5);
+                let b: #big_type = #struct_name::one::<#big_type>().checked_mul(#big_type::from(2u8)).unwrap();
+                assert_eq!(a.big_div_by_number_rounded(b, Rounding::Down), #struct_name::new(2));
+                assert_eq!(a.big_div_by_number_rounded(b, Rounding::Up), #struct_name::new(3));
+                assert_eq!(a.big_div_by_number_rounded(b, Rounding::Nearest), #struct_name::new(3));
+                assert!(a.checked_big_div_by_number_rounded(#big_type::from(0u8), Rounding::Nearest).is_err());
+            }
+
             #[test]
             fn test_big_mul_to_value () {
                 let a = #struct_name::new(2);
@@ -125,6 +142,14 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                 assert_eq!(a.big_mul_to_value(b), #big_type::from(a.get()));
                 assert_eq!(a.big_mul_to_value_up(b), #big_type::from(a.get()));
             }
+
+            #[test]
+            fn test_checked_big_mul_to_value () {
+                let a = #struct_name::new(2);
+                let b = #struct_name::from_integer(1);
+                assert_eq!(a.checked_big_mul_to_value(b), Ok(#big_type::from(a.get())));
+                assert_eq!(a.checked_big_mul_to_value_up(b), Ok(#big_type::from(a.get())));
+            }
         }
     ))
 }
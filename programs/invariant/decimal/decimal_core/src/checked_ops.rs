@@ -4,7 +4,11 @@ use crate::utils::string_to_ident;
 use crate::DecimalCharacteristics;
 
 pub fn generate_checked_ops(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
-    let DecimalCharacteristics { struct_name, .. } = characteristics;
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        ..
+    } = characteristics;
 
     let name_str = &struct_name.to_string();
     let module_name = string_to_ident("tests_checked_ops_", &name_str);
@@ -56,7 +60,7 @@ pub fn generate_checked_ops(characteristics: DecimalCharacteristics) -> proc_mac
 
             #[test]
             fn test_underflow_checked_sub() {
-                let min = #struct_name::new(0);
+                let min = #struct_name::new(#underlying_type::MIN);
                 let result = min.checked_sub(#struct_name::new(1));
 
                 assert_eq!(result, Err("checked_sub: (self - rhs) subtraction underflow".to_string()));
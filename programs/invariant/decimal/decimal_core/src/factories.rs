@@ -16,6 +16,26 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
 
     let module_name = string_to_ident("tests_factories_", &name_str);
 
+    // with no fractional part every underlying value is a valid integer
+    let max_integer_asserts = if scale == 0 {
+        quote! {
+            assert_eq!(
+                #struct_name::checked_from_integer(#struct_name::max_value()),
+                Ok(#struct_name::new(#struct_name::max_value()))
+            );
+        }
+    } else {
+        quote! {
+            let max_integer = #struct_name::max_value() / #struct_name::one::<#underlying_type>();
+            assert_eq!(
+                #struct_name::checked_from_integer(max_integer),
+                Ok(#struct_name::new(max_integer * #struct_name::one::<#underlying_type>()))
+            );
+            assert!(#struct_name::checked_from_integer(max_integer + 1).is_err());
+            assert!(#struct_name::checked_from_integer(#struct_name::max_value()).is_err());
+        }
+    };
+
     proc_macro::TokenStream::from(quote!(
 
         impl<T> Factories<T> for #struct_name
@@ -24,9 +44,12 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
             T: TryFrom<u128>,
             T: TryInto<#underlying_type>,
             T: From<u8>,
+            T: Copy,
+            T: PartialOrd,
             T: num_traits::ops::checked::CheckedDiv,
             T: num_traits::ops::checked::CheckedAdd,
-            T: num_traits::ops::checked::CheckedSub
+            T: num_traits::ops::checked::CheckedSub,
+            T: num_traits::ops::checked::CheckedRem
         {
             fn from_integer(integer: T) -> Self {
                 Self::new({
@@ -38,56 +61,55 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
                 })
             }
 
+            fn checked_from_integer(integer: T) -> std::result::Result<Self, String> {
+                Ok(Self::new({
+                    let base: #underlying_type = integer.try_into()
+                        .map_err(|_| "checked_from_integer: can't convert to base")?;
+                    base
+                        .checked_mul(Self::checked_one()?)
+                        .ok_or_else(|| "checked_from_integer: (base * Self::one()) multiplication overflow")?
+                }))
+            }
+
             fn from_scale(val: T, scale: u8) -> Self {
-                Self::new(
-                    if #scale > scale {
-                        let base: #underlying_type = val.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"));
-                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).unwrap();
-                        base.checked_mul(multiplier.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))).unwrap()
-                    } else {
-                        let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).unwrap();
-                         val.checked_div(
-                            &denominator.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
-                        ).unwrap().try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
-                    }
-                )
+                Self::from_scale_rounded(val, scale, Rounding::Down)
             }
 
             fn checked_from_scale(val: T, scale: u8) -> std::result::Result<Self, String> {
-                Ok(Self::new(
-                    if #scale > scale {
-                        let base: #underlying_type = val.try_into().map_err(|_| "checked_from_scale: can't convert to base")?;
-                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).ok_or_else(|| "checked_from_scale: multiplier overflow")?;
-                        base.checked_mul(multiplier.try_into().map_err(|_| "checked_from_scale: can't convert to multiplier")?).ok_or_else(|| "checked_from_scale: (multiplier * base) overflow")?
-                    } else {
-                        let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).ok_or_else(|| "checked_from_scale: denominator overflow")?;
-                         val.checked_div(
-                            &denominator.try_into().map_err(|_| "checked_from_scale: can't convert to denominator")?
-                        ).ok_or_else(|| "checked_from_scale: (base / denominator) overflow")?
-                        .try_into().map_err(|_| "checked_from_scale: can't convert to result")?
-                    }
-                ))
+                Self::checked_from_scale_rounded(val, scale, Rounding::Down)
             }
 
             fn from_scale_up(val: T, scale: u8) -> Self {
-                Self::new(
+                Self::from_scale_rounded(val, scale, Rounding::Up)
+            }
+
+            fn checked_from_scale_up(val: T, scale: u8) -> std::result::Result<Self, String> {
+                Self::checked_from_scale_rounded(val, scale, Rounding::Up)
+            }
+
+            fn from_scale_rounded(val: T, scale: u8, rounding: Rounding) -> Self {
+                Self::checked_from_scale_rounded(val, scale, rounding)
+                    .unwrap_or_else(|e| std::panic!("decimal: {} in method {}::from_scale_rounded()", e, #name_str))
+            }
+
+            fn checked_from_scale_rounded(val: T, scale: u8, rounding: Rounding) -> std::result::Result<Self, String> {
+                let method = match rounding {
+                    Rounding::Down => "checked_from_scale",
+                    Rounding::Up => "checked_from_scale_up",
+                    Rounding::Nearest => "checked_from_scale_rounded",
+                };
+                Ok(Self::new(
                     if #scale > scale {
-                        let base: #underlying_type = val.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"));
-                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).unwrap();
-                        base.checked_mul(multiplier.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))).unwrap()
+                        let base: #underlying_type = val.try_into().map_err(|_| format!("{}: can't convert to base", method))?;
+                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).ok_or_else(|| format!("{}: multiplier overflow", method))?;
+                        base.checked_mul(multiplier.try_into().map_err(|_| format!("{}: can't convert to multiplier", method))?).ok_or_else(|| format!("{}: (multiplier * base) overflow", method))?
                     } else {
-                        let multiplier: u128 = 10u128.checked_pow((scale - #scale) as u32).unwrap();
-                        let denominator: T = multiplier.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"));
-                        val
-                        .checked_add(
-                            &denominator.checked_sub(&T::from(1u8)).unwrap()
-                        ).unwrap()
-                        .checked_div(
-                            &denominator
-                        ).unwrap()
-                        .try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
+                        let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).ok_or_else(|| format!("{}: denominator overflow", method))?;
+                        let denominator: T = denominator.try_into().map_err(|_| format!("{}: can't convert to denominator", method))?;
+                        rounding.checked_div(val, denominator).ok_or_else(|| format!("{}: (base / denominator) overflow", method))?
+                        .try_into().map_err(|_| format!("{}: can't convert to result", method))?
                     }
-                )
+                ))
             }
         }
 
@@ -106,6 +128,18 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
             fn from_decimal_up(other: T) -> Self {
                 Self::from_scale_up(other.get(), T::scale())
             }
+
+            fn checked_from_decimal_up(other: T) -> std::result::Result<Self, String> {
+                Self::checked_from_scale_up(other.get(), T::scale())
+            }
+
+            fn from_decimal_rounded(other: T, rounding: Rounding) -> Self {
+                Self::from_scale_rounded(other.get(), T::scale(), rounding)
+            }
+
+            fn checked_from_decimal_rounded(other: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                Self::checked_from_scale_rounded(other.get(), T::scale(), rounding)
+            }
         }
 
 
@@ -161,6 +195,65 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
 
             }
 
+            #[test]
+            fn test_from_scale_rounded() {
+                assert_eq!(
+                    #struct_name::from_scale_rounded(44, #scale + 1, Rounding::Down),
+                    #struct_name::new(4)
+                );
+                assert_eq!(
+                    #struct_name::from_scale_rounded(44, #scale + 1, Rounding::Up),
+                    #struct_name::new(5)
+                );
+                assert_eq!(
+                    #struct_name::from_scale_rounded(44, #scale + 1, Rounding::Nearest),
+                    #struct_name::new(4)
+                );
+                assert_eq!(
+                    #struct_name::from_scale_rounded(45, #scale + 1, Rounding::Nearest),
+                    #struct_name::new(5)
+                );
+                assert_eq!(
+                    #struct_name::checked_from_scale_rounded(42, #scale, Rounding::Nearest),
+                    Ok(#struct_name::new(42))
+                );
+                assert_eq!(
+                    #struct_name::from_decimal_rounded(#struct_name::new(42), Rounding::Nearest),
+                    #struct_name::new(42)
+                );
+            }
+
+            #[test]
+            fn test_checked_from_integer() {
+                assert_eq!(
+                    #struct_name::checked_from_integer(0),
+                    Ok(#struct_name::new(0))
+                );
+                #max_integer_asserts
+            }
+
+            #[test]
+            fn test_checked_from_scale_up() {
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(0, 3).unwrap(),
+                    #struct_name::new(0)
+                );
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(42, #scale).unwrap(),
+                    #struct_name::new(42)
+                );
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(42, #scale + 1).unwrap(),
+                    #struct_name::new(5)
+                );
+
+                let max_val = #struct_name::max_value();
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(max_val, 100_000).is_err(),
+                    true
+                );
+            }
+
             #[test]
             fn test_checked_from_scale() {
                 assert_eq!(
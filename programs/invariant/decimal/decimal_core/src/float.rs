@@ -0,0 +1,82 @@
+use quote::quote;
+
+use crate::utils::string_to_ident;
+use crate::DecimalCharacteristics;
+
+pub fn generate_float(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        scale,
+        signed,
+        ..
+    } = characteristics;
+
+    let name_str = &struct_name.to_string();
+
+    let module_name = string_to_ident("tests_float_", name_str);
+
+    let negative_test = match signed {
+        true => quote!(
+            assert_eq!(#struct_name::from_integer(-2).to_f64(), -2.0);
+            assert_eq!(#struct_name::from_f64_approx(-2.0).to_f64(), -2.0);
+        ),
+        false => quote!(
+            assert!(#struct_name::checked_from_f64_approx(-1.0).is_err());
+        ),
+    };
+
+    proc_macro::TokenStream::from(quote!(
+        impl FloatApprox for #struct_name {
+            fn to_f64(self) -> f64 {
+                self.get() as f64 / 10f64.powi(#scale as i32)
+            }
+
+            fn from_f64_approx(value: f64) -> Self {
+                Self::checked_from_f64_approx(value)
+                    .unwrap_or_else(|e| std::panic!("decimal: {} in method {}::from_f64_approx()", e, #name_str))
+            }
+
+            fn checked_from_f64_approx(value: f64) -> std::result::Result<Self, String> {
+                let scaled = (value * 10f64.powi(#scale as i32)).round();
+                if !scaled.is_finite() {
+                    return Err(format!("checked_from_f64_approx: {} is not a finite number", value));
+                }
+                if scaled < #underlying_type::MIN as f64 || scaled > #underlying_type::MAX as f64 {
+                    return Err(format!("checked_from_f64_approx: {} doesn't fit into {}", value, #name_str));
+                }
+                Ok(Self::new(scaled as #underlying_type))
+            }
+        }
+
+        #[cfg(test)]
+        pub mod #module_name {
+            use super::*;
+
+            #[test]
+            fn test_to_f64() {
+                assert_eq!(#struct_name::new(0).to_f64(), 0.0);
+                assert_eq!(#struct_name::from_integer(2).to_f64(), 2.0);
+                assert_eq!(
+                    #struct_name::new(1).to_f64(),
+                    1.0 / 10f64.powi(#scale as i32)
+                );
+            }
+
+            #[test]
+            fn test_from_f64_approx() {
+                assert_eq!(#struct_name::from_f64_approx(0.0), #struct_name::new(0));
+                // big scales aren't exact, 2 * 10^24 has no f64 representation
+                assert_eq!(#struct_name::from_f64_approx(2.0).to_f64(), 2.0);
+                assert_eq!(
+                    #struct_name::from_f64_approx(#struct_name::new(1).to_f64()),
+                    #struct_name::new(1)
+                );
+                assert!(#struct_name::checked_from_f64_approx(f64::NAN).is_err());
+                assert!(#struct_name::checked_from_f64_approx(f64::INFINITY).is_err());
+                assert!(#struct_name::checked_from_f64_approx(f64::MAX).is_err());
+                #negative_test
+            }
+        }
+    ))
+}
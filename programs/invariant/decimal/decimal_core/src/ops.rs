@@ -45,15 +45,17 @@ pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::Toke
 
             fn mul(self, rhs: T) -> Self {
                 Self::new(
-                    self.get()
-                        .checked_mul(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul()", #underlying_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str))
-                        .checked_div(T::one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str))
+                    Rounding::Down.checked_div(
+                        self.get()
+                            .checked_mul(
+                                rhs.get()
+                                    .try_into()
+                                    .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul()", #underlying_str, #name_str))
+                            )
+                            .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str)),
+                        T::one()
+                    )
+                    .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str))
                 )
             }
         }
@@ -66,15 +68,15 @@ pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::Toke
 
             fn div(self, rhs: T) -> Self {
                 Self::new(
-                    self.get()
-                        .checked_mul(T::one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div()", #name_str))
-                        .checked_div(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div()", #underlying_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div()", #name_str))
+                    Rounding::Down.checked_div(
+                        self.get()
+                            .checked_mul(T::one())
+                            .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div()", #name_str)),
+                        rhs.get()
+                            .try_into()
+                            .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div()", #underlying_str, #name_str))
+                    )
+                    .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div()", #name_str))
                 )
             }
         }
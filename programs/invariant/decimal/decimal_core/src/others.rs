@@ -7,12 +7,27 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
     let DecimalCharacteristics {
         struct_name,
         underlying_type,
+        signed,
         ..
     } = characteristics;
 
     let name_str = &struct_name.to_string();
     let underlying_str = &underlying_type.to_string();
 
+    // the sign is printed separately, -0.5 has no integer part to carry it
+    let parts = match signed {
+        true => quote!((
+            if self.get() < 0 { "-" } else { "" },
+            self.get().checked_div(Self::one()).unwrap().unsigned_abs(),
+            self.get().checked_rem(Self::one()).unwrap().unsigned_abs()
+        )),
+        false => quote!((
+            "",
+            self.get().checked_div(Self::one()).unwrap(),
+            self.get().checked_rem(Self::one()).unwrap()
+        )),
+    };
+
     let module_name = string_to_ident("tests_others_", &name_str);
 
     proc_macro::TokenStream::from(quote!(
@@ -22,41 +37,77 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
         {
             fn mul_up(self, rhs: T) -> Self {
                 Self::new(
-                    self.get()
-                        .checked_mul(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul_up()", #underlying_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
-                        .checked_add(T::almost_one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
-                        .checked_div(T::one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
+                    Rounding::Up.checked_div(
+                        self.get()
+                            .checked_mul(
+                                rhs.get()
+                                    .try_into()
+                                    .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul_up()", #underlying_str, #name_str))
+                            )
+                            .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str)),
+                        T::one()
+                    )
+                    .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
                 )
             }
 
             fn div_up(self, rhs: T) -> Self {
                 Self::new(
-                    self.get()
-                        .checked_mul(T::one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
-                        .checked_add(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div_up()", #underlying_str, #name_str))
-                                .checked_sub(#underlying_type::try_from(1u128).unwrap())
-                                .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
-                            )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
-                        .checked_div(
-                            rhs.get()
-                                .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div_up()", #underlying_str, #name_str))
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
+                    Rounding::Up.checked_div(
+                        self.get()
+                            .checked_mul(T::one())
+                            .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str)),
+                        rhs.get()
+                            .try_into()
+                            .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div_up()", #underlying_str, #name_str))
+                    )
+                    .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
                 )
             }
+
+            fn checked_mul(self, rhs: T) -> std::result::Result<Self, String> {
+                let numerator = self.get()
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_mul: can't convert rhs to underlying_type")?
+                    ).ok_or_else(|| "checked_mul: (self * rhs) multiplication overflow")?;
+                Ok(Self::new(
+                    Rounding::Down.checked_div(numerator, T::checked_one()?)
+                    .ok_or_else(|| "checked_mul: ((self * rhs) / rhs::one()) division overflow")?
+                ))
+            }
+
+            fn checked_mul_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let numerator = self.get()
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_mul_up: can't convert rhs to underlying_type")?
+                    ).ok_or_else(|| "checked_mul_up: (self * rhs) multiplication overflow")?;
+                Ok(Self::new(
+                    Rounding::Up.checked_div(numerator, T::checked_one()?)
+                    .ok_or_else(|| "checked_mul_up: ((self * rhs) / rhs::one()) rounded division overflow")?
+                ))
+            }
+
+            fn checked_div(self, rhs: T) -> std::result::Result<Self, String> {
+                let numerator = self.get()
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_div: (self * rhs::one()) multiplication overflow")?;
+                Ok(Self::new(
+                    Rounding::Down.checked_div(
+                        numerator,
+                        rhs.get().try_into().map_err(|_| "checked_div: can't convert rhs to underlying_type")?
+                    ).ok_or_else(|| "checked_div: ((self * rhs::one()) / rhs) division overflow")?
+                ))
+            }
+
+            fn checked_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let numerator = self.get()
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_div_up: (self * rhs::one()) multiplication overflow")?;
+                Ok(Self::new(
+                    Rounding::Up.checked_div(
+                        numerator,
+                        rhs.get().try_into().map_err(|_| "checked_div_up: can't convert rhs to underlying_type")?
+                    ).ok_or_else(|| "checked_div_up: ((self * rhs::one()) / rhs) rounded division overflow")?
+                ))
+            }
         }
 
         impl OthersSameType for #struct_name {
@@ -71,29 +122,22 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
 
         impl std::fmt::Display for #struct_name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let (sign, integer, fraction) = #parts;
                 if Self::scale() > 0 {
-                    let mut decimal_places = self.get().checked_rem(Self::one()).unwrap();
-                    let mut non_zero_tail = 0;
-
-                    while decimal_places > 0 {
-                        non_zero_tail += 1;
-                        decimal_places /= 10;
-                    }
-
                     write!(
                         f,
-                        "{}.{}{}",
-                        self.get().checked_div(Self::one()).unwrap(),
-                        "0".repeat((Self::scale() - non_zero_tail).into()),
-                        self.get().checked_rem(Self::one()).unwrap()
+                        "{}{}.{:0>width$}",
+                        sign,
+                        integer,
+                        fraction,
+                        width = Self::scale() as usize
                     )
                 } else {
-                    write!(f, "{}", self.get())
+                    write!(f, "{}{}", sign, integer)
                 }
             }
         }
 
-
         #[cfg(test)]
         pub mod #module_name {
             use super::*;
@@ -112,6 +156,46 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
                 assert_eq!(a.div_up(b), a);
             }
 
+            #[test]
+            fn test_checked_mul_div() {
+                let a = #struct_name::new(1);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_mul(b), Ok(a));
+                assert_eq!(a.checked_mul_up(b), Ok(a));
+                assert_eq!(a.checked_div(b), Ok(a));
+                assert_eq!(a.checked_div_up(b), Ok(a));
+            }
+
+            #[test]
+            fn test_overflow_checked_mul_div() {
+                let max = #struct_name::max_instance();
+                let two = #struct_name::from_integer(2);
+                let zero = #struct_name::new(0);
+                assert!(max.checked_mul(two).is_err());
+                assert!(max.checked_mul_up(two).is_err());
+                assert!(max.checked_div_up(zero).is_err());
+                assert!(#struct_name::new(1).checked_div(zero).is_err());
+            }
+
+            #[test]
+            fn test_display() {
+                let zero = #struct_name::new(0);
+                let one = #struct_name::from_integer(1);
+                let smallest = #struct_name::new(1);
+                if #struct_name::scale() > 0 {
+                    let zeros = "0".repeat(#struct_name::scale() as usize);
+                    assert_eq!(zero.to_string(), format!("0.{}", zeros));
+                    assert_eq!(one.to_string(), format!("1.{}", zeros));
+                    assert_eq!(
+                        smallest.to_string(),
+                        format!("0.{}1", "0".repeat(#struct_name::scale() as usize - 1))
+                    );
+                } else {
+                    assert_eq!(zero.to_string(), "0");
+                    assert_eq!(one.to_string(), "1");
+                }
+            }
+
             #[test]
             fn test_sub_abs() {
                 let a = #struct_name::new(1);
@@ -0,0 +1,164 @@
+use quote::quote;
+
+use crate::utils::string_to_ident;
+use crate::DecimalCharacteristics;
+
+pub fn generate_parse(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        scale,
+        signed,
+        ..
+    } = characteristics;
+
+    let name_str = &struct_name.to_string();
+
+    // the magnitude is parsed as u128, only signed types accept a leading minus
+    let (split_sign, apply_sign) = match signed {
+        true => (
+            quote!(match value.trim().strip_prefix('-') {
+                Some(magnitude) => (true, magnitude),
+                None => (false, value.trim()),
+            }),
+            // shifted by one, so the magnitude of MIN doesn't have to fit into the type
+            quote!(match negative && value > 0 {
+                true => #underlying_type::try_from(value - 1).map(|value| -value - 1),
+                false => #underlying_type::try_from(value),
+            }.map_err(|_| format!("from_decimal_string: value doesn't fit into {}", #name_str))?),
+        ),
+        false => (
+            quote!((false, value.trim())),
+            quote!(value.try_into().map_err(
+                |_| format!("from_decimal_string: value doesn't fit into {}", #name_str)
+            )?),
+        ),
+    };
+
+    let module_name = string_to_ident("tests_parse_", name_str);
+
+    proc_macro::TokenStream::from(quote!(
+        impl FromDecimalString for #struct_name {
+            fn from_decimal_string(value: &str, rounding: Rounding) -> std::result::Result<Self, String> {
+                let (negative, magnitude): (bool, &str) = #split_sign;
+                let (integer, fraction) = match magnitude.split_once('.') {
+                    Some((integer, fraction)) => (integer, fraction),
+                    None => (magnitude, ""),
+                };
+                if integer.is_empty() && fraction.is_empty() {
+                    return Err(format!("from_decimal_string: empty value for {}", #name_str));
+                }
+                if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+                    return Err(format!("from_decimal_string: invalid digit in {:?}", value));
+                }
+
+                // digits over the scale are only used for rounding
+                let scale = #scale as usize;
+                let (kept, dropped) = fraction.split_at(std::cmp::min(fraction.len(), scale));
+
+                let integer: u128 = match integer.is_empty() {
+                    true => 0,
+                    false => integer.parse().map_err(|_| "from_decimal_string: integer part overflow")?,
+                };
+                let kept: u128 = match kept.is_empty() {
+                    true => 0,
+                    false => kept.parse::<u128>().map_err(|_| "from_decimal_string: fraction overflow")?
+                        .checked_mul(10u128.pow((scale - kept.len()) as u32))
+                        .ok_or_else(|| "from_decimal_string: fraction overflow")?,
+                };
+
+                let round_up = match rounding.for_magnitude(negative) {
+                    Rounding::Down => false,
+                    Rounding::Up => dropped.chars().any(|c| c != '0'),
+                    Rounding::Nearest => dropped.chars().next().map_or(false, |c| c >= '5'),
+                };
+
+                let value = integer
+                    .checked_mul(Self::checked_one::<u128>()?)
+                    .ok_or_else(|| "from_decimal_string: (integer * Self::one()) multiplication overflow")?
+                    .checked_add(kept)
+                    .ok_or_else(|| "from_decimal_string: (integer + fraction) addition overflow")?
+                    .checked_add(round_up as u128)
+                    .ok_or_else(|| "from_decimal_string: rounding overflow")?;
+
+                Ok(Self::new(#apply_sign))
+            }
+        }
+
+        // parsing is exact, use `from_decimal_string` to round
+        impl std::str::FromStr for #struct_name {
+            type Err = String;
+
+            fn from_str(value: &str) -> std::result::Result<Self, String> {
+                let down = Self::from_decimal_string(value, Rounding::Down)?;
+                let up = Self::from_decimal_string(value, Rounding::Up)?;
+                if down != up {
+                    return Err(format!("from_str: {:?} has more decimal places than {} supports", value, #name_str));
+                }
+                Ok(down)
+            }
+        }
+
+        #[cfg(test)]
+        pub mod #module_name {
+            use super::*;
+
+            #[test]
+            fn test_from_decimal_string() {
+                assert_eq!(
+                    #struct_name::from_decimal_string("0", Rounding::Down),
+                    Ok(#struct_name::new(0))
+                );
+                assert_eq!(
+                    #struct_name::from_decimal_string("1", Rounding::Down),
+                    Ok(#struct_name::from_integer(1))
+                );
+                assert_eq!(
+                    #struct_name::from_decimal_string("1.", Rounding::Down),
+                    Ok(#struct_name::from_integer(1))
+                );
+                assert!(#struct_name::from_decimal_string("", Rounding::Down).is_err());
+                assert!(#struct_name::from_decimal_string(".", Rounding::Down).is_err());
+                assert_eq!(
+                    #struct_name::from_decimal_string("-1", Rounding::Down).is_ok(),
+                    #signed
+                );
+                assert!(#struct_name::from_decimal_string("1.2.3", Rounding::Down).is_err());
+            }
+
+            #[test]
+            fn test_from_decimal_string_rounding() {
+                let value = format!("0.{}5", "0".repeat(#scale as usize));
+                assert_eq!(
+                    #struct_name::from_decimal_string(&value, Rounding::Down),
+                    Ok(#struct_name::new(0))
+                );
+                assert_eq!(
+                    #struct_name::from_decimal_string(&value, Rounding::Up),
+                    Ok(#struct_name::new(1))
+                );
+                assert_eq!(
+                    #struct_name::from_decimal_string(&value, Rounding::Nearest),
+                    Ok(#struct_name::new(1))
+                );
+            }
+
+            #[test]
+            fn test_from_str_round_trip() {
+                let values = [
+                    #struct_name::new(0),
+                    #struct_name::new(1),
+                    #struct_name::from_integer(1),
+                    #struct_name::max_instance(),
+                    #struct_name::new(#underlying_type::MIN),
+                ];
+                for value in values.iter() {
+                    assert_eq!(value.to_string().parse::<#struct_name>(), Ok(*value));
+                }
+
+                let too_precise = format!("1.{}1", "0".repeat(#scale as usize));
+                assert!(too_precise.parse::<#struct_name>().is_err());
+            }
+        }
+    ))
+}
@@ -0,0 +1,29 @@
+use quote::quote;
+
+use crate::DecimalCharacteristics;
+
+// values are (de)serialized as decimal strings, u128 doesn't fit into a JSON number
+pub fn generate_serde(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
+    let DecimalCharacteristics { struct_name, .. } = characteristics;
+
+    proc_macro::TokenStream::from(quote!(
+        impl serde::Serialize for #struct_name {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #struct_name {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                value.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    ))
+}
@@ -8,4 +8,5 @@ pub struct DecimalCharacteristics {
     pub underlying_type: Ident,
     pub big_type: Ident,
     pub scale: u8,
+    pub signed: bool,
 }
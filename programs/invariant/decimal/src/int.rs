@@ -0,0 +1,246 @@
+//! Large signed int type
+
+use core::cmp::Ordering;
+
+use crate::uint::U256;
+
+// big type of signed decimals, U256 magnitude with a sign, zero is never negative
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct I256 {
+    negative: bool,
+    magnitude: U256,
+}
+
+impl I256 {
+    pub fn new(negative: bool, magnitude: U256) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.negative
+    }
+
+    pub fn magnitude(self) -> U256 {
+        self.magnitude
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        if self.negative == rhs.negative {
+            return Some(Self::new(
+                self.negative,
+                self.magnitude.checked_add(rhs.magnitude)?,
+            ));
+        }
+        Some(match self.magnitude >= rhs.magnitude {
+            true => Self::new(self.negative, self.magnitude - rhs.magnitude),
+            false => Self::new(rhs.negative, rhs.magnitude - self.magnitude),
+        })
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(Self::new(!rhs.negative, rhs.magnitude))
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative != rhs.negative,
+            self.magnitude.checked_mul(rhs.magnitude)?,
+        ))
+    }
+
+    // truncates toward zero like division of primitive signed types
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative != rhs.negative,
+            self.magnitude.checked_div(rhs.magnitude)?,
+        ))
+    }
+
+    // takes the sign of the dividend like remainder of primitive signed types
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative,
+            self.magnitude.checked_rem(rhs.magnitude)?,
+        ))
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_unsigned_conversions {
+    ($($t:ty),*) => {$(
+        impl From<$t> for I256 {
+            fn from(value: $t) -> Self {
+                Self::new(false, U256::from(value))
+            }
+        }
+
+        impl TryFrom<I256> for $t {
+            type Error = String;
+
+            fn try_from(value: I256) -> Result<Self, Self::Error> {
+                if value.negative || value.magnitude > U256::from(<$t>::MAX) {
+                    return Err(format!("I256: value doesn't fit into {}", stringify!($t)));
+                }
+                Ok(value.magnitude.as_u128() as $t)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed_conversions {
+    ($($t:ty),*) => {$(
+        impl From<$t> for I256 {
+            fn from(value: $t) -> Self {
+                Self::new(value < 0, U256::from(value.unsigned_abs()))
+            }
+        }
+
+        impl TryFrom<I256> for $t {
+            type Error = String;
+
+            fn try_from(value: I256) -> Result<Self, Self::Error> {
+                // the negative range is one longer than the positive one
+                let limit = match value.negative {
+                    true => U256::from(<$t>::MIN.unsigned_abs()),
+                    false => U256::from(<$t>::MAX),
+                };
+                if value.magnitude > limit {
+                    return Err(format!("I256: value doesn't fit into {}", stringify!($t)));
+                }
+                let result = value.magnitude.as_u128() as $t;
+                Ok(match value.negative {
+                    true => result.wrapping_neg(),
+                    false => result,
+                })
+            }
+        }
+    )*};
+}
+
+impl_unsigned_conversions!(u8, u16, u32, u64, u128);
+impl_signed_conversions!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_ops {
+    ($($trait:ident, $method:ident, $checked:ident);*) => {$(
+        impl core::ops::$trait for I256 {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$checked(rhs)
+                    .unwrap_or_else(|| std::panic!("I256: overflow in {}", stringify!($method)))
+            }
+        }
+    )*};
+}
+
+impl_ops!(
+    Add, add, checked_add;
+    Sub, sub, checked_sub;
+    Mul, mul, checked_mul;
+    Div, div, checked_div;
+    Rem, rem, checked_rem
+);
+
+// lets I256 be used by the generic rounding division
+impl num_traits::CheckedAdd for I256 {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        I256::checked_add(*self, *v)
+    }
+}
+
+impl num_traits::CheckedSub for I256 {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        I256::checked_sub(*self, *v)
+    }
+}
+
+impl num_traits::CheckedDiv for I256 {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        I256::checked_div(*self, *v)
+    }
+}
+
+impl num_traits::CheckedRem for I256 {
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        I256::checked_rem(*self, *v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rounding;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(i128::try_from(I256::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(i128::try_from(I256::from(i128::MAX)), Ok(i128::MAX));
+        assert_eq!(i8::try_from(I256::from(-128i16)), Ok(-128));
+        assert!(i8::try_from(I256::from(-129i16)).is_err());
+        assert!(i8::try_from(I256::from(128u8)).is_err());
+        assert_eq!(u128::try_from(I256::from(u128::MAX)), Ok(u128::MAX));
+        assert!(u128::try_from(I256::from(-1)).is_err());
+        assert_eq!(I256::new(true, U256::zero()), I256::from(0));
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = I256::from(-7);
+        let b = I256::from(2);
+
+        assert_eq!(a.checked_add(b), Some(I256::from(-5)));
+        assert_eq!(b.checked_add(a), Some(I256::from(-5)));
+        assert_eq!(a.checked_sub(a), Some(I256::from(0)));
+        assert_eq!(b.checked_sub(a), Some(I256::from(9)));
+        assert_eq!(a.checked_mul(b), Some(I256::from(-14)));
+        assert_eq!(a.checked_mul(a), Some(I256::from(49)));
+        assert_eq!(a.checked_div(b), Some(I256::from(-3)));
+        assert_eq!(a.checked_rem(b), Some(I256::from(-1)));
+        assert_eq!(b.checked_rem(a), Some(I256::from(2)));
+        assert_eq!(a.checked_div(I256::from(0)), None);
+        assert_eq!(I256::new(true, U256::MAX).checked_sub(I256::from(1)), None);
+
+        assert!(a < b);
+        assert!(a < I256::from(-6));
+        assert!(I256::from(0) > a);
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(
+            Rounding::Down.checked_div(I256::from(-5), I256::from(2)),
+            Some(I256::from(-3))
+        );
+        assert_eq!(
+            Rounding::Up.checked_div(I256::from(-5), I256::from(2)),
+            Some(I256::from(-2))
+        );
+        assert_eq!(
+            Rounding::Nearest.checked_div(I256::from(-4), I256::from(3)),
+            Some(I256::from(-1))
+        );
+        assert_eq!(
+            Rounding::Up.checked_div(I256::from(-5), I256::from(-2)),
+            Some(I256::from(3))
+        );
+    }
+}
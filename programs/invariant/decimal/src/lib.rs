@@ -1,10 +1,16 @@
+mod int;
+mod rounding;
 mod traits;
 mod uint;
 
+pub use crate::int::I256;
+pub use crate::rounding::Rounding;
 pub use crate::uint::U256;
 
 pub use decimal_core::decimal;
 pub use num_traits;
+#[cfg(feature = "serde")]
+pub use serde;
 pub use traits::*;
 
 #[cfg(test)]
@@ -24,6 +30,16 @@ struct Q {
 #[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
 struct N(u8);
 
+#[cfg(test)]
+#[decimal(3)]
+#[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
+struct S(i64);
+
+#[cfg(test)]
+#[decimal(12)]
+#[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
+struct L(i128);
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -275,4 +291,228 @@ pub mod tests {
             assert_eq!(a.div_up(b), Q::new(5));
         }
     }
+
+    #[test]
+    fn test_checked_ops_between_decimals() {
+        // same results as the panicking versions
+        {
+            let a = Q::from_integer(2);
+            let b = R::from_integer(3);
+            assert_eq!(a.checked_big_mul(b), Ok(a.big_mul(b)));
+            assert_eq!(a.checked_big_mul_up(b), Ok(a.big_mul_up(b)));
+            assert_eq!(a.checked_big_div(b), Ok(a.big_div(b)));
+            assert_eq!(a.checked_big_div_up(b), Ok(a.big_div_up(b)));
+            assert_eq!(a.checked_mul(b), Ok(a * b));
+            assert_eq!(a.checked_div(b), Ok(a / b));
+        }
+        // rounding
+        {
+            let a = Q::new(42);
+            let b = R::from_integer(10);
+            assert_eq!(a.checked_div(b), Ok(Q::new(4)));
+            assert_eq!(a.checked_div_up(b), Ok(Q::new(5)));
+            assert_eq!(Q::checked_from_decimal_up(R(42)), Ok(Q::new(1)));
+        }
+        // overflow is returned instead of a panic
+        {
+            let a = Q::new(u16::MAX);
+            let b = N::from_integer(2);
+            assert!(a.checked_big_mul(b).is_err());
+            assert!(a.checked_mul(b).is_err());
+            assert!(N::checked_from_decimal(R::new(u32::MAX)).is_err());
+            assert!(a.checked_big_div(N::new(0)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        assert_eq!(R::new(1).to_string(), "0.001");
+        assert_eq!(R::new(1001).to_string(), "1.001");
+        assert_eq!(Q::new(15).to_string(), "1.5");
+        assert_eq!(N::new(7).to_string(), "7");
+
+        assert_eq!("1.001".parse::<R>(), Ok(R::new(1001)));
+        assert_eq!(".5".parse::<Q>(), Ok(Q::new(5)));
+        assert!("1.0001".parse::<R>().is_err());
+        assert_eq!("1.0000".parse::<R>(), Ok(R::from_integer(1)));
+
+        assert_eq!(
+            R::from_decimal_string("1.0004", Rounding::Down),
+            Ok(R::new(1000))
+        );
+        assert_eq!(
+            R::from_decimal_string("1.0004", Rounding::Up),
+            Ok(R::new(1001))
+        );
+        assert_eq!(
+            R::from_decimal_string("1.0004", Rounding::Nearest),
+            Ok(R::new(1000))
+        );
+        assert_eq!(
+            R::from_decimal_string("1.0005", Rounding::Nearest),
+            Ok(R::new(1001))
+        );
+        assert_eq!(
+            Q::from_decimal_string("6553.55", Rounding::Down),
+            Ok(Q::new(u16::MAX))
+        );
+        assert!(Q::from_decimal_string("6553.55", Rounding::Up).is_err());
+        assert!(N::from_decimal_string("256", Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn test_rounded_between_decimals() {
+        // 0.045 -> 0.0 / 0.1 / 0.0
+        assert_eq!(Q::from_decimal_rounded(R(45), Rounding::Down), Q::new(0));
+        assert_eq!(Q::from_decimal_rounded(R(45), Rounding::Up), Q::new(1));
+        assert_eq!(Q::from_decimal_rounded(R(45), Rounding::Nearest), Q::new(0));
+        // 0.050 -> 0.1
+        assert_eq!(Q::from_decimal_rounded(R(50), Rounding::Nearest), Q::new(1));
+        assert_eq!(
+            Q::from_decimal(R(1050)),
+            Q::from_decimal_rounded(R(1050), Rounding::Down)
+        );
+        assert_eq!(
+            Q::from_decimal_up(R(1050)),
+            Q::from_decimal_rounded(R(1050), Rounding::Up)
+        );
+
+        // 1.5 * 0.3 = 0.45
+        assert_eq!(
+            Q::new(15).big_mul_rounded(Q::new(3), Rounding::Down),
+            Q::new(4)
+        );
+        assert_eq!(
+            Q::new(15).big_mul_rounded(Q::new(3), Rounding::Nearest),
+            Q::new(5)
+        );
+        // 1 / 3 = 0.333
+        assert_eq!(
+            R::from_integer(1).big_div_rounded(N(3), Rounding::Nearest),
+            R::new(333)
+        );
+        assert_eq!(
+            R::from_integer(2).big_div_rounded(N(3), Rounding::Nearest),
+            R::new(667)
+        );
+        assert_eq!(
+            R::from_integer(2).checked_big_div_rounded(N(0), Rounding::Nearest),
+            Err(
+                "checked_big_div_rounded: ((self * rhs::one()) / rhs) division overflow"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&R::new(1001)).unwrap(), "\"1.001\"");
+        assert_eq!(serde_json::to_string(&N::new(7)).unwrap(), "\"7\"");
+        assert_eq!(
+            serde_json::from_str::<R>("\"1.001\"").unwrap(),
+            R::new(1001)
+        );
+        assert_eq!(
+            serde_json::from_str::<Q>("\"6553.5\"").unwrap(),
+            Q::new(u16::MAX)
+        );
+        // precision loss is rejected
+        assert!(serde_json::from_str::<R>("\"1.0001\"").is_err());
+        assert!(serde_json::from_str::<R>("1001").is_err());
+    }
+
+    #[test]
+    fn test_signed() {
+        let a = S::from_scale(-25, 1);
+        assert_eq!(a, S::new(-2500));
+        assert_eq!(a.to_string(), "-2.500");
+        assert_eq!(S::new(-5).to_string(), "-0.005");
+        assert_eq!(
+            S::new(i64::MIN).to_string().parse::<S>(),
+            Ok(S::new(i64::MIN))
+        );
+        assert_eq!("-0.005".parse::<S>(), Ok(S::new(-5)));
+        assert!("-0.0005".parse::<S>().is_err());
+        assert_eq!(
+            S::from_decimal_string("-0.0005", Rounding::Down),
+            Ok(S::new(-1))
+        );
+        assert_eq!(
+            S::from_decimal_string("-0.0005", Rounding::Up),
+            Ok(S::new(0))
+        );
+        assert_eq!(
+            S::from_decimal_string("-0.0005", Rounding::Nearest),
+            Ok(S::new(-1))
+        );
+
+        // -2.5 * 0.001 = -0.0025
+        let smallest = S::new(1);
+        assert_eq!(a * smallest, S::new(-3));
+        assert_eq!(a.mul_up(smallest), S::new(-2));
+        assert_eq!(a.big_mul(smallest), S::new(-3));
+        assert_eq!(a.big_mul_up(smallest), S::new(-2));
+        assert_eq!(a.big_mul_rounded(smallest, Rounding::Nearest), S::new(-3));
+        assert_eq!(S::new(2500).big_mul(S::new(-1)), S::new(-3));
+
+        // -0.005 / 2 = -0.0025
+        let b = S::new(-5);
+        assert_eq!(b / N(2), S::new(-3));
+        assert_eq!(b.div_up(N(2)), S::new(-2));
+        assert_eq!(b.big_div(N(2)), S::new(-3));
+        assert_eq!(b.big_div_up(N(2)), S::new(-2));
+        assert_eq!(b.big_div(S::from_integer(-2)), S::new(2));
+        assert_eq!(b.big_div_up(S::from_integer(-2)), S::new(3));
+        assert_eq!(b.big_div_by_number(I256::from(2000)), S::new(-3));
+        assert_eq!(b.big_div_by_number_up(I256::from(2000)), S::new(-2));
+
+        // rounding towards the lower value when converting to a smaller scale
+        assert_eq!(Q::from_decimal(R(1050)), Q::new(10));
+        assert_eq!(S::from_decimal(S::new(-1050)), S::new(-1050));
+        assert_eq!(S::from_scale(-1050i64, 4), S::new(-105));
+        assert_eq!(S::from_scale(-1055i64, 4), S::new(-106));
+        assert_eq!(S::from_scale_up(-1055i64, 4), S::new(-105));
+
+        assert_eq!(S::new(-3).sub_abs(S::new(2)), S::new(5));
+        assert!(S::new(i64::MIN).checked_sub(S::new(1)).is_err());
+    }
+
+    #[test]
+    fn test_signed_big_ops() {
+        // intermediate products don't fit into i128
+        let a = L::from_integer(10i128.pow(13));
+        let b = L::from_integer(-(10i128.pow(12)));
+        assert_eq!(a.big_mul(b), L::from_integer(-(10i128.pow(25))));
+        assert_eq!(b.big_mul(b), L::from_integer(10i128.pow(24)));
+        assert_eq!(
+            L::from_integer(-(10i128.pow(25))).big_div(b),
+            L::from_integer(10i128.pow(13))
+        );
+
+        // -1.5 * 10^13 * (10^12 + 10^-12) = -1.5 * 10^25 - 15
+        let c = L::new(-15 * 10i128.pow(24));
+        let d = L::new(10i128.pow(24) + 1);
+        assert_eq!(
+            c.big_mul(d),
+            L::new(-15 * 10i128.pow(36) - 15 * 10i128.pow(12))
+        );
+        // (-10^18 - 5 * 10^-12) * 0.1 = -10^17 - 0.5 * 10^-12
+        let e = L::new(-(10i128.pow(30)) - 5);
+        let tenth = L::from_scale(1, 1);
+        assert_eq!(e.big_mul(tenth), L::new(-(10i128.pow(29)) - 1));
+        assert_eq!(e.big_mul_up(tenth), L::new(-(10i128.pow(29))));
+        assert_eq!(
+            e.big_mul_rounded(tenth, Rounding::Nearest),
+            L::new(-(10i128.pow(29)) - 1)
+        );
+
+        assert!(L::new(i128::MIN)
+            .checked_big_mul(L::from_integer(2))
+            .is_err());
+        assert_eq!(
+            L::new(i128::MIN).checked_big_mul(L::from_integer(1)),
+            Ok(L::new(i128::MIN))
+        );
+    }
 }
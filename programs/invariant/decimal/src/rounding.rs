@@ -0,0 +1,97 @@
+use num_traits::{CheckedAdd, CheckedDiv, CheckedRem, CheckedSub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest,
+}
+
+impl Rounding {
+    // Down and Up are floor and ceil, so they swap for the magnitude of a negative result
+    pub fn for_magnitude(self, negative: bool) -> Self {
+        match (self, negative) {
+            (Rounding::Down, true) => Rounding::Up,
+            (Rounding::Up, true) => Rounding::Down,
+            (rounding, _) => rounding,
+        }
+    }
+
+    pub fn checked_div<T>(self, numerator: T, denominator: T) -> Option<T>
+    where
+        T: Copy + PartialOrd + From<u8> + CheckedAdd + CheckedSub + CheckedDiv + CheckedRem,
+    {
+        // division truncates toward zero, the quotient is then moved away from zero if needed
+        let quotient = numerator.checked_div(&denominator)?;
+        let remainder = numerator.checked_rem(&denominator)?;
+        let zero = T::from(0u8);
+        if remainder == zero {
+            return Some(quotient);
+        }
+
+        let negative = (numerator < zero) != (denominator < zero);
+        let away_from_zero = match self.for_magnitude(negative) {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::Nearest => {
+                let abs = |value: T| match value < zero {
+                    true => zero.checked_sub(&value),
+                    false => Some(value),
+                };
+                // |d - d / 2| is half of |d| rounded up and can't overflow
+                let half = denominator.checked_sub(&denominator.checked_div(&T::from(2u8))?)?;
+                abs(remainder)? >= abs(half)?
+            }
+        };
+
+        match (away_from_zero, negative) {
+            (false, _) => Some(quotient),
+            (true, false) => quotient.checked_add(&T::from(1u8)),
+            (true, true) => quotient.checked_sub(&T::from(1u8)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+
+    #[test]
+    fn test_checked_div() {
+        assert_eq!(Rounding::Down.checked_div(5u64, 2), Some(2));
+        assert_eq!(Rounding::Up.checked_div(5u64, 2), Some(3));
+        assert_eq!(Rounding::Nearest.checked_div(5u64, 2), Some(3));
+        assert_eq!(Rounding::Nearest.checked_div(4u64, 3), Some(1));
+        assert_eq!(Rounding::Up.checked_div(6u64, 2), Some(3));
+        assert_eq!(Rounding::Up.checked_div(1u64, 0), None);
+        assert_eq!(Rounding::Up.checked_div(u64::MAX, 2), Some(u64::MAX / 2 + 1));
+
+        assert_eq!(
+            Rounding::Up.checked_div(U256::from(5u8), U256::from(2u8)),
+            Some(U256::from(3u8))
+        );
+    }
+
+    #[test]
+    fn test_checked_div_negative() {
+        assert_eq!(Rounding::Down.checked_div(-5i64, 2), Some(-3));
+        assert_eq!(Rounding::Up.checked_div(-5i64, 2), Some(-2));
+        assert_eq!(Rounding::Nearest.checked_div(-5i64, 2), Some(-3));
+        assert_eq!(Rounding::Nearest.checked_div(-4i64, 3), Some(-1));
+
+        assert_eq!(Rounding::Down.checked_div(5i64, -2), Some(-3));
+        assert_eq!(Rounding::Up.checked_div(5i64, -2), Some(-2));
+        assert_eq!(Rounding::Down.checked_div(-5i64, -2), Some(2));
+        assert_eq!(Rounding::Up.checked_div(-5i64, -2), Some(3));
+
+        assert_eq!(Rounding::Down.checked_div(-6i64, 2), Some(-3));
+        assert_eq!(Rounding::Up.checked_div(-6i64, 2), Some(-3));
+        assert_eq!(Rounding::Down.checked_div(i64::MIN, 2), Some(i64::MIN / 2));
+        assert_eq!(
+            Rounding::Nearest.checked_div(i64::MIN + 1, i64::MIN),
+            Some(1)
+        );
+        assert_eq!(Rounding::Down.checked_div(i64::MIN, -1), None);
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use crate::Rounding;
+
 pub trait Decimal {
     type U: Debug + Default;
 
@@ -16,16 +18,36 @@ pub trait Decimal {
     fn almost_one<T: TryFrom<u128>>() -> T;
 }
 
-pub trait BigOps<T> {
+pub trait BigOps<T>: Sized {
     fn big_mul(self, rhs: T) -> Self;
     fn big_mul_up(self, rhs: T) -> Self;
     fn big_div(self, rhs: T) -> Self;
     fn big_div_up(self, rhs: T) -> Self;
+    fn checked_big_mul(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_mul_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_div(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_div_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn big_mul_rounded(self, rhs: T, rounding: Rounding) -> Self;
+    fn big_div_rounded(self, rhs: T, rounding: Rounding) -> Self;
+    fn checked_big_mul_rounded(
+        self,
+        rhs: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
+    fn checked_big_div_rounded(
+        self,
+        rhs: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
-pub trait Others<T> {
+pub trait Others<T>: Sized {
     fn mul_up(self, rhs: T) -> Self;
     fn div_up(self, rhs: T) -> Self;
+    fn checked_mul(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_mul_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_div(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_div_up(self, rhs: T) -> std::result::Result<Self, String>;
 }
 
 pub trait OthersSameType {
@@ -34,20 +56,36 @@ pub trait OthersSameType {
 
 pub trait Factories<T>: Sized {
     fn from_integer(integer: T) -> Self;
+    fn checked_from_integer(integer: T) -> std::result::Result<Self, String>;
     fn from_scale(integer: T, scale: u8) -> Self;
     fn checked_from_scale(integer: T, scale: u8) -> std::result::Result<Self, String>;
     fn from_scale_up(integer: T, scale: u8) -> Self;
+    fn checked_from_scale_up(integer: T, scale: u8) -> std::result::Result<Self, String>;
+    fn from_scale_rounded(integer: T, scale: u8, rounding: Rounding) -> Self;
+    fn checked_from_scale_rounded(
+        integer: T,
+        scale: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
 pub trait BetweenDecimals<T>: Sized {
     fn from_decimal(other: T) -> Self;
     fn checked_from_decimal(other: T) -> std::result::Result<Self, String>;
     fn from_decimal_up(other: T) -> Self;
+    fn checked_from_decimal_up(other: T) -> std::result::Result<Self, String>;
+    fn from_decimal_rounded(other: T, rounding: Rounding) -> Self;
+    fn checked_from_decimal_rounded(
+        other: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
 pub trait ToValue<T, B> {
     fn big_mul_to_value(self, value: T) -> B;
     fn big_mul_to_value_up(self, value: T) -> B;
+    fn checked_big_mul_to_value(self, value: T) -> std::result::Result<B, String>;
+    fn checked_big_mul_to_value_up(self, value: T) -> std::result::Result<B, String>;
 }
 
 pub trait ByNumber<B>: Sized {
@@ -55,9 +93,26 @@ pub trait ByNumber<B>: Sized {
     fn big_div_by_number_up(self, number: B) -> Self;
     fn checked_big_div_by_number(self, number: B) -> std::result::Result<Self, String>;
     fn checked_big_div_by_number_up(self, number: B) -> std::result::Result<Self, String>;
+    fn big_div_by_number_rounded(self, number: B, rounding: Rounding) -> Self;
+    fn checked_big_div_by_number_rounded(
+        self,
+        number: B,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
 pub trait CheckedOps: Sized {
     fn checked_add(self, rhs: Self) -> std::result::Result<Self, String>;
     fn checked_sub(self, rhs: Self) -> std::result::Result<Self, String>;
 }
+
+pub trait FromDecimalString: Sized {
+    fn from_decimal_string(value: &str, rounding: Rounding) -> std::result::Result<Self, String>;
+}
+
+// lossy conversions for off-chain analytics, never use them in on-chain math
+pub trait FloatApprox: Sized {
+    fn to_f64(self) -> f64;
+    fn from_f64_approx(value: f64) -> Self;
+    fn checked_from_f64_approx(value: f64) -> std::result::Result<Self, String>;
+}
@@ -14,6 +14,31 @@ construct_uint! {
     pub struct U192(3);
 }
 
+// lets U256 be used by the generic rounding division
+impl num_traits::CheckedAdd for U256 {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        U256::checked_add(*self, *v)
+    }
+}
+
+impl num_traits::CheckedSub for U256 {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        U256::checked_sub(*self, *v)
+    }
+}
+
+impl num_traits::CheckedDiv for U256 {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        U256::checked_div(*self, *v)
+    }
+}
+
+impl num_traits::CheckedRem for U256 {
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        U256::checked_rem(*self, *v)
+    }
+}
+
 #[allow(dead_code)]
 pub const fn to_u256(n: u128) -> U256 {
     U256([n as u64, (n >> 64) as u64, 0, 0])
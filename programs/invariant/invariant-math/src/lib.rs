@@ -0,0 +1,6 @@
+pub mod decimals;
+pub mod log;
+pub mod math;
+pub mod swap;
+pub mod tickmap;
+pub mod utils;
@@ -1,5 +1,12 @@
-use crate::decimals::*;
-use crate::math::calculate_price_sqrt;
+use crate::{err, function, location, ok_or_mark_trace, trace};
+use std::convert::TryInto;
+
+use crate::{
+    decimals::*,
+    math::calculate_price_sqrt,
+    tickmap::MAX_TICK,
+    utils::{TrackableError, TrackableResult},
+};
 
 const LOG2_SCALE: u8 = 32;
 const LOG2_DOUBLE_SCALE: u8 = 64;
@@ -13,13 +20,14 @@ const LOG2_MIN_BINARY_POSITION: i32 = 15; // accuracy = 2^(-15)
 const LOG2_ACCURACY: u64 = 1u64 << (31 - LOG2_MIN_BINARY_POSITION);
 const PRICE_DENOMINATOR: u128 = 1_000000_000000_000000_000000;
 
-fn price_to_x32(decimal: Price) -> u64 {
-    decimal
+fn price_to_x32(decimal: Price) -> TrackableResult<u64> {
+    let x32 = decimal
         .v
         .checked_mul(LOG2_ONE)
-        .unwrap()
-        .checked_div(PRICE_DENOMINATOR)
-        .unwrap() as u64
+        .ok_or_else(|| err!(TrackableError::MUL))?
+        / PRICE_DENOMINATOR;
+    x32.try_into()
+        .map_err(|_| err!(&TrackableError::cast::<u64>()))
 }
 
 fn align_tick_to_spacing(accurate_tick: i32, tick_spacing: i32) -> i32 {
@@ -85,8 +93,11 @@ fn log2_iterative_approximation_x32(mut sqrt_price_x32: u64) -> (bool, u64) {
     (sign, result)
 }
 
-pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> i32 {
-    let sqrt_price_x32: u64 = price_to_x32(sqrt_price_decimal);
+pub fn get_tick_at_sqrt_price(
+    sqrt_price_decimal: Price,
+    tick_spacing: u16,
+) -> TrackableResult<i32> {
+    let sqrt_price_x32: u64 = ok_or_mark_trace!(price_to_x32(sqrt_price_decimal))?;
     let (log2_sign, log2_sqrt_price) = log2_iterative_approximation_x32(sqrt_price_x32);
 
     let abs_floor_tick: i32 = match log2_sign {
@@ -105,51 +116,76 @@ pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> i
     let farther_tick_with_spacing = align_tick_to_spacing(farther_tick, tick_spacing as i32);
     let nearer_tick_with_spacing = align_tick_to_spacing(nearer_tick, tick_spacing as i32);
     if farther_tick_with_spacing == nearer_tick_with_spacing {
-        return nearer_tick_with_spacing;
+        return Ok(nearer_tick_with_spacing);
     };
 
     let accurate_tick = match log2_sign {
         true => {
-            let farther_tick_sqrt_price_decimal = calculate_price_sqrt(farther_tick);
+            let farther_tick_sqrt_price_decimal =
+                ok_or_mark_trace!(calculate_price_sqrt(farther_tick))?;
             match sqrt_price_decimal >= farther_tick_sqrt_price_decimal {
                 true => farther_tick_with_spacing,
                 false => nearer_tick_with_spacing,
             }
         }
         false => {
-            let nearer_tick_sqrt_price_decimal = calculate_price_sqrt(nearer_tick);
+            let nearer_tick_sqrt_price_decimal =
+                ok_or_mark_trace!(calculate_price_sqrt(nearer_tick))?;
             match nearer_tick_sqrt_price_decimal <= sqrt_price_decimal {
                 true => nearer_tick_with_spacing,
                 false => farther_tick_with_spacing,
             }
         }
     };
-    match tick_spacing > 1 {
+    Ok(match tick_spacing > 1 {
         true => align_tick_to_spacing(accurate_tick, tick_spacing as i32),
         false => accurate_tick,
+    })
+}
+
+// greatest initializable tick with calculate_price_sqrt(tick) <= sqrt_price
+// the approximation is moved by whole spacings until the neighbouring prices confirm it
+// prices below the lowest initializable tick are clamped to that tick, so the result stays inside the domain
+pub fn get_tick_at_sqrt_price_exact(
+    sqrt_price_decimal: Price,
+    tick_spacing: u16,
+) -> TrackableResult<i32> {
+    let tick_spacing = tick_spacing as i32;
+    let lowest_tick = -(MAX_TICK / tick_spacing * tick_spacing);
+    // ticks outside of the domain have no price and are never crossed
+    let price_at = |tick: i32| calculate_price_sqrt(tick).ok();
+
+    let mut tick = ok_or_mark_trace!(get_tick_at_sqrt_price(
+        sqrt_price_decimal,
+        tick_spacing as u16
+    ))?;
+    while matches!(price_at(tick), Some(price) if price > sqrt_price_decimal) {
+        tick -= tick_spacing;
     }
+    while matches!(price_at(tick + tick_spacing), Some(price) if price <= sqrt_price_decimal) {
+        tick += tick_spacing;
+    }
+    Ok(tick.max(lowest_tick))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::structs::MAX_TICK;
-
     use super::*;
 
     #[test]
     fn test_price_to_u64() {
         // min sqrt price -> sqrt(1.0001)^MIN_TICK
         {
-            let min_sqrt_price_decimal = calculate_price_sqrt(-MAX_TICK);
-            let min_sqrt_price_x32 = price_to_x32(min_sqrt_price_decimal);
+            let min_sqrt_price_decimal = calculate_price_sqrt(-MAX_TICK).unwrap();
+            let min_sqrt_price_x32 = price_to_x32(min_sqrt_price_decimal).unwrap();
 
             let expected_min_sqrt_price_x32 = 65536;
             assert_eq!(min_sqrt_price_x32, expected_min_sqrt_price_x32);
         }
         // max sqrt price -> sqrt(1.0001)^MAX_TICK
         {
-            let max_sqrt_price_decimal = calculate_price_sqrt(MAX_TICK);
-            let max_sqrt_price_x32 = price_to_x32(max_sqrt_price_decimal);
+            let max_sqrt_price_decimal = calculate_price_sqrt(MAX_TICK).unwrap();
+            let max_sqrt_price_x32 = price_to_x32(max_sqrt_price_decimal).unwrap();
 
             let expected_max_sqrt_price_x32 = 281472330729535;
             assert_eq!(max_sqrt_price_x32, expected_max_sqrt_price_x32);
@@ -161,7 +197,7 @@ mod tests {
         // log2 of 1
         {
             let sqrt_price_decimal = Price::from_integer(1);
-            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
+            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, true);
             assert_eq!(value, 0);
@@ -169,7 +205,7 @@ mod tests {
         // log2 > 0 when x > 1
         {
             let sqrt_price_decimal = Price::from_integer(879);
-            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
+            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, true);
             assert_eq!(value, 42003464192);
@@ -177,41 +213,41 @@ mod tests {
         // log2 < 0 when x < 1
         {
             let sqrt_price_decimal = Price::from_scale(59, 4);
-            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
+            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, false);
             assert_eq!(value, 31804489728);
         }
         // log2 of max sqrt price
         {
-            let max_sqrt_price = calculate_price_sqrt(MAX_TICK);
-            let sqrt_price_x32 = price_to_x32(max_sqrt_price);
+            let max_sqrt_price = calculate_price_sqrt(MAX_TICK).unwrap();
+            let sqrt_price_x32 = price_to_x32(max_sqrt_price).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, true);
             assert_eq!(value, 68719345664);
         }
         // log2 of min sqrt price
         {
-            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK);
-            let sqrt_price_x32 = price_to_x32(min_sqrt_price);
+            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
+            let sqrt_price_x32 = price_to_x32(min_sqrt_price).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, false);
             assert_eq!(value, 68719345664);
         }
         // log2 of sqrt(1.0001^(-19_999)) - 1
         {
-            let mut sqrt_price_decimal = calculate_price_sqrt(-19_999);
+            let mut sqrt_price_decimal = calculate_price_sqrt(-19_999).unwrap();
             sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
+            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, false);
             assert_eq!(value, 6195642368);
         }
         // log2 of sqrt(1.0001^(19_999)) + 1
         {
-            let mut sqrt_price_decimal = calculate_price_sqrt(19_999);
+            let mut sqrt_price_decimal = calculate_price_sqrt(19_999).unwrap();
             sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
+            let sqrt_price_x32 = price_to_x32(sqrt_price_decimal).unwrap();
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, true);
             assert_eq!(value, 6195642368);
@@ -225,104 +261,104 @@ mod tests {
             // get tick at 1
             {
                 let sqrt_price_decimal = Price::from_integer(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
             // get tick slightly below 1
             {
                 let sqrt_price_decimal = Price::from_integer(1) - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
             // get tick slightly above 1
             {
                 let sqrt_price_decimal = Price::from_integer(1) + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
         }
         // around 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(1);
+            let sqrt_price_decimal = calculate_price_sqrt(1).unwrap();
             // get tick at sqrt(1.0001)
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 1);
             }
             // get tick slightly below sqrt(1.0001)
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
             // get tick slightly above sqrt(1.0001)
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 1);
             }
         }
         // around -1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(-1);
+            let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap();
             // get tick at sqrt(1.0001^(-1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
             // get tick slightly below sqrt(1.0001^(-1))
             {
-                let sqrt_price_decimal = calculate_price_sqrt(-1) - Price::new(1);
+                let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap() - Price::new(1);
 
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -2);
             }
             // get tick slightly above sqrt(1.0001^(-1))
             {
-                let sqrt_price_decimal = calculate_price_sqrt(-1) + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap() + Price::new(1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
         }
         // around max - 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(MAX_TICK - 1);
+            let sqrt_price_decimal = calculate_price_sqrt(MAX_TICK - 1).unwrap();
             // get tick at sqrt(1.0001^(MAX_TICK - 1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 1);
             }
             // get tick slightly below sqrt(1.0001^(MAX_TICK - 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 2);
             }
             // get tick slightly above sqrt(1.0001^(MAX_TICK - 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 1);
             }
         }
         // around min + 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(-(MAX_TICK - 1));
+            let sqrt_price_decimal = calculate_price_sqrt(-(MAX_TICK - 1)).unwrap();
             // get tick at sqrt(1.0001^(-MAX_TICK + 1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -(MAX_TICK - 1));
             }
             // get tick slightly below sqrt(1.0001^(-MAX_TICK + 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -MAX_TICK);
             }
             // get tick slightly above sqrt(1.0001^(-MAX_TICK + 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -(MAX_TICK - 1));
             }
         }
@@ -330,60 +366,60 @@ mod tests {
         {
             let max_sqrt_price = Price::from_scale(655354, 1);
             let sqrt_price_decimal = max_sqrt_price - Price::new(1);
-            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
             assert_eq!(tick, MAX_TICK);
         }
         // around 19_999 tick
         {
             let expected_tick = 19_999;
-            let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+            let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
             // get tick at sqrt(1.0001^19_999)
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
             // get tick slightly below sqrt(1.0001^19_999)
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
 
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick - 1);
             }
             // get tick slightly above sqrt(1.0001^19_999)
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
         }
         // around -19_999 tick
         {
             let expected_tick = -19_999;
-            let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+            let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
             // get tick at sqrt(1.0001^(-19_999))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
             // get tick slightly below sqrt(1.0001^(-19_999))
             {
                 // let sqrt_price_decimal = sqrt_price_decimal - Decimal::new(150);
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick - 1);
             }
             // get tick slightly above sqrt(1.0001^(-19_999))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
         }
         //get tick slightly above at min tick
         {
-            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK);
+            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
             let sqrt_price_decimal = min_sqrt_price + Price::new(1);
-            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
             assert_eq!(tick, -MAX_TICK);
         }
     }
@@ -437,22 +473,22 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let expected_tick = n;
-                let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick - 1);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
             }
@@ -464,22 +500,22 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let expected_tick = -n;
-                let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick - 1);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
             }
@@ -492,24 +528,27 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let input_tick = n;
-                let sqrt_price_decimal = calculate_price_sqrt(input_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(input_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick - 1, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
@@ -523,28 +562,86 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let input_tick = -n;
-                let sqrt_price_decimal = calculate_price_sqrt(input_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(input_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick - 1, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
             }
         }
     }
+
+    // every tick of the domain, the prices next to it and the middle of its range
+    #[test]
+    fn test_get_tick_at_sqrt_price_exact_whole_domain() {
+        let mut sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
+        for tick in -MAX_TICK..=MAX_TICK {
+            let next_sqrt_price = match tick < MAX_TICK {
+                true => calculate_price_sqrt(tick + 1).unwrap(),
+                false => sqrt_price + Price::new(2),
+            };
+            let middle = Price::new((sqrt_price.v + next_sqrt_price.v) / 2);
+            let last = next_sqrt_price - Price::new(1);
+
+            assert_eq!(get_tick_at_sqrt_price_exact(sqrt_price, 1).unwrap(), tick);
+            assert_eq!(get_tick_at_sqrt_price_exact(middle, 1).unwrap(), tick);
+            assert_eq!(get_tick_at_sqrt_price_exact(last, 1).unwrap(), tick);
+            // spacing only aligns the result down, but not below the domain
+            let expected = align_tick_to_spacing(tick, 4).max(-MAX_TICK + 2);
+            assert_eq!(
+                get_tick_at_sqrt_price_exact(sqrt_price, 4).unwrap(),
+                expected
+            );
+            assert_eq!(get_tick_at_sqrt_price_exact(last, 4).unwrap(), expected);
+
+            sqrt_price = next_sqrt_price;
+        }
+    }
+
+    #[test]
+    fn test_get_tick_at_sqrt_price_exact_outside_domain() {
+        let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
+        let max_sqrt_price = calculate_price_sqrt(MAX_TICK).unwrap();
+
+        assert_eq!(
+            get_tick_at_sqrt_price_exact(min_sqrt_price - Price::new(1), 1).unwrap(),
+            -MAX_TICK
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price_exact(Price::new(0), 1).unwrap(),
+            -MAX_TICK
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price_exact(max_sqrt_price, 1).unwrap(),
+            MAX_TICK
+        );
+        // aligned tick below the price is outside of the domain, lowest aligned tick inside is returned
+        assert_eq!(
+            get_tick_at_sqrt_price_exact(min_sqrt_price, 4).unwrap(),
+            -MAX_TICK + 2
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price_exact(max_sqrt_price, 4).unwrap(),
+            MAX_TICK - 2
+        );
+    }
 }
@@ -0,0 +1,548 @@
+use crate::{err, from_result, function, location, ok_or_mark_trace, trace};
+
+use crate::{
+    decimals::*,
+    log::get_tick_at_sqrt_price,
+    math::*,
+    tickmap::{get_search_limit, next_initialized, prev_initialized},
+    utils::{TrackableError, TrackableResult},
+};
+
+pub const REWARDS_PER_POOL: usize = 3;
+
+// pool state walked by a swap, implemented by the on-chain account and its client copy
+// fields are packed, so they are read and written by value
+pub trait PoolState {
+    fn sqrt_price(&self) -> Price;
+    fn set_sqrt_price(&mut self, sqrt_price: Price);
+    fn liquidity(&self) -> Liquidity;
+    fn set_liquidity(&mut self, liquidity: Liquidity);
+    fn current_tick_index(&self) -> i32;
+    fn set_current_tick_index(&mut self, index: i32);
+    fn tick_spacing(&self) -> u16;
+    fn fee(&self) -> FixedPoint;
+    fn protocol_fee(&self) -> FixedPoint;
+    fn fee_growth_global(&self, x: bool) -> FeeGrowth;
+    fn set_fee_growth_global(&mut self, x: bool, growth: FeeGrowth);
+    fn fee_protocol_token(&self, x: bool) -> u64;
+    fn set_fee_protocol_token(&mut self, x: bool, amount: u64);
+    fn seconds_per_liquidity_global(&self) -> FixedPoint;
+    fn set_seconds_per_liquidity_global(&mut self, value: FixedPoint);
+    fn start_timestamp(&self) -> u64;
+    fn last_timestamp(&self) -> u64;
+    fn set_last_timestamp(&mut self, timestamp: u64);
+    fn reward_growth_global(&self) -> [FeeGrowth; REWARDS_PER_POOL];
+
+    // splits the fee between the protocol, the referral and liquidity in range
+    fn add_fee(
+        &mut self,
+        amount: TokenAmount,
+        ref_percentage: FixedPoint,
+        in_x: bool,
+    ) -> TrackableResult<TokenAmount> {
+        let protocol_fee = from_result!(amount.checked_big_mul_up(self.protocol_fee()))
+            .and_then(|fee| from_result!(TokenAmount::checked_from_decimal_up(fee)))?;
+        let ref_fee = match ref_percentage.is_zero() {
+            true => TokenAmount(0),
+            false => from_result!(amount.checked_big_mul(ref_percentage))
+                .and_then(|fee| from_result!(TokenAmount::checked_from_decimal(fee)))?,
+        };
+        let pool_fee = from_result!(amount.checked_sub(protocol_fee))
+            .and_then(|fee| from_result!(fee.checked_sub(ref_fee)))?;
+
+        if (pool_fee.is_zero() && protocol_fee.is_zero()) || self.liquidity().is_zero() {
+            return Ok(ref_fee);
+        }
+        let fee_growth =
+            ok_or_mark_trace!(FeeGrowth::checked_from_fee(self.liquidity(), pool_fee))?;
+
+        let fee_protocol_token = self
+            .fee_protocol_token(in_x)
+            .checked_add(protocol_fee.0)
+            .ok_or_else(|| err!(TrackableError::ADD))?;
+        self.set_fee_growth_global(in_x, self.fee_growth_global(in_x).unchecked_add(fee_growth));
+        self.set_fee_protocol_token(in_x, fee_protocol_token);
+        Ok(ref_fee)
+    }
+
+    fn update_seconds_per_liquidity_global(
+        &mut self,
+        current_timestamp: u64,
+    ) -> TrackableResult<()> {
+        let seconds_passed = current_timestamp
+            .checked_sub(self.last_timestamp())
+            .ok_or_else(|| err!(TrackableError::SUB))?;
+        let seconds_per_liquidity =
+            from_result!(FixedPoint::from_integer(seconds_passed).checked_div(self.liquidity()))?;
+
+        self.set_seconds_per_liquidity_global(
+            self.seconds_per_liquidity_global()
+                .unchecked_add(seconds_per_liquidity),
+        );
+        self.set_last_timestamp(current_timestamp);
+        Ok(())
+    }
+}
+
+pub trait TickState {
+    fn index(&self) -> i32;
+    fn sign(&self) -> bool;
+    fn liquidity_change(&self) -> Liquidity;
+    fn fee_growth_outside(&self, x: bool) -> FeeGrowth;
+    fn set_fee_growth_outside(&mut self, x: bool, growth: FeeGrowth);
+    fn seconds_per_liquidity_outside(&self) -> FixedPoint;
+    fn set_seconds_per_liquidity_outside(&mut self, value: FixedPoint);
+    fn seconds_outside(&self) -> u64;
+    fn set_seconds_outside(&mut self, seconds: u64);
+    fn reward_growth_outside(&self) -> [FeeGrowth; REWARDS_PER_POOL];
+    fn set_reward_growth_outside(&mut self, growth: [FeeGrowth; REWARDS_PER_POOL]);
+}
+
+// both crates name the fields of their `Pool` and `Tick` accounts the same way
+#[macro_export]
+macro_rules! impl_swap_state {
+    ($pool:ty, $tick:ty) => {
+        impl $crate::swap::PoolState for $pool {
+            fn sqrt_price(&self) -> $crate::decimals::Price {
+                self.sqrt_price
+            }
+            fn set_sqrt_price(&mut self, sqrt_price: $crate::decimals::Price) {
+                self.sqrt_price = sqrt_price;
+            }
+            fn liquidity(&self) -> $crate::decimals::Liquidity {
+                self.liquidity
+            }
+            fn set_liquidity(&mut self, liquidity: $crate::decimals::Liquidity) {
+                self.liquidity = liquidity;
+            }
+            fn current_tick_index(&self) -> i32 {
+                self.current_tick_index
+            }
+            fn set_current_tick_index(&mut self, index: i32) {
+                self.current_tick_index = index;
+            }
+            fn tick_spacing(&self) -> u16 {
+                self.tick_spacing
+            }
+            fn fee(&self) -> $crate::decimals::FixedPoint {
+                self.fee
+            }
+            fn protocol_fee(&self) -> $crate::decimals::FixedPoint {
+                self.protocol_fee
+            }
+            fn fee_growth_global(&self, x: bool) -> $crate::decimals::FeeGrowth {
+                match x {
+                    true => self.fee_growth_global_x,
+                    false => self.fee_growth_global_y,
+                }
+            }
+            fn set_fee_growth_global(&mut self, x: bool, growth: $crate::decimals::FeeGrowth) {
+                match x {
+                    true => self.fee_growth_global_x = growth,
+                    false => self.fee_growth_global_y = growth,
+                }
+            }
+            fn fee_protocol_token(&self, x: bool) -> u64 {
+                match x {
+                    true => self.fee_protocol_token_x,
+                    false => self.fee_protocol_token_y,
+                }
+            }
+            fn set_fee_protocol_token(&mut self, x: bool, amount: u64) {
+                match x {
+                    true => self.fee_protocol_token_x = amount,
+                    false => self.fee_protocol_token_y = amount,
+                }
+            }
+            fn seconds_per_liquidity_global(&self) -> $crate::decimals::FixedPoint {
+                self.seconds_per_liquidity_global
+            }
+            fn set_seconds_per_liquidity_global(&mut self, value: $crate::decimals::FixedPoint) {
+                self.seconds_per_liquidity_global = value;
+            }
+            fn start_timestamp(&self) -> u64 {
+                self.start_timestamp
+            }
+            fn last_timestamp(&self) -> u64 {
+                self.last_timestamp
+            }
+            fn set_last_timestamp(&mut self, timestamp: u64) {
+                self.last_timestamp = timestamp;
+            }
+            fn reward_growth_global(
+                &self,
+            ) -> [$crate::decimals::FeeGrowth; $crate::swap::REWARDS_PER_POOL] {
+                let rewards = self.rewards;
+                let mut growth =
+                    [$crate::decimals::FeeGrowth::new(0); $crate::swap::REWARDS_PER_POOL];
+                for (i, reward) in rewards.iter().enumerate() {
+                    growth[i] = reward.growth_global;
+                }
+                growth
+            }
+        }
+
+        impl $crate::swap::TickState for $tick {
+            fn index(&self) -> i32 {
+                self.index
+            }
+            fn sign(&self) -> bool {
+                self.sign
+            }
+            fn liquidity_change(&self) -> $crate::decimals::Liquidity {
+                self.liquidity_change
+            }
+            fn fee_growth_outside(&self, x: bool) -> $crate::decimals::FeeGrowth {
+                match x {
+                    true => self.fee_growth_outside_x,
+                    false => self.fee_growth_outside_y,
+                }
+            }
+            fn set_fee_growth_outside(&mut self, x: bool, growth: $crate::decimals::FeeGrowth) {
+                match x {
+                    true => self.fee_growth_outside_x = growth,
+                    false => self.fee_growth_outside_y = growth,
+                }
+            }
+            fn seconds_per_liquidity_outside(&self) -> $crate::decimals::FixedPoint {
+                self.seconds_per_liquidity_outside
+            }
+            fn set_seconds_per_liquidity_outside(&mut self, value: $crate::decimals::FixedPoint) {
+                self.seconds_per_liquidity_outside = value;
+            }
+            fn seconds_outside(&self) -> u64 {
+                self.seconds_outside
+            }
+            fn set_seconds_outside(&mut self, seconds: u64) {
+                self.seconds_outside = seconds;
+            }
+            fn reward_growth_outside(
+                &self,
+            ) -> [$crate::decimals::FeeGrowth; $crate::swap::REWARDS_PER_POOL] {
+                self.reward_growth_outside
+            }
+            fn set_reward_growth_outside(
+                &mut self,
+                growth: [$crate::decimals::FeeGrowth; $crate::swap::REWARDS_PER_POOL],
+            ) {
+                self.reward_growth_outside = growth;
+            }
+        }
+    };
+}
+
+#[derive(Debug)]
+pub enum SwapError<E> {
+    WrongLimit,
+    LimitReached(TrackableError),
+    PriceLimitReached,
+    InvalidTickIndex,
+    NoGainSwap,
+    SwapStepOverflow(TrackableError),
+    TokenAmountOverflow(TrackableError),
+    FeeOverflow(TrackableError),
+    PriceOverflow(TrackableError),
+    // failure of the caller while crossing a tick, e.g. the tick wasn't passed
+    Cross(E),
+}
+
+pub struct SwapTotals {
+    pub amount_in: TokenAmount,
+    pub amount_out: TokenAmount,
+    pub amount_referral: TokenAmount,
+    pub amount_fee: TokenAmount,
+}
+
+// Finds closes initialized tick in direction of trade
+// and compares its price to the price limit of the trade
+pub fn get_closer_limit(
+    sqrt_price_limit: Price,
+    x_to_y: bool,
+    current_tick: i32, // tick already scaled by tick_spacing
+    tick_spacing: u16,
+    bitmap: &[u8],
+) -> TrackableResult<(Price, Option<(i32, bool)>)> {
+    // find initalized tick (None also for virtual tick limiated by search scope)
+    let closes_tick_index = if x_to_y {
+        prev_initialized(bitmap, current_tick, tick_spacing)
+    } else {
+        next_initialized(bitmap, current_tick, tick_spacing)
+    };
+
+    match closes_tick_index {
+        Some(index) => {
+            let price = ok_or_mark_trace!(calculate_price_sqrt(index))?;
+            // trunk-ignore(clippy/if_same_then_else)
+            if x_to_y && price > sqrt_price_limit {
+                Ok((price, Some((index, true))))
+            } else if !x_to_y && price < sqrt_price_limit {
+                Ok((price, Some((index, true))))
+            } else {
+                Ok((sqrt_price_limit, None))
+            }
+        }
+        None => {
+            let index = get_search_limit(current_tick, tick_spacing, !x_to_y);
+            let price = ok_or_mark_trace!(calculate_price_sqrt(index))?;
+
+            if current_tick == index {
+                return Err(err!("absolute price limit reached"));
+            }
+
+            // trunk-ignore(clippy/if_same_then_else)
+            if x_to_y && price > sqrt_price_limit {
+                Ok((price, Some((index, false))))
+            } else if !x_to_y && price < sqrt_price_limit {
+                Ok((price, Some((index, false))))
+            } else {
+                Ok((sqrt_price_limit, None))
+            }
+        }
+    }
+}
+
+pub fn cross_tick<T: TickState, P: PoolState>(
+    tick: &mut T,
+    pool: &mut P,
+    current_timestamp: u64,
+) -> TrackableResult<()> {
+    for x in [true, false] {
+        tick.set_fee_growth_outside(
+            x,
+            pool.fee_growth_global(x)
+                .unchecked_sub(tick.fee_growth_outside(x)),
+        );
+    }
+
+    // rewards are settled by the caller, crossing only flips the growth outside
+    let reward_growth_global = pool.reward_growth_global();
+    let mut reward_growth_outside = tick.reward_growth_outside();
+    for (outside, global) in reward_growth_outside
+        .iter_mut()
+        .zip(reward_growth_global.iter())
+    {
+        *outside = global.unchecked_sub(*outside);
+    }
+    tick.set_reward_growth_outside(reward_growth_outside);
+
+    let seconds_passed: u64 = current_timestamp
+        .checked_sub(pool.start_timestamp())
+        .ok_or_else(|| err!(TrackableError::SUB))?;
+    tick.set_seconds_outside(
+        seconds_passed
+            .checked_sub(tick.seconds_outside())
+            .ok_or_else(|| err!(TrackableError::SUB))?,
+    );
+
+    if !pool.liquidity().is_zero() {
+        ok_or_mark_trace!(pool.update_seconds_per_liquidity_global(current_timestamp))?;
+    } else {
+        pool.set_last_timestamp(current_timestamp);
+    }
+    tick.set_seconds_per_liquidity_outside(
+        pool.seconds_per_liquidity_global()
+            .unchecked_sub(tick.seconds_per_liquidity_outside()),
+    );
+
+    // When going to higher tick net_liquidity should be added and for going lower subtracted
+    let liquidity = if (pool.current_tick_index() >= tick.index()) ^ tick.sign() {
+        from_result!(pool.liquidity().checked_add(tick.liquidity_change()))?
+    } else {
+        from_result!(pool.liquidity().checked_sub(tick.liquidity_change()))?
+    };
+    pool.set_liquidity(liquidity);
+    Ok(())
+}
+
+// Moves the pool along the curve, `cross` is called with every initialized tick to cross
+// and is expected to load it and pass it to `cross_tick`
+// Token transfers and reward emission are left to the caller
+#[allow(clippy::too_many_arguments)]
+pub fn swap_within_pool<P: PoolState, E>(
+    pool: &mut P,
+    bitmap: &[u8],
+    x_to_y: bool,
+    amount: u64,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+    referral_fee: FixedPoint,
+    mut cross: impl FnMut(i32, &mut P) -> Result<(), E>,
+) -> Result<SwapTotals, SwapError<E>> {
+    // limit is on the right side of price
+    let is_limit_valid = if x_to_y {
+        pool.sqrt_price() > sqrt_price_limit && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE)
+    } else {
+        pool.sqrt_price() < sqrt_price_limit && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE)
+    };
+    if !is_limit_valid {
+        return Err(SwapError::WrongLimit);
+    }
+
+    let mut remaining_amount = TokenAmount(amount);
+
+    let mut total_amount_in = TokenAmount(0);
+    let mut total_amount_out = TokenAmount(0);
+    let mut total_amount_referral = TokenAmount(0);
+    let mut total_amount_fee = TokenAmount(0);
+
+    while !remaining_amount.is_zero() {
+        let (swap_limit, limiting_tick) = get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index(),
+            pool.tick_spacing(),
+            bitmap,
+        )
+        .map_err(SwapError::LimitReached)?;
+
+        let result = compute_swap_step(
+            pool.sqrt_price(),
+            swap_limit,
+            pool.liquidity(),
+            remaining_amount,
+            by_amount_in,
+            pool.fee(),
+        )
+        .map_err(SwapError::SwapStepOverflow)?;
+        let step_amount_in = from_result!(result.amount_in.checked_add(result.fee_amount))
+            .map_err(SwapError::TokenAmountOverflow)?;
+
+        // make remaining amount smaller
+        remaining_amount = from_result!(remaining_amount.checked_sub(match by_amount_in {
+            true => step_amount_in,
+            false => result.amount_out,
+        }))
+        .map_err(SwapError::TokenAmountOverflow)?;
+
+        let referral_amount = pool
+            .add_fee(result.fee_amount, referral_fee, x_to_y)
+            .map_err(SwapError::FeeOverflow)?;
+        total_amount_referral = from_result!(total_amount_referral.checked_add(referral_amount))
+            .map_err(SwapError::FeeOverflow)?;
+        total_amount_fee = from_result!(total_amount_fee.checked_add(result.fee_amount))
+            .map_err(SwapError::FeeOverflow)?;
+
+        pool.set_sqrt_price(result.next_price_sqrt);
+
+        total_amount_in = from_result!(total_amount_in.checked_add(step_amount_in))
+            .map_err(SwapError::TokenAmountOverflow)?;
+        total_amount_out = from_result!(total_amount_out.checked_add(result.amount_out))
+            .map_err(SwapError::TokenAmountOverflow)?;
+
+        // Fail if price would go over swap limit
+        if pool.sqrt_price() == sqrt_price_limit && !remaining_amount.is_zero() {
+            return Err(SwapError::PriceLimitReached);
+        }
+
+        // crossing tick
+        match limiting_tick {
+            Some((tick_index, initialized)) if result.next_price_sqrt == swap_limit => {
+                let is_enough_amount_to_cross = is_enough_amount_to_push_price(
+                    remaining_amount,
+                    result.next_price_sqrt,
+                    pool.liquidity(),
+                    pool.fee(),
+                    by_amount_in,
+                    x_to_y,
+                )
+                .map_err(SwapError::PriceOverflow)?;
+
+                if initialized {
+                    if !x_to_y || is_enough_amount_to_cross {
+                        cross(tick_index, pool).map_err(SwapError::Cross)?;
+                    } else if !remaining_amount.is_zero() {
+                        if by_amount_in {
+                            pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y)
+                                .map_err(SwapError::FeeOverflow)?;
+                            total_amount_in =
+                                from_result!(total_amount_in.checked_add(remaining_amount))
+                                    .map_err(SwapError::TokenAmountOverflow)?;
+                            total_amount_fee =
+                                from_result!(total_amount_fee.checked_add(remaining_amount))
+                                    .map_err(SwapError::FeeOverflow)?;
+                        }
+                        remaining_amount = TokenAmount(0);
+                    }
+                }
+                // set tick to limit (below if price is going down, because current tick should always be below price)
+                pool.set_current_tick_index(if x_to_y && is_enough_amount_to_cross {
+                    tick_index
+                        .checked_sub(pool.tick_spacing() as i32)
+                        .ok_or(SwapError::InvalidTickIndex)?
+                } else {
+                    tick_index
+                });
+            }
+            _ => {
+                if pool
+                    .current_tick_index()
+                    .checked_rem(pool.tick_spacing().into())
+                    != Some(0)
+                {
+                    return Err(SwapError::InvalidTickIndex);
+                }
+                pool.set_current_tick_index(
+                    get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing())
+                        .map_err(SwapError::PriceOverflow)?,
+                );
+            }
+        }
+    }
+
+    if total_amount_out.0 == 0 {
+        return Err(SwapError::NoGainSwap);
+    }
+
+    Ok(SwapTotals {
+        amount_in: total_amount_in,
+        amount_out: total_amount_out,
+        amount_referral: total_amount_referral,
+        amount_fee: total_amount_fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tickmap::{tick_to_position, TICKMAP_SIZE};
+
+    #[test]
+    fn test_get_closer_limit() {
+        let mut bitmap = [0u8; (TICKMAP_SIZE as usize + 7) / 8];
+        let (byte, bit) = tick_to_position(0, 1);
+        bitmap[byte] |= 1 << bit;
+        // tick limit closer
+        {
+            let (result, from_tick) =
+                get_closer_limit(Price::from_integer(5), true, 100, 1, &bitmap).unwrap();
+
+            let expected = Price::from_integer(5);
+            assert_eq!(result, expected);
+            assert_eq!(from_tick, None);
+        }
+        // trade limit closer
+        {
+            let (result, from_tick) =
+                get_closer_limit(Price::from_scale(1, 1), true, 100, 1, &bitmap).unwrap();
+            let expected = Price::from_integer(1);
+            assert_eq!(result, expected);
+            assert_eq!(from_tick, Some((0, true)));
+        }
+        // other direction
+        {
+            let (result, from_tick) =
+                get_closer_limit(Price::from_integer(2), false, -5, 1, &bitmap).unwrap();
+            let expected = Price::from_integer(1);
+            assert_eq!(result, expected);
+            assert_eq!(from_tick, Some((0, true)));
+        }
+        // other direction
+        {
+            let (result, from_tick) =
+                get_closer_limit(Price::from_scale(1, 1), false, -100, 10, &bitmap).unwrap();
+            let expected = Price::from_scale(1, 1);
+            assert_eq!(result, expected);
+            assert_eq!(from_tick, None);
+        }
+    }
+}
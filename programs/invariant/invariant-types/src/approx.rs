@@ -0,0 +1,52 @@
+//! Lossy `f64` views of pool state for analytics and backtesting.
+//! Nothing here is exact, on-chain math has to stay in `math`.
+
+use crate::decimals::*;
+use crate::utils::{TrackableError, TrackableResult};
+use crate::{err, function, location};
+
+// human price of token x denominated in token y
+pub fn sqrt_price_to_price(sqrt_price: Price, decimals_x: u8, decimals_y: u8) -> f64 {
+    let sqrt_price = sqrt_price.to_f64();
+    sqrt_price * sqrt_price * 10f64.powi(decimals_x as i32 - decimals_y as i32)
+}
+
+pub fn price_to_sqrt_price(price: f64, decimals_x: u8, decimals_y: u8) -> TrackableResult<Price> {
+    if !(price.is_finite() && price > 0.0) {
+        return Err(err!("price has to be a positive number"));
+    }
+    let raw_price = price * 10f64.powi(decimals_y as i32 - decimals_x as i32);
+    Price::checked_from_f64_approx(raw_price.sqrt()).map_err(|err| err!(&err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() <= b.abs() * 1e-12, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_sqrt_price_to_price() {
+        assert_close(sqrt_price_to_price(Price::from_integer(1), 6, 6), 1.0);
+        assert_close(sqrt_price_to_price(Price::from_integer(2), 6, 6), 4.0);
+        assert_close(sqrt_price_to_price(Price::from_integer(1), 9, 6), 1000.0);
+        assert_close(sqrt_price_to_price(Price::from_scale(5, 1), 6, 9), 0.00025);
+    }
+
+    #[test]
+    fn test_price_to_sqrt_price() {
+        assert_close(price_to_sqrt_price(4.0, 6, 6).unwrap().to_f64(), 2.0);
+        assert_close(price_to_sqrt_price(1000.0, 9, 6).unwrap().to_f64(), 1.0);
+
+        // 100 USDC per SOL
+        let sqrt_price = price_to_sqrt_price(100.0, 9, 6).unwrap();
+        assert_close(sqrt_price_to_price(sqrt_price, 9, 6), 100.0);
+
+        assert!(price_to_sqrt_price(0.0, 6, 6).is_err());
+        assert!(price_to_sqrt_price(-1.0, 6, 6).is_err());
+        assert!(price_to_sqrt_price(f64::NAN, 6, 6).is_err());
+        assert!(price_to_sqrt_price(1e80, 6, 6).is_err());
+    }
+}
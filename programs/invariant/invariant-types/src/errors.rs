@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-#[error_code]
+#[error]
 pub enum InvariantErrorCode {
     #[msg("Amount is zero")]
     ZeroAmount = 0, // 1770
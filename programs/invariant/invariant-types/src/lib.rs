@@ -1,3 +1,4 @@
+pub mod approx;
 pub mod decimals;
 pub mod errors;
 pub mod log;
@@ -7,12 +8,13 @@ pub mod structs;
 pub mod utils;
 
 use anchor_lang::prelude::*;
+pub use invariant_math::math::{MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+pub use invariant_math::{err, from_result, function, location, ok_or_mark_trace, trace};
 
 declare_id!("HyaB3W9q6XdA5xwpU4XnSZV94htfmbmqJXZcEbRaJutt");
 pub const SEED: &str = "Invariant";
 pub const STATE_SEED: &str = "statev1";
+pub const POOL_STATS_SEED: &str = "poolstatsv1";
 pub const TICK_SEED: &str = "tickv1";
 pub const ANCHOR_DISCRIMINATOR_SIZE: usize = 8;
 pub const MAX_VIRTUAL_CROSS: u16 = 10;
-pub const MAX_SQRT_PRICE: u128 = 65535383934512647000000000000;
-pub const MIN_SQRT_PRICE: u128 = 15258932000000000000;
@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 #[account(zero_copy)]
 #[repr(packed)]
 #[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FeeTier {
     pub fee: FixedPoint,
     pub tick_spacing: u16,
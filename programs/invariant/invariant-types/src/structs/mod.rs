@@ -1,9 +1,11 @@
 pub mod fee_tier;
 pub mod pool;
+pub mod pool_stats;
 pub mod tick;
 pub mod tickmap;
 
 pub use fee_tier::*;
 pub use pool::*;
+pub use pool_stats::*;
 pub use tick::*;
 pub use tickmap::*;
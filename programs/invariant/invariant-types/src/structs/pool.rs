@@ -1,15 +1,34 @@
 use anchor_lang::prelude::*;
 
-use crate::{decimals::*, size};
+use crate::{decimals::*, size, structs::Tick};
+use invariant_math::impl_swap_state;
+
+pub use invariant_math::swap::REWARDS_PER_POOL;
+
+#[zero_copy]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolReward {
+    pub token: Pubkey,
+    pub reserve: Pubkey,
+    pub authority: Pubkey,
+    pub emission_per_second: FixedPoint,
+    pub growth_global: FeeGrowth,
+    pub emission_end: u64,
+    pub unclaimed: FixedPoint,
+}
 
 #[account(zero_copy)]
 #[repr(packed)]
 #[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pool {
     pub token_x: Pubkey,
     pub token_y: Pubkey,
     pub token_x_reserve: Pubkey,
     pub token_y_reserve: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_u128"))]
     pub position_iterator: u128,
     pub tick_spacing: u16,
     pub fee: FixedPoint,
@@ -28,6 +47,33 @@ pub struct Pool {
     pub fee_receiver: Pubkey,
     pub oracle_address: Pubkey,
     pub oracle_initialized: bool,
+    pub rewards: [PoolReward; REWARDS_PER_POOL],
+    pub rewards_last_timestamp: u64,
     pub bump: u8,
 }
 size!(Pool);
+impl_swap_state!(Pool, Tick);
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_serde() {
+        let pool = Pool {
+            liquidity: Liquidity::from_integer(5),
+            sqrt_price: Price::from_integer(1),
+            fee: FixedPoint::from_scale(6, 4),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&pool).unwrap();
+        assert_eq!(json["position_iterator"], "0");
+        assert_eq!(json["liquidity"], "5.000000");
+        assert_eq!(json["sqrt_price"], "1.000000000000000000000000");
+        assert_eq!(json["fee"], "0.000600000000");
+
+        let deserialized: Pool = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, pool);
+    }
+}
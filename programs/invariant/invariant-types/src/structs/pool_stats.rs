@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::size;
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolStats {
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_u128"))]
+    pub volume_x: u128, // cumulative swapped amounts, fees included
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_u128"))]
+    pub volume_y: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_u128"))]
+    pub fees_x: u128, // cumulative swap fees, before the protocol and referral split
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_u128"))]
+    pub fees_y: u128,
+    pub swap_count: u64,
+    pub last_swap_timestamp: u64,
+    pub bump: u8,
+}
+
+size!(PoolStats);
@@ -1,9 +1,10 @@
-use crate::{decimals::*, size};
+use crate::{decimals::*, size, structs::REWARDS_PER_POOL};
 use anchor_lang::prelude::*;
 
 #[account(zero_copy)]
 #[repr(packed)]
 #[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tick {
     pub pool: Pubkey,
     pub index: i32,
@@ -15,6 +16,7 @@ pub struct Tick {
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_per_liquidity_outside: FixedPoint,
     pub seconds_outside: u64,
+    pub reward_growth_outside: [FeeGrowth; REWARDS_PER_POOL],
     pub bump: u8,
 }
 size!(Tick);
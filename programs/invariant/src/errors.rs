@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use invariant_math::swap::SwapError;
+use invariant_math::utils::TrackableError;
 
 #[error]
 pub enum ErrorCode {
@@ -64,4 +66,65 @@ pub enum ErrorCode {
     InvalidListOwner = 29, // 178d
     #[msg("Invalid tick spacing")]
     InvalidTickSpacing = 30, // 178e
+    #[msg("Remaining accounts should be position, lower tick and upper tick triplets")]
+    InvalidBatchAccounts = 31, // 178f
+    #[msg("Invalid operator permissions")]
+    InvalidOperatorPermissions = 32, // 1790
+    #[msg("Position is locked")]
+    PositionLocked = 33, // 1791
+    #[msg("Unlock timestamp can only be extended")]
+    InvalidUnlockTimestamp = 34, // 1792
+    #[msg("Invalid reward index")]
+    InvalidRewardIndex = 35, // 1793
+    #[msg("Reward is already initialized")]
+    RewardAlreadyInitialized = 36, // 1794
+    #[msg("Swap step computation overflowed")]
+    SwapStepOverflow = 37, // 1795
+    #[msg("Price computation overflowed")]
+    PriceOverflow = 38, // 1796
+    #[msg("Token amount computation overflowed")]
+    TokenAmountOverflow = 39, // 1797
+    #[msg("Fee computation overflowed")]
+    FeeOverflow = 40, // 1798
+    #[msg("Position does not belong to the pool")]
+    InvalidPositionPool = 41, // 1799
+    #[msg("Account can't be migrated")]
+    InvalidMigration = 42, // 179a
+    #[msg("Reward computation overflowed")]
+    RewardOverflow = 43, // 179b
+    #[msg("Reward reserve doesn't cover the emission")]
+    InsufficientRewardReserve = 44, // 179c
+    #[msg("Position has unclaimed rewards")]
+    RewardsNotClaimed = 45, // 179d
+    #[msg("Owed fees don't cover the amount")]
+    InsufficientOwedFees = 46, // 179e
+    #[msg("Claim exceeds the unclaimed rewards of the pool")]
+    InsufficientUnclaimedRewards = 47, // 179f
+}
+
+// logs the stack trace of a failed calculation and replaces it with an error code
+pub fn trace_error(code: ErrorCode) -> impl FnOnce(TrackableError) -> ProgramError {
+    move |error| {
+        msg!("{}", error.to_string());
+        code.into()
+    }
+}
+
+pub fn swap_error(error: SwapError<Error>) -> Error {
+    match error {
+        SwapError::WrongLimit => ErrorCode::WrongLimit.into(),
+        SwapError::LimitReached(error) => trace_error(ErrorCode::LimitReached)(error).into(),
+        SwapError::PriceLimitReached => ErrorCode::PriceLimitReached.into(),
+        SwapError::InvalidTickIndex => ErrorCode::InvalidTickIndex.into(),
+        SwapError::NoGainSwap => ErrorCode::NoGainSwap.into(),
+        SwapError::SwapStepOverflow(error) => {
+            trace_error(ErrorCode::SwapStepOverflow)(error).into()
+        }
+        SwapError::TokenAmountOverflow(error) => {
+            trace_error(ErrorCode::TokenAmountOverflow)(error).into()
+        }
+        SwapError::FeeOverflow(error) => trace_error(ErrorCode::FeeOverflow)(error).into(),
+        SwapError::PriceOverflow(error) => trace_error(ErrorCode::PriceOverflow)(error).into(),
+        SwapError::Cross(error) => error,
+    }
 }
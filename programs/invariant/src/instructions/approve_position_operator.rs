@@ -0,0 +1,33 @@
+use crate::structs::position::{Position, OPERATOR_ALL_PERMISSIONS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct ApprovePositionOperator<'info> {
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump
+    )]
+    pub position: AccountLoader<'info, Position>,
+    pub owner: Signer<'info>,
+    pub operator: AccountInfo<'info>,
+}
+
+impl<'info> ApprovePositionOperator<'info> {
+    pub fn handler(&self, permissions: u8) -> ProgramResult {
+        msg!("INVARIANT: APPROVE POSITION OPERATOR");
+
+        require!(
+            permissions != 0 && permissions & !OPERATOR_ALL_PERMISSIONS == 0,
+            InvalidOperatorPermissions
+        );
+
+        let mut position = self.position.load_mut()?;
+        position.operator = *self.operator.key;
+        position.operator_permissions = permissions;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::decimals::*;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::structs::pool::Pool;
-use crate::structs::position::Position;
+use crate::structs::position::{Position, OPERATOR_CLAIM_FEE};
 use crate::structs::tick::Tick;
 use crate::util::*;
 use crate::ErrorCode::*;
@@ -39,7 +39,8 @@ pub struct ClaimFee<'info> {
         constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
     )]
     pub upper_tick: AccountLoader<'info, Tick>,
-    pub owner: Signer<'info>,
+    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
     #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
     pub token_x: Account<'info, Mint>,
     #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
@@ -107,6 +108,7 @@ impl<'info> ClaimFee<'info> {
         let upper_tick = &mut self.upper_tick.load_mut()?;
         let current_timestamp = get_current_timestamp();
 
+        position.check_authority(self.signer.key, OPERATOR_CLAIM_FEE)?;
         check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
 
         position
@@ -0,0 +1,122 @@
+use crate::decimals::*;
+use crate::structs::pool::{Pool, REWARDS_PER_POOL};
+use crate::structs::position::{Position, OPERATOR_CLAIM_FEE};
+use crate::structs::tick::Tick;
+use crate::util::*;
+use crate::ErrorCode::*;
+use crate::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount, Transfer};
+use std::cmp::min;
+
+#[derive(Accounts)]
+#[instruction( index: u32, lower_tick_index: i32, upper_tick_index: i32)]
+pub struct ClaimReward<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump,
+        constraint = position.load()?.pool == pool.key() @ InvalidPositionPool
+    )]
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump = lower_tick.load()?.bump,
+        constraint = lower_tick_index == position.load()?.lower_tick_index @ WrongTick
+    )]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump = upper_tick.load()?.bump,
+        constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
+    )]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    #[account(mut,
+        constraint = &reward_reserve.owner == program_authority.key @ InvalidAuthority
+    )]
+    pub reward_reserve: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = account_reward.mint == reward_reserve.mint @ InvalidMint,
+        constraint = &account_reward.owner == owner.key @ InvalidOwner
+    )]
+    pub account_reward: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimReward<'info> {
+    fn send_reward(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reward_reserve.to_account_info(),
+                to: self.account_reward.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+}
+
+impl<'info> ClaimReward<'info> {
+    pub fn handler(&self, reward_index: u8) -> ProgramResult {
+        msg!("INVARIANT: CLAIM REWARD");
+
+        let index = reward_index as usize;
+        require!(index < REWARDS_PER_POOL, InvalidRewardIndex);
+
+        let state = self.state.load()?;
+        let pool = &mut self.pool.load_mut()?;
+        let position = &mut self.position.load_mut()?;
+        let lower_tick = &mut self.lower_tick.load_mut()?;
+        let upper_tick = &mut self.upper_tick.load_mut()?;
+        let current_timestamp = get_current_timestamp();
+
+        position.check_authority(self.signer.key, OPERATOR_CLAIM_FEE)?;
+        check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
+        require!(
+            pool.rewards[index].reserve == self.reward_reserve.key(),
+            InvalidTokenAccount
+        );
+
+        position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            Liquidity::new(0),
+            true,
+            current_timestamp,
+        )?;
+
+        // reserve may be underfunded, the rest stays owed
+        let rewards_owed = position.rewards_owed;
+        let reward_to_collect = min(
+            TokenAmount::from_decimal(rewards_owed[index]),
+            TokenAmount(self.reward_reserve.amount),
+        );
+        position.rewards_owed[index] =
+            rewards_owed[index] - FixedPoint::from_decimal(reward_to_collect);
+        pool.settle_reward_claim(index, reward_to_collect)?;
+
+        let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+        token::transfer(self.send_reward().with_signer(signer), reward_to_collect.0)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::structs::pool::Pool;
+use crate::structs::position::{Position, OPERATOR_CLAIM_FEE};
+use crate::structs::tick::Tick;
+use crate::util::*;
+use crate::ErrorCode::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+#[derive(Accounts)]
+#[instruction( index: u32, lower_tick_index: i32, upper_tick_index: i32)]
+pub struct CompoundFee<'info> {
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump,
+        constraint = position.load()?.pool == pool.key() @ InvalidPositionPool
+    )]
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump = lower_tick.load()?.bump,
+        constraint = lower_tick_index == position.load()?.lower_tick_index @ WrongTick
+    )]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump = upper_tick.load()?.bump,
+        constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
+    )]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+}
+
+impl<'info> CompoundFee<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: COMPOUND FEE");
+
+        let pool = &mut self.pool.load_mut()?;
+        let position = &mut self.position.load_mut()?;
+        let lower_tick = &mut self.lower_tick.load_mut()?;
+        let upper_tick = &mut self.upper_tick.load_mut()?;
+        let current_timestamp = get_current_timestamp();
+
+        position.check_authority(self.signer.key, OPERATOR_CLAIM_FEE)?;
+        check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
+
+        position.compound(pool, upper_tick, lower_tick, current_timestamp)?;
+        Ok(())
+    }
+}
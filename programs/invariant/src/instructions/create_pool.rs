@@ -1,4 +1,5 @@
 use crate::decimals::*;
+use crate::errors::trace_error;
 use crate::math::calculate_price_sqrt;
 use crate::structs::fee_tier::FeeTier;
 use crate::structs::pool::Pool;
@@ -82,7 +83,7 @@ impl<'info> CreatePool<'info> {
             fee: fee_tier.fee,
             protocol_fee: FixedPoint::from_scale(1, 2),
             liquidity: Liquidity::new(0),
-            sqrt_price: calculate_price_sqrt(init_tick),
+            sqrt_price: calculate_price_sqrt(init_tick).map_err(trace_error(InvalidTickIndex))?,
             current_tick_index: init_tick,
             tickmap: *self.tickmap.to_account_info().key,
             fee_growth_global_x: FeeGrowth::new(0),
@@ -96,6 +97,8 @@ impl<'info> CreatePool<'info> {
             fee_receiver: self.state.load()?.admin,
             oracle_address: Pubkey::default(),
             oracle_initialized: false,
+            rewards: Default::default(),
+            rewards_last_timestamp: current_timestamp,
             bump,
         };
 
@@ -0,0 +1,34 @@
+use crate::structs::pool::Pool;
+use crate::structs::pool_stats::PoolStats;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+#[derive(Accounts)]
+pub struct CreatePoolStats<'info> {
+    #[account(init,
+        seeds = [b"poolstatsv1", pool.key().as_ref()],
+        bump,
+        payer = payer
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStats>,
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> CreatePoolStats<'info> {
+    pub fn handler(&self, bump: u8) -> ProgramResult {
+        msg!("INVARIANT: CREATE POOL STATS");
+        let mut pool_stats = self.pool_stats.load_init()?;
+        *pool_stats = PoolStats {
+            pool: self.pool.key(),
+            bump,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+}
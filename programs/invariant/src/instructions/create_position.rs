@@ -165,6 +165,12 @@ impl<'info> CreatePosition<'info> {
             last_slot: slot,
             tokens_owed_x: FixedPoint::new(0),
             tokens_owed_y: FixedPoint::new(0),
+            operator: Pubkey::default(),
+            operator_permissions: 0,
+            unlock_timestamp: 0,
+            reward_growth_inside: Default::default(),
+            rewards_owed: Default::default(),
+            range_nonce: 0,
             bump,
         };
 
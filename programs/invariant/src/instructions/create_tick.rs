@@ -1,6 +1,7 @@
 use crate::decimals::*;
+use crate::errors::trace_error;
 use crate::math::calculate_price_sqrt;
-use crate::structs::pool::Pool;
+use crate::structs::pool::{Pool, PoolState};
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
 use crate::util::check_tick;
@@ -58,7 +59,7 @@ impl<'info> CreateTick<'info> {
             sign: true,
             liquidity_change: Liquidity::new(0),
             liquidity_gross: Liquidity::new(0),
-            sqrt_price: calculate_price_sqrt(index),
+            sqrt_price: calculate_price_sqrt(index).map_err(trace_error(InvalidTickIndex))?,
             fee_growth_outside_x: match below_current_tick {
                 true => pool.fee_growth_global_x,
                 false => FeeGrowth::new(0),
@@ -75,6 +76,10 @@ impl<'info> CreateTick<'info> {
                 true => pool.seconds_per_liquidity_global,
                 false => FixedPoint::new(0),
             },
+            reward_growth_outside: match below_current_tick {
+                true => pool.reward_growth_global(),
+                false => Default::default(),
+            },
             bump,
         };
 
@@ -0,0 +1,65 @@
+use crate::structs::pool::{Pool, PoolReward, REWARDS_PER_POOL};
+use crate::structs::State;
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::Token;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitializeReward<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Box<Account<'info, Mint>>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Box<Account<'info, Mint>>,
+    pub reward_token: Box<Account<'info, Mint>>,
+    #[account(init,
+        token::mint = reward_token,
+        token::authority = program_authority,
+        payer = admin,
+    )]
+    pub reward_reserve: Box<Account<'info, TokenAccount>>,
+    pub reward_authority: AccountInfo<'info>,
+    #[account(mut, constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> InitializeReward<'info> {
+    pub fn handler(&self, reward_index: u8) -> ProgramResult {
+        msg!("INVARIANT: INITIALIZE REWARD");
+
+        let index = reward_index as usize;
+        require!(index < REWARDS_PER_POOL, InvalidRewardIndex);
+
+        let pool = &mut self.pool.load_mut()?;
+        require!(
+            pool.rewards[index].token == Pubkey::default(),
+            RewardAlreadyInitialized
+        );
+
+        // emission is started by the reward authority once the reserve is funded
+        pool.update_rewards(get_current_timestamp())?;
+        pool.rewards[index] = PoolReward {
+            token: self.reward_token.key(),
+            reserve: self.reward_reserve.key(),
+            authority: self.reward_authority.key(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+}
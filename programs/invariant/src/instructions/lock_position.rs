@@ -0,0 +1,32 @@
+use crate::structs::position::Position;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct LockPosition<'info> {
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump
+    )]
+    pub position: AccountLoader<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+impl<'info> LockPosition<'info> {
+    pub fn handler(&self, unlock_timestamp: u64) -> ProgramResult {
+        msg!("INVARIANT: LOCK POSITION");
+
+        let mut position = self.position.load_mut()?;
+
+        // lock is one-way, it can only be extended
+        require!(
+            unlock_timestamp > position.unlock_timestamp,
+            InvalidUnlockTimestamp
+        );
+        position.unlock_timestamp = unlock_timestamp;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::structs::pool::{Pool, LEGACY_POOL_SIZE};
+use crate::util::{get_current_timestamp, migrate_account};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut,
+        constraint = pool.owner == program_id @ InvalidMigration
+    )]
+    pub pool: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> MigratePool<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: MIGRATE POOL");
+
+        migrate_account(
+            &self.pool,
+            &self.payer,
+            &self.system_program,
+            Pool::discriminator(),
+            LEGACY_POOL_SIZE,
+            8 + size_of::<Pool>(),
+        )?;
+
+        let loader = AccountLoader::<'_, Pool>::try_from(&self.pool)?;
+        loader.load_mut()?.rewards_last_timestamp = get_current_timestamp();
+
+        Ok(())
+    }
+}
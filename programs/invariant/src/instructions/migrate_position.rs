@@ -0,0 +1,66 @@
+use crate::math::calculate_reward_growth_inside;
+use crate::structs::pool::{Pool, PoolState};
+use crate::structs::position::{Position, LEGACY_POSITION_SIZE};
+use crate::structs::tick::Tick;
+use crate::util::migrate_account;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+// pool and ticks of the position have to be migrated first
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut,
+        constraint = position.owner == program_id @ InvalidMigration
+    )]
+    pub position: AccountInfo<'info>,
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = lower_tick.load()?.pool == pool.key() @ InvalidMigration)]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(constraint = upper_tick.load()?.pool == pool.key() @ InvalidMigration)]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> MigratePosition<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: MIGRATE POSITION");
+
+        migrate_account(
+            &self.position,
+            &self.payer,
+            &self.system_program,
+            Position::discriminator(),
+            LEGACY_POSITION_SIZE,
+            8 + size_of::<Position>(),
+        )?;
+
+        let loader = AccountLoader::<'_, Position>::try_from(&self.position)?;
+        let position = &mut loader.load_mut()?;
+        let pool = self.pool.load()?;
+        let lower_tick = self.lower_tick.load()?;
+        let upper_tick = self.upper_tick.load()?;
+
+        require!(position.pool == self.pool.key(), InvalidMigration);
+        require!(
+            lower_tick.index == position.lower_tick_index
+                && upper_tick.index == position.upper_tick_index,
+            WrongTick
+        );
+
+        // rewards are earned from the migration onwards
+        position.reward_growth_inside = calculate_reward_growth_inside(
+            *lower_tick,
+            *upper_tick,
+            pool.current_tick_index,
+            pool.reward_growth_global(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::structs::pool::{Pool, PoolState};
+use crate::structs::tick::{Tick, LEGACY_TICK_SIZE};
+use crate::util::migrate_account;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+// pool of the tick has to be migrated first
+#[derive(Accounts)]
+pub struct MigrateTick<'info> {
+    #[account(mut,
+        constraint = tick.owner == program_id @ InvalidMigration
+    )]
+    pub tick: AccountInfo<'info>,
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> MigrateTick<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: MIGRATE TICK");
+
+        migrate_account(
+            &self.tick,
+            &self.payer,
+            &self.system_program,
+            Tick::discriminator(),
+            LEGACY_TICK_SIZE,
+            8 + size_of::<Tick>(),
+        )?;
+
+        let loader = AccountLoader::<'_, Tick>::try_from(&self.tick)?;
+        let tick = &mut loader.load_mut()?;
+        let pool = self.pool.load()?;
+        require!(tick.pool == self.pool.key(), InvalidMigration);
+
+        // same as in create_tick, growth so far is assumed to have happened below the tick
+        if tick.index <= pool.current_tick_index {
+            tick.reward_growth_outside = pool.reward_growth_global();
+        }
+
+        Ok(())
+    }
+}
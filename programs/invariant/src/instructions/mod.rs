@@ -1,30 +1,56 @@
+pub mod approve_position_operator;
+pub mod batch_claim_fee;
 pub mod change_fee_receiver;
 pub mod change_protocol_fee;
 pub mod claim_fee;
+pub mod claim_reward;
+pub mod compound_fee;
 pub mod create_fee_tier;
 pub mod create_pool;
+pub mod create_pool_stats;
 pub mod create_position;
 pub mod create_position_list;
 pub mod create_state;
 pub mod create_tick;
 pub mod initialize_oracle;
+pub mod initialize_reward;
+pub mod lock_position;
+pub mod migrate_pool;
+pub mod migrate_position;
+pub mod migrate_tick;
+pub mod rebalance_position;
 pub mod remove_position;
+pub mod revoke_position_operator;
+pub mod set_reward_emission;
 pub mod swap;
 pub mod transfer_position_ownership;
 pub mod update_seconds_per_liquidity;
 pub mod withdraw_protocol_fee;
 
+pub use approve_position_operator::*;
+pub use batch_claim_fee::*;
 pub use change_fee_receiver::*;
 pub use change_protocol_fee::*;
 pub use claim_fee::*;
+pub use claim_reward::*;
+pub use compound_fee::*;
 pub use create_fee_tier::*;
 pub use create_pool::*;
+pub use create_pool_stats::*;
 pub use create_position::*;
 pub use create_position_list::*;
 pub use create_state::*;
 pub use create_tick::*;
 pub use initialize_oracle::*;
+pub use initialize_reward::*;
+pub use lock_position::*;
+pub use migrate_pool::*;
+pub use migrate_position::*;
+pub use migrate_tick::*;
+pub use rebalance_position::*;
 pub use remove_position::*;
+pub use revoke_position_operator::*;
+pub use set_reward_emission::*;
 pub use swap::*;
 pub use transfer_position_ownership::*;
 pub use update_seconds_per_liquidity::*;
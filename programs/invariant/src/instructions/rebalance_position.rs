@@ -0,0 +1,338 @@
+use crate::decimals::*;
+use crate::interfaces::send_tokens::SendTokens;
+use crate::interfaces::take_tokens::TakeTokens;
+use crate::structs::pool::Pool;
+use crate::structs::pool_stats::PoolStats;
+use crate::structs::position::{Position, OPERATOR_MANAGE_LIQUIDITY};
+use crate::structs::tick::Tick;
+use crate::structs::tickmap::Tickmap;
+use crate::util::{check_ticks, close, swap_within_pool};
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(index: u32, lower_tick_index: i32, upper_tick_index: i32)]
+pub struct RebalancePosition<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump,
+        constraint = position.load()?.pool == pool.key() @ InvalidPositionPool
+    )]
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"poolstatsv1", pool.key().as_ref()],
+        bump = pool_stats.load()?.bump
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStats>,
+    #[account(mut,
+        constraint = tickmap.key() == pool.load()?.tickmap @ InvalidTickmap,
+        constraint = tickmap.to_account_info().owner == program_id @ InvalidTickmapOwner,
+    )]
+    pub tickmap: AccountLoader<'info, Tickmap>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &position.load()?.lower_tick_index.to_le_bytes()],
+        bump = old_lower_tick.load()?.bump
+    )]
+    pub old_lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &position.load()?.upper_tick_index.to_le_bytes()],
+        bump = old_upper_tick.load()?.bump
+    )]
+    pub old_upper_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump = new_lower_tick.load()?.bump
+    )]
+    pub new_lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump = new_upper_tick.load()?.bump
+    )]
+    pub new_upper_tick: AccountLoader<'info, Tick>,
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    #[account(mut,
+        constraint = account_x.mint == token_x.key() @ InvalidMint,
+        constraint = &account_x.owner == owner.key @ InvalidOwner
+    )]
+    pub account_x: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = account_y.mint == token_y.key() @ InvalidMint,
+        constraint = &account_y.owner == owner.key @ InvalidOwner
+    )]
+    pub account_y: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = reserve_x.mint == token_x.key() @ InvalidMint,
+        constraint = &reserve_x.owner == program_authority.key @ InvalidAuthority,
+        constraint = reserve_x.key() == pool.load()?.token_x_reserve @ InvalidTokenAccount
+    )]
+    pub reserve_x: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = reserve_y.mint == token_y.key() @ InvalidMint,
+        constraint = &reserve_y.owner == program_authority.key @ InvalidAuthority,
+        constraint = reserve_y.key() == pool.load()?.token_y_reserve @ InvalidTokenAccount
+    )]
+    pub reserve_y: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> TakeTokens<'info> for RebalancePosition<'info> {
+    fn take_x(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.account_x.to_account_info(),
+                to: self.reserve_x.to_account_info(),
+                authority: self.owner.to_account_info().clone(),
+            },
+        )
+    }
+
+    fn take_y(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.account_y.to_account_info(),
+                to: self.reserve_y.to_account_info(),
+                authority: self.owner.to_account_info().clone(),
+            },
+        )
+    }
+}
+
+impl<'info> SendTokens<'info> for RebalancePosition<'info> {
+    fn send_x(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reserve_x.to_account_info(),
+                to: self.account_x.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+
+    fn send_y(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reserve_y.to_account_info(),
+                to: self.account_y.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+}
+
+impl<'info> RebalancePosition<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(
+        ctx: Context<'_, '_, '_, 'info, RebalancePosition<'info>>,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: Price,
+        slippage_limit_upper: Price,
+        swap_x_to_y: bool,
+        swap_amount: u64,
+        swap_sqrt_price_limit: Price,
+    ) -> ProgramResult {
+        msg!("INVARIANT: REBALANCE POSITION");
+
+        let accounts = &ctx.accounts;
+        let state = accounts.state.load()?;
+        let position = &mut accounts.position.load_mut()?;
+        let pool = &mut accounts.pool.load_mut()?;
+        let tickmap = &mut accounts.tickmap.load_mut()?;
+        let current_timestamp = get_current_timestamp();
+        let slot = get_current_slot();
+
+        position.check_authority(accounts.signer.key, OPERATOR_MANAGE_LIQUIDITY)?;
+        position.check_unlocked(current_timestamp)?;
+
+        // validate new ticks
+        check_ticks(lower_tick_index, upper_tick_index, pool.tick_spacing)?;
+
+        let old_lower_tick_index = position.lower_tick_index;
+        let old_upper_tick_index = position.upper_tick_index;
+
+        // old ticks can't be closed if the new range still uses them
+        let new_tick_keys = [accounts.new_lower_tick.key(), accounts.new_upper_tick.key()];
+        let close_lower;
+        let close_upper;
+
+        // withdraw whole liquidity from the old range
+        let (withdrawn_x, withdrawn_y) = {
+            let lower_tick = &mut accounts.old_lower_tick.load_mut()?;
+            let upper_tick = &mut accounts.old_upper_tick.load_mut()?;
+
+            let liquidity_delta = position.liquidity;
+            let (amount_x, amount_y) = position.modify(
+                pool,
+                upper_tick,
+                lower_tick,
+                liquidity_delta,
+                false,
+                current_timestamp,
+            )?;
+
+            close_lower = lower_tick.liquidity_gross.is_zero()
+                && !new_tick_keys.contains(&accounts.old_lower_tick.key());
+            close_upper = upper_tick.liquidity_gross.is_zero()
+                && !new_tick_keys.contains(&accounts.old_upper_tick.key());
+
+            (amount_x, amount_y)
+        };
+
+        // accumulated fees are withdrawn together with liquidity
+        let fee_to_collect_x = TokenAmount::from_decimal(position.tokens_owed_x);
+        let fee_to_collect_y = TokenAmount::from_decimal(position.tokens_owed_y);
+        position.tokens_owed_x =
+            position.tokens_owed_x - FixedPoint::from_decimal(fee_to_collect_x);
+        position.tokens_owed_y =
+            position.tokens_owed_y - FixedPoint::from_decimal(fee_to_collect_y);
+
+        if close_lower {
+            {
+                let lower_tick = &mut accounts.old_lower_tick.load_mut()?;
+                **lower_tick = Default::default();
+            }
+            close(
+                accounts.old_lower_tick.to_account_info(),
+                accounts.owner.to_account_info(),
+            )?;
+
+            tickmap.flip(false, old_lower_tick_index, pool.tick_spacing);
+        }
+        if close_upper {
+            {
+                let upper_tick = &mut accounts.old_upper_tick.load_mut()?;
+                **upper_tick = Default::default();
+            }
+            close(
+                accounts.old_upper_tick.to_account_info(),
+                accounts.owner.to_account_info(),
+            )?;
+
+            tickmap.flip(false, old_upper_tick_index, pool.tick_spacing);
+        }
+
+        // optional swap of the imbalance, done against the same pool
+        let (swapped_in, swapped_out) = match swap_amount {
+            0 => (TokenAmount(0), TokenAmount(0)),
+            _ => {
+                let totals = swap_within_pool(
+                    pool,
+                    accounts.pool.to_account_info().key,
+                    tickmap,
+                    ctx.remaining_accounts,
+                    ctx.program_id,
+                    swap_x_to_y,
+                    swap_amount,
+                    true,
+                    swap_sqrt_price_limit,
+                    FixedPoint::from_integer(0),
+                )?;
+                accounts.pool_stats.load_mut()?.record_swap(
+                    swap_x_to_y,
+                    totals.amount_in,
+                    totals.amount_out,
+                    totals.amount_fee,
+                    current_timestamp,
+                )?;
+                (totals.amount_in, totals.amount_out)
+            }
+        };
+
+        // validate price after the swap
+        let price = pool.sqrt_price;
+        require!(price >= slippage_limit_lower, PriceLimitReached);
+        require!(price <= slippage_limit_upper, PriceLimitReached);
+
+        // deposit into the new range, reusing the same position account
+        let (deposit_x, deposit_y) = {
+            let lower_tick = &mut accounts.new_lower_tick.load_mut()?;
+            let upper_tick = &mut accounts.new_upper_tick.load_mut()?;
+
+            if !tickmap.get(lower_tick.index, pool.tick_spacing) {
+                tickmap.flip(true, lower_tick.index, pool.tick_spacing)
+            }
+            if !tickmap.get(upper_tick.index, pool.tick_spacing) {
+                tickmap.flip(true, upper_tick.index, pool.tick_spacing)
+            }
+
+            position.lower_tick_index = lower_tick.index;
+            position.upper_tick_index = upper_tick.index;
+            position.fee_growth_inside_x = FeeGrowth::new(0);
+            position.fee_growth_inside_y = FeeGrowth::new(0);
+            position.seconds_per_liquidity_inside = FixedPoint::new(0);
+            position.last_slot = slot;
+            position.range_nonce = { position.range_nonce }.wrapping_add(1);
+
+            position.modify(
+                pool,
+                upper_tick,
+                lower_tick,
+                liquidity_delta,
+                true,
+                current_timestamp,
+            )?
+        };
+
+        // settle the difference with the owner
+        let (swapped_in_x, swapped_in_y, swapped_out_x, swapped_out_y) = match swap_x_to_y {
+            true => (swapped_in, TokenAmount(0), TokenAmount(0), swapped_out),
+            false => (TokenAmount(0), swapped_in, swapped_out, TokenAmount(0)),
+        };
+        let credit_x = withdrawn_x + fee_to_collect_x + swapped_out_x;
+        let credit_y = withdrawn_y + fee_to_collect_y + swapped_out_y;
+        let debit_x = deposit_x + swapped_in_x;
+        let debit_y = deposit_y + swapped_in_y;
+
+        // operator can't spend owner's tokens, only the ones released from the position
+        require!(
+            accounts.owner.is_signer || (credit_x >= debit_x && credit_y >= debit_y),
+            Unauthorized
+        );
+
+        let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+        if credit_x > debit_x {
+            token::transfer(
+                accounts.send_x().with_signer(signer),
+                (credit_x - debit_x).0,
+            )?;
+        } else {
+            token::transfer(accounts.take_x(), (debit_x - credit_x).0)?;
+        }
+        if credit_y > debit_y {
+            token::transfer(
+                accounts.send_y().with_signer(signer),
+                (credit_y - debit_y).0,
+            )?;
+        } else {
+            token::transfer(accounts.take_y(), (debit_y - credit_y).0)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::decimals::*;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::structs::pool::Pool;
-use crate::structs::position::Position;
+use crate::structs::position::{Position, OPERATOR_MANAGE_LIQUIDITY};
 use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
@@ -60,7 +60,8 @@ pub struct RemovePosition<'info> {
     )]
     pub upper_tick: AccountLoader<'info, Tick>,
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub owner: AccountInfo<'info>,
+    pub signer: Signer<'info>,
     #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
     pub token_x: Account<'info, Mint>,
     #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
@@ -133,6 +134,9 @@ impl<'info> RemovePosition<'info> {
         let tickmap = &mut self.tickmap.load_mut()?;
         let current_timestamp = get_current_timestamp();
 
+        removed_position.check_authority(self.signer.key, OPERATOR_MANAGE_LIQUIDITY)?;
+        removed_position.check_unlocked(current_timestamp)?;
+
         // closing tick can't be in the same scope as loaded tick
         let close_lower;
         let close_upper;
@@ -153,6 +157,16 @@ impl<'info> RemovePosition<'info> {
                 current_timestamp,
             )?;
 
+            // rewards are paid out only by claim_reward, so they have to be claimed first
+            // fractions of a token can't be paid out and are left behind
+            let rewards_owed = removed_position.rewards_owed;
+            require!(
+                rewards_owed
+                    .iter()
+                    .all(|owed| TokenAmount::from_decimal(*owed).is_zero()),
+                RewardsNotClaimed
+            );
+
             let amount_x = amount_x + TokenAmount::from_decimal(removed_position.tokens_owed_x);
             let amount_y = amount_y + TokenAmount::from_decimal(removed_position.tokens_owed_y);
 
@@ -211,6 +225,12 @@ impl<'info> RemovePosition<'info> {
                 last_slot: last_position.last_slot,
                 tokens_owed_x: last_position.tokens_owed_x,
                 tokens_owed_y: last_position.tokens_owed_y,
+                operator: last_position.operator,
+                operator_permissions: last_position.operator_permissions,
+                unlock_timestamp: last_position.unlock_timestamp,
+                reward_growth_inside: last_position.reward_growth_inside,
+                rewards_owed: last_position.rewards_owed,
+                range_nonce: last_position.range_nonce,
             };
 
             *last_position = Default::default();
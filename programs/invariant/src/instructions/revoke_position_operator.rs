@@ -0,0 +1,27 @@
+use crate::structs::position::Position;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct RevokePositionOperator<'info> {
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump
+    )]
+    pub position: AccountLoader<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+impl<'info> RevokePositionOperator<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: REVOKE POSITION OPERATOR");
+
+        let mut position = self.position.load_mut()?;
+        position.operator = Pubkey::default();
+        position.operator_permissions = 0;
+
+        Ok(())
+    }
+}
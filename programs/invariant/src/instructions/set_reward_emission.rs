@@ -0,0 +1,60 @@
+use crate::decimals::*;
+use crate::structs::pool::{Pool, REWARDS_PER_POOL};
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetRewardEmission<'info> {
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    pub reward_reserve: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetRewardEmission<'info> {
+    pub fn handler(
+        &self,
+        reward_index: u8,
+        emission_per_second: FixedPoint,
+        emission_end: u64,
+    ) -> ProgramResult {
+        msg!("INVARIANT: SET REWARD EMISSION");
+
+        let index = reward_index as usize;
+        require!(index < REWARDS_PER_POOL, InvalidRewardIndex);
+
+        let pool = &mut self.pool.load_mut()?;
+        require!(
+            pool.rewards[index].authority == self.authority.key(),
+            Unauthorized
+        );
+        require!(
+            pool.rewards[index].reserve == self.reward_reserve.key(),
+            InvalidTokenAccount
+        );
+
+        // settle emission with the old rate first
+        let current_timestamp = get_current_timestamp();
+        pool.update_rewards(current_timestamp)?;
+        pool.rewards[index].emission_per_second = emission_per_second;
+        pool.rewards[index].emission_end = emission_end;
+
+        // new emission can't promise more than the reserve holds
+        pool.check_reward_reserve(
+            index,
+            TokenAmount(self.reward_reserve.amount),
+            current_timestamp,
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,10 @@
 use crate::interfaces::send_tokens::SendTokens;
 use crate::interfaces::take_ref_tokens::TakeRefTokens;
 use crate::interfaces::take_tokens::TakeTokens;
-use crate::log::get_tick_at_sqrt_price;
-use crate::math::compute_swap_step;
 use crate::structs::pool::Pool;
-use crate::structs::tick::Tick;
+use crate::structs::pool_stats::PoolStats;
 use crate::structs::tickmap::Tickmap;
-use crate::util::get_closer_limit;
+use crate::util::{swap_within_pool, SwapTotals};
 use crate::ErrorCode::*;
 use crate::*;
 use crate::{decimals::*, referral::whitelist::contains_owner};
@@ -22,6 +20,11 @@ pub struct Swap<'info> {
         bump = pool.load()?.bump
     )]
     pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"poolstatsv1", pool.key().as_ref()],
+        bump = pool_stats.load()?.bump
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStats>,
     #[account(mut,
         constraint = tickmap.to_account_info().key == &pool.load()?.tickmap @ InvalidTickmap,
         constraint = tickmap.to_account_info().owner == program_id @ InvalidTickmapOwner
@@ -164,136 +167,34 @@ impl<'info> Swap<'info> {
             None => None,
         };
 
-        // limit is on the right side of price
-        if x_to_y {
-            require!(
-                { pool.sqrt_price } > sqrt_price_limit
-                    && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE),
-                WrongLimit
-            );
-        } else {
-            require!(
-                { pool.sqrt_price } < sqrt_price_limit
-                    && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE),
-                WrongLimit
-            );
-        }
-
-        let mut remaining_amount = TokenAmount(amount);
-
-        let mut total_amount_in = TokenAmount(0);
-        let mut total_amount_out = TokenAmount(0);
-        let mut total_amount_referral = TokenAmount(0);
-
-        while !remaining_amount.is_zero() {
-            let (swap_limit, limiting_tick) = get_closer_limit(
-                sqrt_price_limit,
-                x_to_y,
-                pool.current_tick_index,
-                pool.tick_spacing,
-                &tickmap,
-            )?;
-
-            let result = compute_swap_step(
-                pool.sqrt_price,
-                swap_limit,
-                pool.liquidity,
-                remaining_amount,
-                by_amount_in,
-                pool.fee,
-            );
-            // make remaining amount smaller
-            if by_amount_in {
-                remaining_amount -= result.amount_in + result.fee_amount;
-            } else {
-                remaining_amount -= result.amount_out;
-            }
-
-            total_amount_referral += match ref_account.is_some() {
-                true => pool.add_fee(result.fee_amount, FixedPoint::from_scale(2, 1), x_to_y),
-                false => pool.add_fee(result.fee_amount, FixedPoint::from_integer(0), x_to_y),
-            };
-
-            pool.sqrt_price = result.next_price_sqrt;
-
-            total_amount_in += result.amount_in + result.fee_amount;
-            total_amount_out += result.amount_out;
-
-            // Fail if price would go over swap limit
-            if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
-                return Err(ErrorCode::PriceLimitReached.into());
-            }
-
-            // crossing tick
-            // trunk-ignore(clippy/unnecessary_unwrap)
-            if result.next_price_sqrt == swap_limit && limiting_tick.is_some() {
-                let (tick_index, initialized) = limiting_tick.unwrap();
-
-                let is_enough_amount_to_cross = is_enough_amount_to_push_price(
-                    remaining_amount,
-                    result.next_price_sqrt,
-                    pool.liquidity,
-                    pool.fee,
-                    by_amount_in,
-                    x_to_y,
-                );
-
-                if initialized {
-                    // Calculating address of the crossed tick
-                    let (tick_address, _) = Pubkey::find_program_address(
-                        &[
-                            b"tickv1",
-                            ctx.accounts.pool.to_account_info().key.as_ref(),
-                            &tick_index.to_le_bytes(),
-                        ],
-                        ctx.program_id,
-                    );
-
-                    // Finding the correct tick in remaining accounts
-                    let loader = match ctx
-                        .remaining_accounts
-                        .iter()
-                        .find(|account| *account.key == tick_address)
-                    {
-                        Some(account) => AccountLoader::<'_, Tick>::try_from(account).unwrap(),
-                        None => return Err(ErrorCode::TickNotFound.into()),
-                    };
-                    let mut tick = loader.load_mut().unwrap();
-
-                    // crossing tick
-                    if !x_to_y || is_enough_amount_to_cross {
-                        msg!("INVARIANT: CROSSING TICK {} ", { tick.index });
-                        cross_tick(&mut tick, &mut pool, get_current_timestamp())?;
-                    } else if !remaining_amount.is_zero() {
-                        if by_amount_in {
-                            pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y);
-                            total_amount_in += remaining_amount;
-                        }
-                        remaining_amount = TokenAmount(0);
-                    }
-                }
-                // set tick to limit (below if price is going down, because current tick should always be below price)
-                pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
-                    tick_index.checked_sub(pool.tick_spacing as i32).unwrap()
-                } else {
-                    tick_index
-                };
-            } else {
-                assert!(
-                    pool.current_tick_index
-                        .checked_rem(pool.tick_spacing.into())
-                        .unwrap()
-                        == 0,
-                    "tick not divisible by spacing"
-                );
-                pool.current_tick_index =
-                    get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
-            }
-        }
-
-        if total_amount_out.0 == 0 {
-            return Err(ErrorCode::NoGainSwap.into());
-        }
+        let referral_fee = match ref_account.is_some() {
+            true => FixedPoint::from_scale(2, 1),
+            false => FixedPoint::from_integer(0),
+        };
+        let SwapTotals {
+            amount_in: total_amount_in,
+            amount_out: total_amount_out,
+            amount_referral: total_amount_referral,
+            amount_fee: total_amount_fee,
+        } = swap_within_pool(
+            &mut pool,
+            ctx.accounts.pool.to_account_info().key,
+            &tickmap,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+            referral_fee,
+        )?;
+        ctx.accounts.pool_stats.load_mut()?.record_swap(
+            x_to_y,
+            total_amount_in,
+            total_amount_out,
+            total_amount_fee,
+            get_current_timestamp(),
+        )?;
 
         // Execute swap
         let (take_ctx, send_ctx) = match x_to_y {
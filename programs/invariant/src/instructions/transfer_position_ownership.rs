@@ -1,5 +1,6 @@
 use crate::structs::position::Position;
 use crate::structs::position_list::PositionList;
+use crate::util::get_current_timestamp;
 use crate::ErrorCode::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
@@ -57,6 +58,8 @@ impl<'info> TransferPositionOwnership<'info> {
         let new_position = &mut self.new_position.load_init()?;
         let removed_position = &mut self.removed_position.load_mut()?;
 
+        removed_position.check_unlocked(get_current_timestamp())?;
+
         owner_list.head = owner_list.head.checked_sub(1).unwrap();
         recipient_list.head = recipient_list.head.checked_add(1).unwrap();
 
@@ -75,6 +78,13 @@ impl<'info> TransferPositionOwnership<'info> {
                 tokens_owed_x: removed_position.tokens_owed_x,
                 tokens_owed_y: removed_position.tokens_owed_y,
                 last_slot: removed_position.last_slot,
+                // approvals are not carried over to the recipient
+                operator: Pubkey::default(),
+                operator_permissions: 0,
+                unlock_timestamp: removed_position.unlock_timestamp,
+                reward_growth_inside: removed_position.reward_growth_inside,
+                rewards_owed: removed_position.rewards_owed,
+                range_nonce: removed_position.range_nonce,
                 bump, // assign new bump
             };
         }
@@ -96,6 +106,12 @@ impl<'info> TransferPositionOwnership<'info> {
                 tokens_owed_x: last_position.tokens_owed_x,
                 tokens_owed_y: last_position.tokens_owed_y,
                 last_slot: last_position.last_slot,
+                operator: last_position.operator,
+                operator_permissions: last_position.operator_permissions,
+                unlock_timestamp: last_position.unlock_timestamp,
+                reward_growth_inside: last_position.reward_growth_inside,
+                rewards_owed: last_position.rewards_owed,
+                range_nonce: last_position.range_nonce,
                 bump: removed_position.bump, // stay with the same bump
             };
             *last_position = Default::default();
@@ -58,7 +58,7 @@ impl<'info> UpdateSecondsPerLiquidity<'info> {
         let current_time = get_current_timestamp();
         let position = &mut self.position.load_mut()?;
         position.seconds_per_liquidity_inside =
-            calculate_seconds_per_liquidity_inside(lower_tick, upper_tick, pool, current_time);
+            calculate_seconds_per_liquidity_inside(lower_tick, upper_tick, pool, current_time)?;
         position.last_slot = get_current_slot();
 
         Ok(())
@@ -2,7 +2,6 @@ mod decimals;
 mod errors;
 mod instructions;
 mod interfaces;
-mod log;
 mod macros;
 mod math;
 mod referral;
@@ -48,6 +47,10 @@ pub mod invariant {
             .handler(init_tick, *ctx.bumps.get("pool").unwrap())
     }
 
+    pub fn create_pool_stats(ctx: Context<CreatePoolStats>) -> ProgramResult {
+        ctx.accounts.handler(*ctx.bumps.get("pool_stats").unwrap())
+    }
+
     pub fn swap<'info>(
         ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
         x_to_y: bool,
@@ -97,6 +100,52 @@ pub mod invariant {
             .handler(index, lower_tick_index, upper_tick_index)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn rebalance_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, RebalancePosition<'info>>,
+        _index: u32,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: Price,
+        slippage_limit_upper: Price,
+        swap_x_to_y: bool,
+        swap_amount: u64,
+        swap_sqrt_price_limit: Price,
+    ) -> ProgramResult {
+        RebalancePosition::handler(
+            ctx,
+            lower_tick_index,
+            upper_tick_index,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+            swap_x_to_y,
+            swap_amount,
+            swap_sqrt_price_limit,
+        )
+    }
+
+    pub fn lock_position(
+        ctx: Context<LockPosition>,
+        _index: u32,
+        unlock_timestamp: u64,
+    ) -> ProgramResult {
+        ctx.accounts.handler(unlock_timestamp)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
+    pub fn migrate_tick(ctx: Context<MigrateTick>) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
     pub fn transfer_position_ownership(
         ctx: Context<TransferPositionOwnership>,
         index: u32,
@@ -105,6 +154,21 @@ pub mod invariant {
             .handler(index, *ctx.bumps.get("new_position").unwrap())
     }
 
+    pub fn approve_position_operator(
+        ctx: Context<ApprovePositionOperator>,
+        _index: u32,
+        permissions: u8,
+    ) -> ProgramResult {
+        ctx.accounts.handler(permissions)
+    }
+
+    pub fn revoke_position_operator(
+        ctx: Context<RevokePositionOperator>,
+        _index: u32,
+    ) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
     pub fn claim_fee(
         ctx: Context<ClaimFee>,
         _index: u32,
@@ -114,6 +178,31 @@ pub mod invariant {
         ctx.accounts.handler()
     }
 
+    pub fn batch_claim_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchClaimFee<'info>>,
+    ) -> ProgramResult {
+        BatchClaimFee::handler(ctx)
+    }
+
+    pub fn claim_reward(
+        ctx: Context<ClaimReward>,
+        _index: u32,
+        _lower_tick_index: i32,
+        _upper_tick_index: i32,
+        reward_index: u8,
+    ) -> ProgramResult {
+        ctx.accounts.handler(reward_index)
+    }
+
+    pub fn compound_fee(
+        ctx: Context<CompoundFee>,
+        _index: u32,
+        _lower_tick_index: i32,
+        _upper_tick_index: i32,
+    ) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
     pub fn update_seconds_per_liquidity(
         ctx: Context<UpdateSecondsPerLiquidity>,
         _lower_tick_index: i32,
@@ -123,6 +212,21 @@ pub mod invariant {
         ctx.accounts.handler()
     }
 
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn initialize_reward(ctx: Context<InitializeReward>, reward_index: u8) -> ProgramResult {
+        ctx.accounts.handler(reward_index)
+    }
+
+    pub fn set_reward_emission(
+        ctx: Context<SetRewardEmission>,
+        reward_index: u8,
+        emission_per_second: FixedPoint,
+        emission_end: u64,
+    ) -> ProgramResult {
+        ctx.accounts
+            .handler(reward_index, emission_per_second, emission_end)
+    }
+
     #[access_control(receiver(&ctx.accounts.pool, &ctx.accounts.authority))]
     pub fn withdraw_protocol_fee(ctx: Context<WithdrawProtocolFee>) -> ProgramResult {
         ctx.accounts.handler()
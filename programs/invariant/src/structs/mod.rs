@@ -1,6 +1,7 @@
 pub mod fee_tier;
 pub mod oracle;
 pub mod pool;
+pub mod pool_stats;
 pub mod position;
 pub mod position_list;
 pub mod state;
@@ -10,6 +11,7 @@ pub mod tickmap;
 pub use fee_tier::*;
 pub use oracle::*;
 pub use pool::*;
+pub use pool_stats::*;
 pub use position::*;
 pub use position_list::*;
 pub use state::*;
@@ -1,6 +1,34 @@
+use crate::structs::tick::Tick;
 use crate::*;
 use anchor_lang::prelude::*;
 use decimals::*;
+use invariant_math::utils::TrackableError;
+use invariant_math::{
+    err, from_result, function, impl_swap_state, location, ok_or_mark_trace, trace,
+};
+use std::cmp::min;
+
+pub use invariant_math::swap::PoolState;
+
+// anchor sizes IDL arrays from consts declared in this crate, so the shared value is restated
+// the swap state impls below don't compile unless both match
+pub const REWARDS_PER_POOL: usize = 3;
+
+// accounts created before rewards were added, discriminator included
+pub const LEGACY_POOL_SIZE: usize = 400;
+
+#[zero_copy]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PoolReward {
+    pub token: Pubkey,
+    pub reserve: Pubkey,
+    pub authority: Pubkey, // can change emission rate
+    pub emission_per_second: FixedPoint,
+    pub growth_global: FeeGrowth,
+    pub emission_end: u64,     // nothing is emitted afterwards
+    pub unclaimed: FixedPoint, // emitted but not claimed yet, kept in the reserve
+}
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -28,43 +56,20 @@ pub struct Pool {
     pub fee_receiver: Pubkey,
     pub oracle_address: Pubkey,
     pub oracle_initialized: bool,
+    pub rewards: [PoolReward; REWARDS_PER_POOL],
+    pub rewards_last_timestamp: u64,
     pub bump: u8,
 }
 
 impl Pool {
-    #[allow(unaligned_references)]
     pub fn add_fee(
         &mut self,
         amount: TokenAmount,
         ref_percentage: FixedPoint,
         in_x: bool,
-    ) -> TokenAmount {
-        let protocol_fee = TokenAmount::from_decimal_up(amount.big_mul_up(self.protocol_fee));
-        let ref_fee = match ref_percentage.is_zero() {
-            true => TokenAmount(0),
-            false => TokenAmount::from_decimal(amount.big_mul(ref_percentage)),
-        };
-        let pool_fee = amount - protocol_fee - ref_fee;
-
-        if (pool_fee.is_zero() && protocol_fee.is_zero()) || self.liquidity.is_zero() {
-            return ref_fee;
-        }
-        let fee_growth = FeeGrowth::from_fee(self.liquidity, pool_fee);
-
-        if in_x {
-            self.fee_growth_global_x = self.fee_growth_global_x.unchecked_add(fee_growth);
-            self.fee_protocol_token_x = self
-                .fee_protocol_token_x
-                .checked_add(protocol_fee.0)
-                .unwrap();
-        } else {
-            self.fee_growth_global_y = self.fee_growth_global_y.unchecked_add(fee_growth);
-            self.fee_protocol_token_y = self
-                .fee_protocol_token_y
-                .checked_add(protocol_fee.0)
-                .unwrap();
-        }
-        ref_fee
+    ) -> Result<TokenAmount> {
+        Ok(PoolState::add_fee(self, amount, ref_percentage, in_x)
+            .map_err(trace_error(ErrorCode::FeeOverflow))?)
     }
 
     pub fn update_liquidity_safely(&mut self, liquidity_delta: Liquidity, add: bool) -> Result<()> {
@@ -81,13 +86,75 @@ impl Pool {
         Ok(())
     }
 
-    pub fn update_seconds_per_liquidity_global(&mut self, current_timestamp: u64) {
-        self.seconds_per_liquidity_global = self.seconds_per_liquidity_global.unchecked_add(
-            FixedPoint::from_integer(current_timestamp.checked_sub(self.last_timestamp).unwrap())
-                / self.liquidity,
-        );
+    // emissions are distributed among liquidity in range
+    // without liquidity nobody could claim them, so the emission is postponed instead
+    pub fn update_rewards(&mut self, current_timestamp: u64) -> Result<()> {
+        let last_timestamp = self.rewards_last_timestamp;
 
-        self.last_timestamp = current_timestamp;
+        let mut rewards = self.rewards;
+        for reward in rewards.iter_mut() {
+            let end_timestamp = min(current_timestamp, reward.emission_end);
+            if { reward.emission_per_second }.is_zero() || end_timestamp <= last_timestamp {
+                continue;
+            }
+            if self.liquidity.is_zero() {
+                reward.emission_end =
+                    { reward.emission_end }.saturating_add(current_timestamp - last_timestamp);
+                continue;
+            }
+            let (growth, unclaimed) = { reward.emission_per_second }
+                .get()
+                .checked_mul((end_timestamp - last_timestamp) as u128)
+                .ok_or_else(|| err!(TrackableError::MUL))
+                .and_then(|emitted| {
+                    let emitted = FixedPoint::new(emitted);
+                    let growth =
+                        ok_or_mark_trace!(FeeGrowth::from_reward(self.liquidity, emitted))?;
+                    let unclaimed = from_result!({ reward.unclaimed }.checked_add(emitted))?;
+                    Ok((growth, unclaimed))
+                })
+                .map_err(trace_error(ErrorCode::RewardOverflow))?;
+
+            reward.growth_global = { reward.growth_global }.unchecked_add(growth);
+            reward.unclaimed = unclaimed;
+        }
+        self.rewards = rewards;
+
+        self.rewards_last_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    // claimed tokens leave the reserve, so they no longer count as owed
+    pub fn settle_reward_claim(&mut self, index: usize, amount: TokenAmount) -> Result<()> {
+        self.rewards[index].unclaimed = { self.rewards[index].unclaimed }
+            .checked_sub(FixedPoint::from_decimal(amount))
+            .map_err(|_| ErrorCode::InsufficientUnclaimedRewards)?;
+        Ok(())
+    }
+
+    // reserve has to cover what is owed and what will be emitted until the end
+    pub fn check_reward_reserve(
+        &self,
+        index: usize,
+        reserve_amount: TokenAmount,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let reward = self.rewards[index];
+        let remaining = reward.emission_end.saturating_sub(current_timestamp);
+
+        let required = { reward.emission_per_second }
+            .get()
+            .checked_mul(remaining as u128)
+            .ok_or_else(|| err!(TrackableError::MUL))
+            .and_then(|emission| {
+                from_result!(FixedPoint::new(emission).checked_add(reward.unclaimed))
+            })
+            .map_err(trace_error(ErrorCode::RewardOverflow))?;
+        require!(
+            required <= FixedPoint::from_decimal(reserve_amount),
+            InsufficientRewardReserve
+        );
+        Ok(())
     }
 
     pub fn set_oracle(&mut self, address: Pubkey) {
@@ -96,10 +163,15 @@ impl Pool {
     }
 }
 
+impl_swap_state!(Pool, Tick);
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::util::move_bump_to_end;
+    use anchor_lang::__private::bytemuck;
+    use anchor_lang::Discriminator;
 
     #[test]
     fn test_update_liquidity_safely_pool() {
@@ -155,7 +227,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::from_integer(6);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_integer(0), true);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_integer(0), true)
+                .unwrap();
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::from_scale(4, 1));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::from_integer(0));
             assert_eq!({ pool.fee_protocol_token_x }, 2);
@@ -166,7 +240,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::from_integer(200);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_scale(1, 2), false);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_scale(1, 2), false)
+                .unwrap();
 
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::from_integer(0));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::from_scale(158, 1));
@@ -178,7 +254,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::new(1);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_scale(2, 1), true);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_scale(2, 1), true)
+                .unwrap();
 
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::new(0));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::new(0));
@@ -186,6 +264,24 @@ mod tests {
             assert_eq!({ pool.fee_protocol_token_y }, 0);
             assert_eq!(ref_fee, TokenAmount(0));
         }
+        // protocol and referral fees exceed the amount
+        {
+            let mut pool = Pool {
+                protocol_fee: FixedPoint::from_scale(95, 2),
+                ..pool
+            };
+            let result = pool.add_fee(TokenAmount(10), FixedPoint::from_scale(2, 1), true);
+            assert!(result.is_err());
+        }
+        // protocol fee doesn't fit into the counter
+        {
+            let mut pool = Pool {
+                fee_protocol_token_y: u64::MAX,
+                ..pool
+            };
+            let result = pool.add_fee(TokenAmount(10), FixedPoint::from_integer(0), false);
+            assert!(result.is_err());
+        }
     }
 
     #[test]
@@ -200,7 +296,9 @@ mod tests {
                 ..Default::default()
             };
             let current_timestamp: u64 = 18446;
-            test_pool.update_seconds_per_liquidity_global(current_timestamp);
+            test_pool
+                .update_seconds_per_liquidity_global(current_timestamp)
+                .unwrap();
             let result = test_pool.seconds_per_liquidity_global;
             assert_eq!(
                 result,
@@ -219,7 +317,9 @@ mod tests {
                 ..Default::default()
             };
             let current_timestamp: u64 = u64::MAX;
-            test_pool.update_seconds_per_liquidity_global(current_timestamp);
+            test_pool
+                .update_seconds_per_liquidity_global(current_timestamp)
+                .unwrap();
             let result = test_pool.seconds_per_liquidity_global;
             assert_eq!(
                 result,
@@ -229,4 +329,201 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_update_rewards() {
+        let reward = PoolReward {
+            emission_per_second: FixedPoint::from_integer(2),
+            emission_end: 100,
+            ..Default::default()
+        };
+        // growth is shared by liquidity in range
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                rewards_last_timestamp: 10,
+                ..Default::default()
+            };
+            pool.update_rewards(20).unwrap();
+
+            // 2 * 10 / 4
+            assert_eq!(
+                { pool.rewards[0].growth_global },
+                FeeGrowth::from_integer(5)
+            );
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::from_integer(20));
+            assert_eq!({ pool.rewards[1].growth_global }, FeeGrowth::new(0));
+            assert_eq!({ pool.rewards_last_timestamp }, 20);
+            assert_eq!(
+                pool.reward_growth_global(),
+                [
+                    FeeGrowth::from_integer(5),
+                    FeeGrowth::new(0),
+                    FeeGrowth::new(0)
+                ]
+            );
+        }
+        // nothing is emitted without liquidity, the emission is postponed
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(0),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                rewards_last_timestamp: 10,
+                ..Default::default()
+            };
+            pool.update_rewards(20).unwrap();
+
+            assert_eq!({ pool.rewards[0].growth_global }, FeeGrowth::new(0));
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::new(0));
+            assert_eq!({ pool.rewards[0].emission_end }, 110);
+            assert_eq!({ pool.rewards[1].emission_end }, 0);
+            assert_eq!({ pool.rewards_last_timestamp }, 20);
+
+            // whole emission reaches liquidity that comes later
+            pool.liquidity = Liquidity::from_integer(4);
+            pool.update_rewards(200).unwrap();
+
+            // 2 * 90 / 4
+            assert_eq!(
+                { pool.rewards[0].growth_global },
+                FeeGrowth::from_integer(45)
+            );
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::from_integer(180));
+            assert_eq!({ pool.rewards[0].emission_end }, 110);
+        }
+        // finished emission isn't postponed
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(0),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                rewards_last_timestamp: 100,
+                ..Default::default()
+            };
+            pool.update_rewards(200).unwrap();
+
+            assert_eq!({ pool.rewards[0].emission_end }, 100);
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::new(0));
+        }
+        // emission stops at the end
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                rewards_last_timestamp: 90,
+                ..Default::default()
+            };
+            pool.update_rewards(200).unwrap();
+
+            // 2 * 10 / 4
+            assert_eq!(
+                { pool.rewards[0].growth_global },
+                FeeGrowth::from_integer(5)
+            );
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::from_integer(20));
+            assert_eq!({ pool.rewards_last_timestamp }, 200);
+
+            pool.update_rewards(300).unwrap();
+            assert_eq!(
+                { pool.rewards[0].growth_global },
+                FeeGrowth::from_integer(5)
+            );
+        }
+        // emission overflow
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                rewards: [
+                    PoolReward {
+                        emission_per_second: FixedPoint::new(u128::MAX),
+                        emission_end: u64::MAX,
+                        ..Default::default()
+                    },
+                    PoolReward::default(),
+                    PoolReward::default(),
+                ],
+                rewards_last_timestamp: 10,
+                ..Default::default()
+            };
+            let result = pool.update_rewards(20);
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_reward_reserve() {
+        let mut pool = Pool {
+            rewards: [
+                PoolReward {
+                    emission_per_second: FixedPoint::from_integer(2),
+                    emission_end: 100,
+                    unclaimed: FixedPoint::from_integer(30),
+                    ..Default::default()
+                },
+                PoolReward::default(),
+                PoolReward::default(),
+            ],
+            ..Default::default()
+        };
+        // 30 owed and 2 * 50 still to be emitted
+        {
+            assert!(pool.check_reward_reserve(0, TokenAmount(130), 50).is_ok());
+            assert!(pool.check_reward_reserve(0, TokenAmount(129), 50).is_err());
+            // emission already ended
+            assert!(pool.check_reward_reserve(0, TokenAmount(30), 150).is_ok());
+        }
+        // claimed tokens are no longer owed
+        {
+            pool.settle_reward_claim(0, TokenAmount(10)).unwrap();
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::from_integer(20));
+            assert!(pool.check_reward_reserve(0, TokenAmount(120), 50).is_ok());
+
+            // claiming more than is owed fails and leaves the pool untouched
+            assert!(pool.settle_reward_claim(0, TokenAmount(25)).is_err());
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::from_integer(20));
+
+            pool.settle_reward_claim(0, TokenAmount(20)).unwrap();
+            assert_eq!({ pool.rewards[0].unclaimed }, FixedPoint::new(0));
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_pool() {
+        let pool = Pool {
+            token_x: Pubkey::new_unique(),
+            token_y: Pubkey::new_unique(),
+            tick_spacing: 10,
+            liquidity: Liquidity::from_integer(100),
+            sqrt_price: Price::from_integer(1),
+            current_tick_index: -20,
+            fee_receiver: Pubkey::new_unique(),
+            oracle_initialized: true,
+            bump: 253,
+            ..Default::default()
+        };
+        let mut expected = Pool::discriminator().to_vec();
+        expected.extend_from_slice(bytemuck::bytes_of(&pool));
+
+        // legacy layout ends with bump right after the oracle flag
+        let rewards_offset = std::ptr::addr_of!(pool.rewards) as usize - &pool as *const _ as usize;
+        assert_eq!(LEGACY_POOL_SIZE, 8 + rewards_offset + 1);
+
+        let mut data = expected[..LEGACY_POOL_SIZE - 1].to_vec();
+        data.push(pool.bump);
+        // realloc doesn't have to zero the new space
+        data.resize(expected.len(), u8::MAX);
+
+        move_bump_to_end(&mut data, LEGACY_POOL_SIZE);
+        assert_eq!(data, expected);
+
+        let migrated: &Pool = bytemuck::from_bytes(&data[8..]);
+        assert_eq!(*migrated, pool);
+        // stats live in their own account, the pool ends with rewards
+        assert_eq!(
+            { migrated.rewards },
+            [PoolReward::default(); REWARDS_PER_POOL]
+        );
+        assert_eq!({ migrated.bump }, 253);
+    }
 }
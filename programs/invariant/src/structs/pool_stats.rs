@@ -0,0 +1,114 @@
+use crate::*;
+use anchor_lang::prelude::*;
+use decimals::*;
+
+// statistics of a pool kept apart from it, they don't affect the state of the curve
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PoolStats {
+    pub pool: Pubkey,
+    pub volume_x: u128, // cumulative swapped amounts, fees included
+    pub volume_y: u128,
+    pub fees_x: u128, // cumulative swap fees, before the protocol and referral split
+    pub fees_y: u128,
+    pub swap_count: u64,
+    pub last_swap_timestamp: u64,
+    pub bump: u8,
+}
+
+impl PoolStats {
+    pub fn record_swap(
+        &mut self,
+        x_to_y: bool,
+        amount_in: TokenAmount,
+        amount_out: TokenAmount,
+        amount_fee: TokenAmount,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let (volume_in, volume_out, fees) = match x_to_y {
+            true => (self.volume_x, self.volume_y, self.fees_x),
+            false => (self.volume_y, self.volume_x, self.fees_y),
+        };
+        let volume_in = volume_in
+            .checked_add(amount_in.0 as u128)
+            .ok_or(ErrorCode::TokenAmountOverflow)?;
+        let volume_out = volume_out
+            .checked_add(amount_out.0 as u128)
+            .ok_or(ErrorCode::TokenAmountOverflow)?;
+        let fees = fees
+            .checked_add(amount_fee.0 as u128)
+            .ok_or(ErrorCode::FeeOverflow)?;
+
+        if x_to_y {
+            self.volume_x = volume_in;
+            self.volume_y = volume_out;
+            self.fees_x = fees;
+        } else {
+            self.volume_y = volume_in;
+            self.volume_x = volume_out;
+            self.fees_y = fees;
+        }
+        // only a counter, it can't block swaps
+        self.swap_count = self.swap_count.wrapping_add(1);
+        self.last_swap_timestamp = current_timestamp;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_swap() {
+        let mut stats = PoolStats::default();
+        // x to y
+        {
+            stats
+                .record_swap(true, TokenAmount(100), TokenAmount(90), TokenAmount(1), 10)
+                .unwrap();
+            assert_eq!({ stats.volume_x }, 100);
+            assert_eq!({ stats.volume_y }, 90);
+            assert_eq!({ stats.fees_x }, 1);
+            assert_eq!({ stats.fees_y }, 0);
+            assert_eq!({ stats.swap_count }, 1);
+            assert_eq!({ stats.last_swap_timestamp }, 10);
+        }
+        // y to x
+        {
+            stats
+                .record_swap(false, TokenAmount(50), TokenAmount(55), TokenAmount(2), 20)
+                .unwrap();
+            assert_eq!({ stats.volume_x }, 155);
+            assert_eq!({ stats.volume_y }, 140);
+            assert_eq!({ stats.fees_x }, 1);
+            assert_eq!({ stats.fees_y }, 2);
+            assert_eq!({ stats.swap_count }, 2);
+            assert_eq!({ stats.last_swap_timestamp }, 20);
+        }
+        // overflow leaves the stats untouched
+        {
+            stats.volume_x = u128::MAX;
+            let result =
+                stats.record_swap(true, TokenAmount(1), TokenAmount(1), TokenAmount(0), 30);
+            assert!(result.is_err());
+            assert_eq!({ stats.volume_y }, 140);
+            assert_eq!({ stats.swap_count }, 2);
+            assert_eq!({ stats.last_swap_timestamp }, 20);
+        }
+        // swap count wraps around
+        {
+            let mut stats = PoolStats {
+                swap_count: u64::MAX,
+                ..Default::default()
+            };
+            stats
+                .record_swap(true, TokenAmount(1), TokenAmount(1), TokenAmount(0), 40)
+                .unwrap();
+            assert_eq!({ stats.swap_count }, 0);
+            assert_eq!({ stats.last_swap_timestamp }, 40);
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use crate::decimals::*;
-use crate::structs::pool::Pool;
+use crate::structs::pool::{Pool, PoolState, REWARDS_PER_POOL};
 use crate::structs::tick::Tick;
 use crate::*;
 use anchor_lang::prelude::*;
+use invariant_math::utils::TrackableError;
+use invariant_math::{err, from_result, function, location, ok_or_mark_trace, trace};
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -20,9 +22,23 @@ pub struct Position {
     pub last_slot: u64,
     pub tokens_owed_x: FixedPoint,
     pub tokens_owed_y: FixedPoint,
+    pub operator: Pubkey,
+    pub operator_permissions: u8,
+    pub unlock_timestamp: u64, // liquidity can't leave the position before
+    pub reward_growth_inside: [FeeGrowth; REWARDS_PER_POOL],
+    pub rewards_owed: [FixedPoint; REWARDS_PER_POOL],
+    pub range_nonce: u64, // changes whenever the liquidity is moved to another range
     pub bump: u8,
 }
 
+// accounts created before the operator fields were added, discriminator included
+pub const LEGACY_POSITION_SIZE: usize = 201;
+
+// permissions that can be granted to a position operator
+pub const OPERATOR_CLAIM_FEE: u8 = 1; // claim_fee, batch_claim_fee, compound_fee
+pub const OPERATOR_MANAGE_LIQUIDITY: u8 = 2; // remove_position, rebalance_position
+pub const OPERATOR_ALL_PERMISSIONS: u8 = OPERATOR_CLAIM_FEE | OPERATOR_MANAGE_LIQUIDITY;
+
 impl Position {
     pub fn modify(
         &mut self,
@@ -34,10 +50,12 @@ impl Position {
         current_timestamp: u64,
     ) -> Result<(TokenAmount, TokenAmount)> {
         if !pool.liquidity.is_zero() {
-            pool.update_seconds_per_liquidity_global(current_timestamp);
+            pool.update_seconds_per_liquidity_global(current_timestamp)
+                .map_err(trace_error(ErrorCode::NegativeTime))?;
         } else {
             pool.last_timestamp = current_timestamp;
         }
+        pool.update_rewards(current_timestamp)?;
 
         // calculate dynamically limit allows easy modification
         let max_liquidity_per_tick = calculate_max_liquidity_per_tick(pool.tick_spacing);
@@ -56,6 +74,15 @@ impl Position {
             pool.fee_growth_global_y,
         );
 
+        // rewards accumulate with liquidity from before the change
+        let reward_growth_inside = calculate_reward_growth_inside(
+            *lower_tick,
+            *upper_tick,
+            pool.current_tick_index,
+            pool.reward_growth_global(),
+        );
+        self.update_rewards(reward_growth_inside)?;
+
         self.update(
             add,
             liquidity_delta,
@@ -73,6 +100,56 @@ impl Position {
         )
     }
 
+    // reinvests the whole units of owed fees into the position, the unusable remainder stays owed
+    pub fn compound(
+        &mut self,
+        pool: &mut Pool,
+        upper_tick: &mut Tick,
+        lower_tick: &mut Tick,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        // accumulate fees up to now
+        self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            Liquidity::new(0),
+            true,
+            current_timestamp,
+        )?;
+
+        let liquidity_delta = get_liquidity_for_deposit(
+            TokenAmount::from_decimal(self.tokens_owed_x),
+            TokenAmount::from_decimal(self.tokens_owed_y),
+            lower_tick.index,
+            upper_tick.index,
+            pool.current_tick_index,
+            pool.sqrt_price,
+        )
+        .unwrap_or(Liquidity::new(0));
+
+        if liquidity_delta.is_zero() {
+            return Ok(());
+        }
+
+        let (amount_x, amount_y) = self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            true,
+            current_timestamp,
+        )?;
+
+        self.tokens_owed_x = { self.tokens_owed_x }
+            .checked_sub(FixedPoint::from_decimal(amount_x))
+            .map_err(|_| ErrorCode::InsufficientOwedFees)?;
+        self.tokens_owed_y = { self.tokens_owed_y }
+            .checked_sub(FixedPoint::from_decimal(amount_y))
+            .map_err(|_| ErrorCode::InsufficientOwedFees)?;
+        Ok(())
+    }
+
     pub fn update(
         &mut self,
         sign: bool,
@@ -86,24 +163,67 @@ impl Position {
         );
 
         // calculate accumulated fee
-        let tokens_owed_x = fee_growth_inside_x
+        let tokens_owed_x = ok_or_mark_trace!(fee_growth_inside_x
             .unchecked_sub(self.fee_growth_inside_x)
-            .to_fee(self.liquidity);
-        let tokens_owed_y = fee_growth_inside_y
+            .checked_to_fee(self.liquidity))
+        .and_then(|owed| from_result!(self.tokens_owed_x.checked_add(owed)))
+        .map_err(trace_error(ErrorCode::FeeOverflow))?;
+        let tokens_owed_y = ok_or_mark_trace!(fee_growth_inside_y
             .unchecked_sub(self.fee_growth_inside_y)
-            .to_fee(self.liquidity);
+            .checked_to_fee(self.liquidity))
+        .and_then(|owed| from_result!(self.tokens_owed_y.checked_add(owed)))
+        .map_err(trace_error(ErrorCode::FeeOverflow))?;
 
         self.liquidity = self.calculate_new_liquidity_safely(sign, liquidity_delta)?;
         self.fee_growth_inside_x = fee_growth_inside_x;
         self.fee_growth_inside_y = fee_growth_inside_y;
-        // trunk-ignore(clippy/assign_op_pattern)
-        self.tokens_owed_x = self.tokens_owed_x + tokens_owed_x;
-        // trunk-ignore(clippy/assign_op_pattern)
-        self.tokens_owed_y = self.tokens_owed_y + tokens_owed_y;
+        self.tokens_owed_x = tokens_owed_x;
+        self.tokens_owed_y = tokens_owed_y;
 
         Ok(())
     }
 
+    // rewards accrue to the liquidity held since the last update
+    pub fn update_rewards(
+        &mut self,
+        reward_growth_inside: [FeeGrowth; REWARDS_PER_POOL],
+    ) -> Result<()> {
+        let previous_growth_inside = self.reward_growth_inside;
+        let mut rewards_owed = self.rewards_owed;
+
+        for i in 0..REWARDS_PER_POOL {
+            rewards_owed[i] = ok_or_mark_trace!(reward_growth_inside[i]
+                .unchecked_sub(previous_growth_inside[i])
+                .checked_to_fee(self.liquidity))
+            .and_then(|owed| from_result!(rewards_owed[i].checked_add(owed)))
+            .map_err(trace_error(ErrorCode::RewardOverflow))?;
+        }
+
+        self.rewards_owed = rewards_owed;
+        self.reward_growth_inside = reward_growth_inside;
+        Ok(())
+    }
+
+    // owner can do everything, operator only what was approved
+    pub fn check_authority(&self, signer: &Pubkey, permission: u8) -> Result<()> {
+        if self.owner == *signer {
+            return Ok(());
+        }
+        require!(
+            self.operator == *signer && self.operator_permissions & permission == permission,
+            ErrorCode::Unauthorized
+        );
+        Ok(())
+    }
+
+    pub fn check_unlocked(&self, current_timestamp: u64) -> Result<()> {
+        require!(
+            current_timestamp >= self.unlock_timestamp,
+            ErrorCode::PositionLocked
+        );
+        Ok(())
+    }
+
     pub fn initialized_id(&mut self, pool: &mut Pool) {
         self.id = pool.position_iterator;
         pool.position_iterator = pool.position_iterator.checked_add(1).unwrap();
@@ -136,6 +256,9 @@ impl Position {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::move_bump_to_end;
+    use anchor_lang::__private::bytemuck;
+    use anchor_lang::Discriminator;
 
     #[test]
     fn test_calculate_new_liquidity_safely() {
@@ -358,4 +481,246 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_compound() {
+        let lower_tick_index = -20;
+        let upper_tick_index = 40;
+        let liquidity = Liquidity::from_integer(1_000_000);
+        let position = Position {
+            liquidity,
+            lower_tick_index,
+            upper_tick_index,
+            tokens_owed_x: FixedPoint::from_integer(1000),
+            tokens_owed_y: FixedPoint::from_integer(1000),
+            ..Default::default()
+        };
+        let lower_tick = Tick {
+            index: lower_tick_index,
+            sign: true,
+            liquidity_change: liquidity,
+            liquidity_gross: liquidity,
+            ..Default::default()
+        };
+        let upper_tick = Tick {
+            index: upper_tick_index,
+            sign: false,
+            liquidity_change: liquidity,
+            liquidity_gross: liquidity,
+            ..Default::default()
+        };
+        // price sitting on the lower tick takes only x
+        {
+            let mut position = position;
+            let mut lower_tick = lower_tick;
+            let mut upper_tick = upper_tick;
+            let mut pool = Pool {
+                liquidity,
+                sqrt_price: calculate_price_sqrt(lower_tick_index).unwrap(),
+                current_tick_index: lower_tick_index,
+                tick_spacing: 10,
+                ..Default::default()
+            };
+
+            position
+                .compound(&mut pool, &mut upper_tick, &mut lower_tick, 0)
+                .unwrap();
+
+            assert!({ position.liquidity } > liquidity);
+            assert_eq!({ pool.liquidity }, { position.liquidity });
+            assert!({ position.tokens_owed_x } <= FixedPoint::from_integer(1));
+            assert_eq!({ position.tokens_owed_y }, FixedPoint::from_integer(1000));
+        }
+        // price sitting on the upper tick after crossing it down takes only y
+        {
+            let mut position = position;
+            let mut lower_tick = lower_tick;
+            let mut upper_tick = upper_tick;
+            let mut pool = Pool {
+                liquidity,
+                sqrt_price: calculate_price_sqrt(upper_tick_index).unwrap(),
+                current_tick_index: upper_tick_index - 10,
+                tick_spacing: 10,
+                ..Default::default()
+            };
+
+            position
+                .compound(&mut pool, &mut upper_tick, &mut lower_tick, 0)
+                .unwrap();
+
+            assert!({ position.liquidity } > liquidity);
+            assert_eq!({ pool.liquidity }, { position.liquidity });
+            assert_eq!({ position.tokens_owed_x }, FixedPoint::from_integer(1000));
+            assert!({ position.tokens_owed_y } <= FixedPoint::from_integer(1));
+        }
+    }
+
+    #[test]
+    fn test_check_authority() {
+        let owner = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        // no operator
+        {
+            let position = Position {
+                owner,
+                ..Default::default()
+            };
+            assert!(position
+                .check_authority(&owner, OPERATOR_ALL_PERMISSIONS)
+                .is_ok());
+            assert!(position
+                .check_authority(&operator, OPERATOR_CLAIM_FEE)
+                .is_err());
+            // default operator can't be impersonated
+            assert!(position
+                .check_authority(&Pubkey::default(), OPERATOR_CLAIM_FEE)
+                .is_err());
+        }
+        // claim only operator
+        {
+            let position = Position {
+                owner,
+                operator,
+                operator_permissions: OPERATOR_CLAIM_FEE,
+                ..Default::default()
+            };
+            assert!(position
+                .check_authority(&operator, OPERATOR_CLAIM_FEE)
+                .is_ok());
+            assert!(position
+                .check_authority(&operator, OPERATOR_MANAGE_LIQUIDITY)
+                .is_err());
+            assert!(position
+                .check_authority(&stranger, OPERATOR_CLAIM_FEE)
+                .is_err());
+        }
+        // operator with all permissions
+        {
+            let position = Position {
+                owner,
+                operator,
+                operator_permissions: OPERATOR_ALL_PERMISSIONS,
+                ..Default::default()
+            };
+            assert!(position
+                .check_authority(&operator, OPERATOR_CLAIM_FEE)
+                .is_ok());
+            assert!(position
+                .check_authority(&operator, OPERATOR_MANAGE_LIQUIDITY)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_check_unlocked() {
+        let position = Position {
+            unlock_timestamp: 100,
+            ..Default::default()
+        };
+        assert!(position.check_unlocked(99).is_err());
+        assert!(position.check_unlocked(100).is_ok());
+        assert!(position.check_unlocked(101).is_ok());
+
+        // not locked by default
+        let position = Position::default();
+        assert!(position.check_unlocked(0).is_ok());
+    }
+
+    #[test]
+    fn test_update_rewards() {
+        let mut position = Position {
+            liquidity: Liquidity::from_integer(2),
+            reward_growth_inside: [
+                FeeGrowth::from_integer(1),
+                FeeGrowth::new(0),
+                FeeGrowth::new(0),
+            ],
+            rewards_owed: [
+                FixedPoint::from_integer(1),
+                FixedPoint::new(0),
+                FixedPoint::new(0),
+            ],
+            ..Default::default()
+        };
+        let reward_growth_inside = [
+            FeeGrowth::from_integer(4),
+            FeeGrowth::from_scale(5, 1),
+            FeeGrowth::new(0),
+        ];
+
+        position.update_rewards(reward_growth_inside).unwrap();
+
+        assert_eq!({ position.reward_growth_inside }, reward_growth_inside);
+        assert_eq!(
+            { position.rewards_owed },
+            [
+                FixedPoint::from_integer(7),
+                FixedPoint::from_integer(1),
+                FixedPoint::new(0)
+            ]
+        );
+
+        // owed overflow is an error, not a panic
+        {
+            let mut position = Position {
+                liquidity: Liquidity::from_integer(1),
+                rewards_owed: [
+                    FixedPoint::new(u128::MAX),
+                    FixedPoint::new(0),
+                    FixedPoint::new(0),
+                ],
+                ..Default::default()
+            };
+            let result = position.update_rewards([
+                FeeGrowth::from_integer(1),
+                FeeGrowth::new(0),
+                FeeGrowth::new(0),
+            ]);
+            assert!(result.is_err());
+            assert_eq!({ position.rewards_owed[0] }, FixedPoint::new(u128::MAX));
+            assert_eq!({ position.reward_growth_inside[0] }, FeeGrowth::new(0));
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_position() {
+        let position = Position {
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            id: 7,
+            liquidity: Liquidity::from_integer(100),
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            fee_growth_inside_x: FeeGrowth::from_integer(3),
+            fee_growth_inside_y: FeeGrowth::from_integer(4),
+            seconds_per_liquidity_inside: FixedPoint::from_integer(5),
+            last_slot: 6,
+            tokens_owed_x: FixedPoint::from_integer(8),
+            tokens_owed_y: FixedPoint::from_integer(9),
+            bump: 254,
+            ..Default::default()
+        };
+        let mut expected = Position::discriminator().to_vec();
+        expected.extend_from_slice(bytemuck::bytes_of(&position));
+
+        // legacy layout ends with bump right after tokens owed
+        let operator_offset =
+            std::ptr::addr_of!(position.operator) as usize - &position as *const _ as usize;
+        assert_eq!(LEGACY_POSITION_SIZE, 8 + operator_offset + 1);
+
+        let mut data = expected[..LEGACY_POSITION_SIZE - 1].to_vec();
+        data.push(position.bump);
+        // realloc doesn't have to zero the new space
+        data.resize(expected.len(), u8::MAX);
+
+        move_bump_to_end(&mut data, LEGACY_POSITION_SIZE);
+        assert_eq!(data, expected);
+
+        let migrated: &Position = bytemuck::from_bytes(&data[8..]);
+        assert_eq!(*migrated, position);
+        // legacy positions were never locked
+        assert_eq!({ migrated.unlock_timestamp }, 0);
+        assert!(migrated.check_unlocked(0).is_ok());
+    }
 }
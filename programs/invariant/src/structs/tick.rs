@@ -1,3 +1,4 @@
+use crate::structs::pool::REWARDS_PER_POOL;
 use crate::*;
 use anchor_lang::prelude::*;
 use decimals::*;
@@ -16,9 +17,13 @@ pub struct Tick {
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_per_liquidity_outside: FixedPoint,
     pub seconds_outside: u64,
+    pub reward_growth_outside: [FeeGrowth; REWARDS_PER_POOL],
     pub bump: u8,
 }
 
+// accounts created before rewards were added, discriminator included
+pub const LEGACY_TICK_SIZE: usize = 150;
+
 impl Tick {
     pub fn update(
         &mut self,
@@ -76,6 +81,9 @@ impl Tick {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::move_bump_to_end;
+    use anchor_lang::__private::bytemuck;
+    use anchor_lang::Discriminator;
 
     #[test]
     fn test_update_liquidity_change() {
@@ -202,4 +210,33 @@ mod tests {
             assert!(result.is_err());
         }
     }
+
+    #[test]
+    fn test_migrate_legacy_tick() {
+        let tick = Tick {
+            pool: Pubkey::new_unique(),
+            index: -20,
+            sign: true,
+            liquidity_change: Liquidity::from_integer(5),
+            liquidity_gross: Liquidity::from_integer(5),
+            fee_growth_outside_x: FeeGrowth::from_integer(2),
+            seconds_outside: 30,
+            bump: 252,
+            ..Default::default()
+        };
+        let mut expected = Tick::discriminator().to_vec();
+        expected.extend_from_slice(bytemuck::bytes_of(&tick));
+
+        // legacy layout ends with bump right after seconds outside
+        let rewards_offset =
+            std::ptr::addr_of!(tick.reward_growth_outside) as usize - &tick as *const _ as usize;
+        assert_eq!(LEGACY_TICK_SIZE, 8 + rewards_offset + 1);
+
+        let mut data = expected[..LEGACY_TICK_SIZE - 1].to_vec();
+        data.push(tick.bump);
+        data.resize(expected.len(), u8::MAX);
+
+        move_bump_to_end(&mut data, LEGACY_TICK_SIZE);
+        assert_eq!(data, expected);
+    }
 }
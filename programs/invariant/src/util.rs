@@ -1,15 +1,18 @@
 use anchor_lang::__private::ErrorCode;
 use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
-use std::cell::RefMut;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
 use std::convert::TryInto;
 use std::io::Write;
 
-use crate::math::calculate_price_sqrt;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
-use crate::structs::tickmap::{get_search_limit, MAX_TICK, TICK_LIMIT};
+use crate::structs::tickmap::{MAX_TICK, TICK_LIMIT};
 use crate::*;
+use invariant_math::swap::{self, cross_tick};
+
+pub use invariant_math::swap::SwapTotals;
 
 pub fn check_ticks(tick_lower: i32, tick_upper: i32, tick_spacing: u16) -> Result<()> {
     // Check order
@@ -38,81 +41,57 @@ pub fn check_tick(tick_index: i32, tick_spacing: u16) -> Result<()> {
     Ok(())
 }
 
-// Finds closes initialized tick in direction of trade
-// and compares its price to the price limit of the trade
-pub fn get_closer_limit(
-    sqrt_price_limit: Price,
-    x_to_y: bool,
-    current_tick: i32,
-    tick_spacing: u16,
+// Moves the pool along the curve, crossing initialized ticks found in remaining accounts
+// Token transfers are left to the caller
+#[allow(clippy::too_many_arguments)]
+pub fn swap_within_pool<'info>(
+    pool: &mut Pool,
+    pool_address: &Pubkey,
     tickmap: &Tickmap,
-) -> Result<(Price, Option<(i32, bool)>)> {
-    let closes_tick_index = if x_to_y {
-        tickmap.prev_initialized(current_tick, tick_spacing)
-    } else {
-        tickmap.next_initialized(current_tick, tick_spacing)
-    };
-
-    match closes_tick_index {
-        Some(index) => {
-            let price = calculate_price_sqrt(index);
-            // trunk-ignore(clippy/if_same_then_else)
-            if x_to_y && price > sqrt_price_limit {
-                Ok((price, Some((index, true))))
-            } else if !x_to_y && price < sqrt_price_limit {
-                Ok((price, Some((index, true))))
-            } else {
-                Ok((sqrt_price_limit, None))
-            }
-        }
-        None => {
-            let index = get_search_limit(current_tick, tick_spacing, !x_to_y);
-            let price = calculate_price_sqrt(index);
-
-            require!(current_tick != index, LimitReached);
-
-            // trunk-ignore(clippy/if_same_then_else)
-            if x_to_y && price > sqrt_price_limit {
-                Ok((price, Some((index, false))))
-            } else if !x_to_y && price < sqrt_price_limit {
-                Ok((price, Some((index, false))))
-            } else {
-                Ok((sqrt_price_limit, None))
-            }
-        }
-    }
-}
-
-pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u64) -> Result<()> {
-    tick.fee_growth_outside_x = pool
-        .fee_growth_global_x
-        .unchecked_sub(tick.fee_growth_outside_x);
-    tick.fee_growth_outside_y = pool
-        .fee_growth_global_y
-        .unchecked_sub(tick.fee_growth_outside_y);
-
-    let seconds_passed: u64 = current_timestamp.checked_sub(pool.start_timestamp).unwrap();
-    tick.seconds_outside = seconds_passed - tick.seconds_outside;
-
-    if !pool.liquidity.is_zero() {
-        pool.update_seconds_per_liquidity_global(current_timestamp);
-    } else {
-        pool.last_timestamp = current_timestamp;
-    }
-    tick.seconds_per_liquidity_outside = pool
-        .seconds_per_liquidity_global
-        .unchecked_sub(tick.seconds_per_liquidity_outside);
-
-    // When going to higher tick net_liquidity should be added and for going lower subtracted
-    if (pool.current_tick_index >= tick.index) ^ tick.sign {
-        // trunk-ignore(clippy/assign_op_pattern)
-        pool.liquidity = pool.liquidity + tick.liquidity_change;
-    } else {
-        // trunk-ignore(clippy/assign_op_pattern)
-        pool.liquidity = pool.liquidity - tick.liquidity_change;
-    }
-
-    Ok(())
+    remaining_accounts: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+    x_to_y: bool,
+    amount: u64,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+    referral_fee: FixedPoint,
+) -> Result<SwapTotals> {
+    let current_timestamp = get_current_timestamp();
+    // emission is settled once, time doesn't pass between crossed ticks
+    pool.update_rewards(current_timestamp)?;
+
+    swap::swap_within_pool(
+        pool,
+        &tickmap.bitmap,
+        x_to_y,
+        amount,
+        by_amount_in,
+        sqrt_price_limit,
+        referral_fee,
+        |tick_index, pool| -> Result<()> {
+            // Calculating address of the crossed tick
+            let (tick_address, _) = Pubkey::find_program_address(
+                &[b"tickv1", pool_address.as_ref(), &tick_index.to_le_bytes()],
+                program_id,
+            );
+
+            // Finding the correct tick in remaining accounts
+            let loader = match remaining_accounts
+                .iter()
+                .find(|account| *account.key == tick_address)
+            {
+                Some(account) => AccountLoader::<'_, Tick>::try_from(account)?,
+                None => return Err(errors::ErrorCode::TickNotFound.into()),
+            };
+            let mut tick = loader.load_mut()?;
+
+            msg!("INVARIANT: CROSSING TICK {} ", { tick.index });
+            cross_tick(&mut *tick, pool, current_timestamp)
+                .map_err(trace_error(errors::ErrorCode::InvalidPoolLiquidity))?;
+            Ok(())
+        },
+    )
+    .map_err(swap_error)
 }
 
 pub fn get_current_timestamp() -> u64 {
@@ -143,51 +122,50 @@ pub fn close<'info>(
     Ok(())
 }
 
+// fields are only ever appended in front of bump, so a legacy account is grown in place
+// and its bump moved to the new end, leaving the appended fields zeroed
+pub fn migrate_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    discriminator: [u8; 8],
+    legacy_size: usize,
+    size: usize,
+) -> ProgramResult {
+    {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == legacy_size && data[..8] == discriminator,
+            InvalidMigration
+        );
+    }
+
+    let lamports_required = Rent::get()?.minimum_balance(size);
+    if lamports_required > info.lamports() {
+        invoke(
+            &system_instruction::transfer(payer.key, info.key, lamports_required - info.lamports()),
+            &[payer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+
+    info.realloc(size, false)?;
+    let mut data = info.try_borrow_mut_data()?;
+    move_bump_to_end(&mut data, legacy_size);
+    Ok(())
+}
+
+pub fn move_bump_to_end(data: &mut [u8], legacy_size: usize) {
+    let bump = data[legacy_size - 1];
+    data[legacy_size - 1..].fill(0);
+    data[data.len() - 1] = bump;
+}
+
 #[cfg(test)]
 mod test {
     use std::cell::RefCell;
 
     use super::*;
-
-    #[test]
-    fn test_get_closer_limit() -> Result<()> {
-        let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 0, 1);
-        // tick limit closer
-        {
-            let (result, from_tick) =
-                get_closer_limit(Price::from_integer(5), true, 100, 1, tickmap)?;
-
-            let expected = Price::from_integer(5);
-            assert_eq!(result, expected);
-            assert_eq!(from_tick, None);
-        }
-        // trade limit closer
-        {
-            let (result, from_tick) =
-                get_closer_limit(Price::from_scale(1, 1), true, 100, 1, tickmap)?;
-            let expected = Price::from_integer(1);
-            assert_eq!(result, expected);
-            assert_eq!(from_tick, Some((0, true)));
-        }
-        // other direction
-        {
-            let (result, from_tick) =
-                get_closer_limit(Price::from_integer(2), false, -5, 1, tickmap)?;
-            let expected = Price::from_integer(1);
-            assert_eq!(result, expected);
-            assert_eq!(from_tick, Some((0, true)));
-        }
-        // other direction
-        {
-            let (result, from_tick) =
-                get_closer_limit(Price::from_scale(1, 1), false, -100, 10, tickmap)?;
-            let expected = Price::from_scale(1, 1);
-            assert_eq!(result, expected);
-            assert_eq!(from_tick, None);
-        }
-        Ok(())
-    }
+    use crate::structs::pool::PoolReward;
 
     #[test]
     fn test_cross_tick() -> Result<()> {
@@ -234,7 +212,7 @@ mod test {
             let ref_tick = RefCell::new(tick);
             let mut refmut_tick = ref_tick.borrow_mut();
 
-            cross_tick(&mut refmut_tick, &mut pool, 18446744073709).ok();
+            cross_tick(&mut *refmut_tick, &mut pool, 18446744073709).unwrap();
 
             assert_eq!(*refmut_tick, result_tick);
             assert_eq!(pool, result_pool);
@@ -281,7 +259,7 @@ mod test {
 
             let fef_tick = RefCell::new(tick);
             let mut refmut_tick = fef_tick.borrow_mut();
-            cross_tick(&mut refmut_tick, &mut pool, 1844674407370).ok();
+            cross_tick(&mut *refmut_tick, &mut pool, 1844674407370).unwrap();
             assert_eq!(*refmut_tick, result_tick);
             assert_eq!(pool, result_pool);
         }
@@ -328,7 +306,7 @@ mod test {
 
             let fef_tick = RefCell::new(tick);
             let mut refmut_tick = fef_tick.borrow_mut();
-            cross_tick(&mut refmut_tick, &mut pool, 1844674407370953).ok();
+            cross_tick(&mut *refmut_tick, &mut pool, 1844674407370953).unwrap();
             assert_eq!(*refmut_tick, result_tick);
             assert_eq!(pool, result_pool);
         }
@@ -375,10 +353,78 @@ mod test {
 
             let fef_tick = RefCell::new(tick);
             let mut refmut_tick = fef_tick.borrow_mut();
-            cross_tick(&mut refmut_tick, &mut pool, 1844674407370953).ok();
+            cross_tick(&mut *refmut_tick, &mut pool, 1844674407370953).unwrap();
             assert_eq!(*refmut_tick, result_tick);
             assert_eq!(pool, result_pool);
         }
+        // liquidity_change over pool liquidity should fail instead of underflowing
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(4),
+                last_timestamp: 16,
+                start_timestamp: 15,
+                current_tick_index: 9,
+                ..Default::default()
+            };
+            let tick = Tick {
+                index: 45,
+                liquidity_change: Liquidity::new(10),
+                ..Default::default()
+            };
+
+            let fef_tick = RefCell::new(tick);
+            let mut refmut_tick = fef_tick.borrow_mut();
+            assert!(cross_tick(&mut *refmut_tick, &mut pool, 20).is_err());
+        }
+        // timestamp before pool start should fail
+        {
+            let mut pool = Pool {
+                start_timestamp: 15,
+                last_timestamp: 15,
+                ..Default::default()
+            };
+            let tick = Tick::default();
+
+            let fef_tick = RefCell::new(tick);
+            let mut refmut_tick = fef_tick.borrow_mut();
+            assert!(cross_tick(&mut *refmut_tick, &mut pool, 10).is_err());
+        }
         Ok(())
     }
+
+    #[test]
+    fn test_cross_tick_rewards() {
+        let reward = PoolReward {
+            emission_per_second: FixedPoint::from_integer(2),
+            emission_end: u64::MAX,
+            growth_global: FeeGrowth::new(50),
+            ..Default::default()
+        };
+        let mut pool = Pool {
+            liquidity: Liquidity::from_integer(4),
+            current_tick_index: 7,
+            rewards: [reward, PoolReward::default(), PoolReward::default()],
+            rewards_last_timestamp: 10,
+            last_timestamp: 10,
+            ..Default::default()
+        };
+        let tick = Tick {
+            index: 3,
+            reward_growth_outside: [FeeGrowth::new(20), FeeGrowth::new(0), FeeGrowth::new(0)],
+            ..Default::default()
+        };
+
+        let ref_tick = RefCell::new(tick);
+        let mut refmut_tick = ref_tick.borrow_mut();
+        cross_tick(&mut *refmut_tick, &mut pool, 20).unwrap();
+
+        // growth outside is flipped against the settled global growth
+        assert_eq!(
+            { refmut_tick.reward_growth_outside },
+            [FeeGrowth::new(30), FeeGrowth::new(0), FeeGrowth::new(0)]
+        );
+        // emission isn't settled by crossing
+        assert_eq!({ pool.rewards[0].growth_global }, FeeGrowth::new(50));
+        assert_eq!({ pool.rewards_last_timestamp }, 10);
+    }
 }
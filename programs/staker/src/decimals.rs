@@ -39,6 +39,15 @@ pub struct TokenAmount {
     pub v: u64,
 }
 
+#[decimal(6)]
+#[zero_copy]
+#[derive(
+    Default, std::fmt::Debug, PartialEq, Eq, PartialOrd, Ord, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct Multiplier {
+    pub v: u128,
+}
+
 #[decimal(0)]
 #[zero_copy]
 #[derive(
@@ -48,4 +48,34 @@ pub enum ErrorCode {
     InvalidMint = 21, // 1785
     #[msg("Incentive pool is different from stake pool")]
     DifferentIncentivePool = 22, // 1786
+    #[msg("Incentive already started")]
+    AlreadyStarted = 23, // 1787
+    #[msg("Too many reward tokens in incentive")]
+    TooManyRewards = 24, // 1788
+    #[msg("Invalid reward token accounts passed as remaining accounts")]
+    InvalidRewardAccounts = 25, // 1789
+    #[msg("Extension would lower the reward rate for stakers")]
+    RewardRateDecrease = 26, // 178a
+    #[msg("New end time has to be later than the current one")]
+    InvalidEndTime = 27, // 178b
+    #[msg("Incentive tick band is invalid")]
+    InvalidTickBand = 28, // 178c
+    #[msg("Position range is outside of incentive tick band")]
+    PositionOutOfBand = 29, // 178d
+    #[msg("Boost tiers have to be sorted and multipliers can't be lower than one")]
+    InvalidBoostTiers = 30, // 178e
+    #[msg("Position has to stay locked in invariant for the whole stake lock")]
+    PositionNotLocked = 31, // 178f
+    #[msg("Stake is still locked")]
+    StakeLocked = 32, // 1790
+    #[msg("Provided payer is different than the one who paid for the stake")]
+    InvalidPayer = 33, // 1791
+    #[msg("Provided position is different than the staked one")]
+    InvalidPosition = 34, // 1792
+    #[msg("Stake is still backed by its position")]
+    StakeStillBacked = 35, // 1793
+    #[msg("Account can't be migrated")]
+    InvalidMigration = 36, // 1794
+    #[msg("Reward amount overflowed")]
+    RewardOverflow = 37, // 1795
 }
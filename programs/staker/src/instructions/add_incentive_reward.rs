@@ -0,0 +1,75 @@
+use crate::decimals::*;
+use crate::structs::*;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::Mint;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(nonce: u8)]
+pub struct AddIncentiveReward<'info> {
+    #[account(mut,
+        constraint = incentive.load()?.founder == founder.key() @ InvalidFounder
+    )]
+    pub incentive: AccountLoader<'info, Incentive>,
+    #[account(init,
+        token::mint = incentive_token,
+        token::authority = staker_authority,
+        payer = founder,
+    )]
+    pub incentive_token_account: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = founder_token_account.key() != incentive_token_account.key() @ InvalidTokenAccount,
+        constraint = founder_token_account.mint == incentive_token.key() @ InvalidMint,
+        constraint = founder_token_account.owner == founder.key() @ InvalidOwner
+    )]
+    pub founder_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub founder: Signer<'info>,
+    #[account(seeds = [b"staker".as_ref()], bump = nonce)]
+    pub staker_authority: AccountInfo<'info>,
+    pub incentive_token: Account<'info, Mint>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> AddIncentiveReward<'info> {
+    fn deposit(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.founder_token_account.to_account_info(),
+                to: self.incentive_token_account.to_account_info(),
+                authority: self.founder.to_account_info().clone(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<AddIncentiveReward>, _nonce: u8, reward: TokenAmount) -> ProgramResult {
+    msg!("ADD INCENTIVE REWARD");
+    require!(reward != TokenAmount::new(0), ZeroAmount);
+
+    {
+        let mut incentive = ctx.accounts.incentive.load_mut()?;
+        // stakers already accruing must see every reward token from the start
+        require!(Seconds::now() < { incentive.start_time }, AlreadyStarted);
+
+        let count = incentive.extra_rewards_count as usize;
+        require!(count < MAX_EXTRA_REWARDS, TooManyRewards);
+
+        incentive.extra_rewards[count] = IncentiveReward {
+            token_account: ctx.accounts.incentive_token_account.key(),
+            total_reward_unclaimed: reward,
+        };
+        incentive.extra_rewards_count += 1;
+    }
+
+    token::transfer(ctx.accounts.deposit(), reward.get())?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::decimals::*;
+use crate::instructions::create_incentive::WEEK;
+use crate::math::calculate_earned_reward;
+use crate::structs::*;
+use crate::util::STAKER_SEED;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(nonce: u8)]
+pub struct CancelIncentive<'info> {
+    #[account(mut,
+        constraint = incentive.load()?.founder == founder.key() @ InvalidFounder
+    )]
+    pub incentive: AccountLoader<'info, Incentive>,
+    #[account(mut,
+        constraint = incentive_token_account.owner == staker_authority.key() @ InvalidTokenAccount,
+        constraint = incentive.load()?.token_account == incentive_token_account.key() @ InvalidTokenAccount
+    )]
+    pub incentive_token_account: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = founder_token_account.mint == incentive_token_account.mint @ InvalidMint
+    )]
+    pub founder_token_account: Account<'info, TokenAccount>,
+    #[account(seeds = [b"staker".as_ref()], bump = nonce)]
+    pub staker_authority: AccountInfo<'info>,
+    pub founder: Signer<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> CancelIncentive<'info> {
+    fn return_to_founder(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from,
+                to,
+                authority: self.staker_authority.to_account_info().clone(),
+            },
+        )
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelIncentive<'info>>,
+    nonce: u8,
+) -> ProgramResult {
+    msg!("CANCEL INCENTIVE");
+    let mut incentive = ctx.accounts.incentive.load_mut()?;
+    let now = Seconds::now();
+    require!(now < { incentive.end_time }, Ended);
+
+    // extra reward tokens are passed as (incentive token account, founder token account) pairs
+    let extra_rewards_count = incentive.extra_rewards_count as usize;
+    require!(
+        ctx.remaining_accounts.len() == extra_rewards_count * 2,
+        InvalidRewardAccounts
+    );
+
+    let seeds = &[STAKER_SEED.as_bytes(), &[nonce]];
+    let signer = &[&seeds[..]];
+
+    // stakers keep what they earned so far, the rest goes back to the founder
+    let earned = calculate_earned_reward(
+        incentive.total_reward_unclaimed,
+        incentive.total_seconds_claimed,
+        incentive.start_time,
+        incentive.end_time,
+        now,
+    );
+    let refund = incentive.total_reward_unclaimed - earned;
+    incentive.total_reward_unclaimed = earned;
+
+    if !refund.is_zero() {
+        let cpi_ctx = ctx
+            .accounts
+            .return_to_founder(
+                ctx.accounts.incentive_token_account.to_account_info(),
+                ctx.accounts.founder_token_account.to_account_info(),
+            )
+            .with_signer(signer);
+        token::transfer(cpi_ctx, refund.get())?;
+    }
+
+    for (i, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+        let extra_reward = incentive.extra_rewards[i];
+        let incentive_token_account = Account::<TokenAccount>::try_from(&pair[0])?;
+        let founder_token_account = Account::<TokenAccount>::try_from(&pair[1])?;
+
+        require!(
+            incentive_token_account.key() == extra_reward.token_account,
+            InvalidTokenAccount
+        );
+        require!(
+            founder_token_account.key() != incentive_token_account.key(),
+            InvalidTokenAccount
+        );
+        require!(
+            founder_token_account.owner == ctx.accounts.founder.key(),
+            InvalidOwner
+        );
+        require!(
+            founder_token_account.mint == incentive_token_account.mint,
+            InvalidMint
+        );
+
+        let earned = calculate_earned_reward(
+            extra_reward.total_reward_unclaimed,
+            incentive.total_seconds_claimed,
+            incentive.start_time,
+            incentive.end_time,
+            now,
+        );
+        let refund = extra_reward.total_reward_unclaimed - earned;
+        incentive.extra_rewards[i].total_reward_unclaimed = earned;
+
+        if !refund.is_zero() {
+            let cpi_ctx = ctx
+                .accounts
+                .return_to_founder(pair[0].clone(), pair[1].clone())
+                .with_signer(signer);
+            token::transfer(cpi_ctx, refund.get())?;
+        }
+    }
+
+    // an incentive cancelled before its start never accepts stakes
+    incentive.end_time = if now > { incentive.start_time } {
+        now
+    } else {
+        incentive.start_time
+    };
+    incentive.end_claim_time = incentive.end_time + Seconds::new(WEEK);
+
+    Ok(())
+}
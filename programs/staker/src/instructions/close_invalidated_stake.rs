@@ -0,0 +1,52 @@
+use crate::decimals::Seconds;
+use crate::structs::{Incentive, UserStake};
+use crate::util::STAKER_SEED;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use invariant::structs::Position;
+
+#[derive(Accounts)]
+pub struct CloseInvalidatedStake<'info> {
+    #[account(mut, constraint = user_stake.load()?.incentive == incentive.key() @ InvalidIncentive)]
+    pub incentive: AccountLoader<'info, Incentive>,
+    #[account(mut, close = payer)]
+    pub user_stake: AccountLoader<'info, UserStake>,
+    // may be closed or hold another position after the staked one was removed
+    #[account(constraint = position.key() == user_stake.load()?.position @ InvalidPosition)]
+    pub position: AccountInfo<'info>,
+    #[account(mut, constraint = payer.key() == user_stake.load()?.payer @ InvalidPayer)]
+    pub payer: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<CloseInvalidatedStake>) -> ProgramResult {
+    msg!("CLOSE INVALIDATED STAKE");
+
+    let mut incentive = ctx.accounts.incentive.load_mut()?;
+    let user_stake = ctx.accounts.user_stake.load()?;
+
+    let is_backed = match AccountLoader::<Position>::try_from(&ctx.accounts.position) {
+        Ok(loader) => {
+            let position = loader.load()?;
+            // stake address is derived from the id of the position it was created for
+            let stake_address = Pubkey::create_program_address(
+                &[
+                    STAKER_SEED.as_bytes(),
+                    ctx.accounts.incentive.key().as_ref(),
+                    position.pool.as_ref(),
+                    &{ position.id }.to_le_bytes(),
+                    &[user_stake.bump],
+                ],
+                ctx.program_id,
+            );
+            stake_address == Ok(ctx.accounts.user_stake.key()) && user_stake.is_backed_by(&position)
+        }
+        Err(_) => false,
+    };
+    require!(!is_backed, StakeStillBacked);
+    require!(incentive.num_of_stakes > 0, NoStakes);
+
+    incentive.num_of_stakes -= 1;
+    incentive.remove_stake_liquidity(user_stake.liquidity, user_stake.boost, Seconds::now());
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::decimals::*;
 use crate::structs::{Incentive, UserStake};
 use crate::ErrorCode::*;
 use anchor_lang::prelude::*;
@@ -28,9 +29,12 @@ pub struct CloseStakeByOwner<'info> {
 pub fn handler(ctx: Context<CloseStakeByOwner>, _index: i32) -> ProgramResult {
     let mut incentive = ctx.accounts.incentive.load_mut()?;
     require!(incentive.num_of_stakes > 0, NoStakes);
+    let user_stake = ctx.accounts.user_stake.load()?;
+    require!(Seconds::now() >= { user_stake.lock_until }, StakeLocked);
 
     // decrease number of stakes by 1
     incentive.num_of_stakes -= 1;
+    incentive.remove_stake_liquidity(user_stake.liquidity, user_stake.boost, Seconds::now());
 
     Ok(())
 }
@@ -6,17 +6,26 @@ use anchor_lang::solana_program::system_program;
 use anchor_spl::token::Mint;
 use anchor_spl::token::{self, TokenAccount, Transfer};
 use invariant::program::Invariant;
-use invariant::structs::Pool;
+use invariant::structs::{Pool, MAX_TICK};
 
 const MAX_TIME_BEFORE_START: u64 = 3_600; //hour in sec
-const MAX_DURATION: u64 = 31_556_926; //year in sec
-const WEEK: u64 = 604_800; //week in sec
+pub const MAX_DURATION: u64 = 31_556_926; //year in sec
+pub const WEEK: u64 = 604_800; //week in sec
 
 #[derive(Accounts)]
 #[instruction(nonce: u8)]
 pub struct CreateIncentive<'info> {
-    #[account(init, payer = founder)]
+    #[account(init,
+        seeds = [b"incentivev1", pool.key().as_ref(), founder.key().as_ref(), &incentive_list.load()?.head.to_le_bytes()],
+        bump,
+        payer = founder
+    )]
     pub incentive: AccountLoader<'info, Incentive>,
+    #[account(mut,
+        seeds = [b"incentivelistv1", pool.key().as_ref(), founder.key().as_ref()],
+        bump = incentive_list.load()?.bump
+    )]
+    pub incentive_list: AccountLoader<'info, IncentiveList>,
     #[account(init,
         token::mint = incentive_token,
         token::authority = staker_authority,
@@ -67,10 +76,20 @@ pub fn handler(
     reward: TokenAmount,
     start_time: Seconds,
     end_time: Seconds,
+    min_tick: Option<i32>,
+    max_tick: Option<i32>,
 ) -> ProgramResult {
     msg!("CREATE INCENTIVE");
     require!((reward) != TokenAmount::new(0), ZeroAmount);
 
+    // without a band every position in the pool is eligible
+    let min_tick = min_tick.unwrap_or(-MAX_TICK);
+    let max_tick = max_tick.unwrap_or(MAX_TICK);
+    require!(
+        -MAX_TICK <= min_tick && min_tick < max_tick && max_tick <= MAX_TICK,
+        InvalidTickBand
+    );
+
     require!(
         (start_time + Seconds::new(MAX_TIME_BEFORE_START)) >= Seconds::now(),
         StartInPast
@@ -80,19 +99,33 @@ pub fn handler(
         TooLongDuration
     );
     let incentive = &mut ctx.accounts.incentive.load_init()?;
+    let mut incentive_list = ctx.accounts.incentive_list.load_mut()?;
 
     **incentive = Incentive {
         founder: ctx.accounts.founder.key(),
         pool: ctx.accounts.pool.key(),
         token_account: ctx.accounts.incentive_token_account.key(),
         total_reward_unclaimed: reward,
+        extra_rewards: Default::default(),
+        extra_rewards_count: 0,
         total_seconds_claimed: Seconds::new(0),
         num_of_stakes: 0,
         start_time,
         end_time,
         end_claim_time: end_time + Seconds::new(WEEK),
+        min_tick,
+        max_tick,
+        boost_tiers: Default::default(),
+        boost_tiers_count: 0,
+        total_liquidity: Liquidity::new(0),
+        total_boosted_liquidity: Liquidity::new(0),
+        boosted_seconds_per_liquidity: SecondsPerLiquidity::new(0),
+        last_boost_update: Seconds::now(),
+        index: incentive_list.head,
         nonce,
+        bump: *ctx.bumps.get("incentive").unwrap(),
     };
+    incentive_list.head += 1;
 
     //send tokens to incentive
     let cpi_ctx = ctx.accounts.deposit();
@@ -0,0 +1,32 @@
+use crate::structs::IncentiveList;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use invariant::structs::Pool;
+
+#[derive(Accounts)]
+pub struct CreateIncentiveList<'info> {
+    #[account(init,
+        seeds = [b"incentivelistv1", pool.key().as_ref(), founder.key().as_ref()],
+        bump,
+        payer = payer
+    )]
+    pub incentive_list: AccountLoader<'info, IncentiveList>,
+    pub pool: AccountLoader<'info, Pool>,
+    pub founder: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<CreateIncentiveList>) -> ProgramResult {
+    msg!("CREATE INCENTIVE LIST");
+    let mut incentive_list = ctx.accounts.incentive_list.load_init()?;
+    *incentive_list = IncentiveList {
+        head: 0,
+        bump: *ctx.bumps.get("incentive_list").unwrap(),
+    };
+
+    Ok(())
+}
@@ -41,9 +41,27 @@ impl<'info> ReturnFounds<'info> {
             },
         )
     }
+
+    fn return_extra_to_founder(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: from.clone(),
+                to: to.clone(),
+                authority: self.staker_authority.to_account_info().clone(),
+            },
+        )
+    }
 }
 
-pub fn handler(ctx: Context<ReturnFounds>, nonce: u8) -> ProgramResult {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReturnFounds<'info>>,
+    nonce: u8,
+) -> ProgramResult {
     {
         let incentive = ctx.accounts.incentive.load()?;
         require!(Seconds::now() > { incentive.end_time }, TooEarly);
@@ -55,6 +73,27 @@ pub fn handler(ctx: Context<ReturnFounds>, nonce: u8) -> ProgramResult {
         let cpi_ctx = ctx.accounts.return_to_founder().with_signer(signer);
 
         token::transfer(cpi_ctx, remaining_reward.get())?;
+
+        // extra reward tokens are passed as (incentive token account, founder token account) pairs
+        require!(
+            ctx.remaining_accounts.len() == incentive.extra_rewards_count as usize * 2,
+            InvalidRewardAccounts
+        );
+        for (i, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+            let extra_reward = incentive.extra_rewards[i];
+            require!(
+                *pair[0].key == extra_reward.token_account,
+                InvalidTokenAccount
+            );
+
+            if !extra_reward.total_reward_unclaimed.is_zero() {
+                let cpi_ctx = ctx
+                    .accounts
+                    .return_extra_to_founder(&pair[0], &pair[1])
+                    .with_signer(signer);
+                token::transfer(cpi_ctx, extra_reward.total_reward_unclaimed.get())?;
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,307 @@
+use crate::decimals::*;
+use crate::instructions::create_incentive::{MAX_DURATION, WEEK};
+use crate::math::is_reward_rate_kept;
+use crate::structs::*;
+use crate::ErrorCode::*;
+use crate::Result;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct FundIncentive<'info> {
+    #[account(mut)]
+    pub incentive: AccountLoader<'info, Incentive>,
+    #[account(mut)]
+    pub incentive_token_account: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = funder_token_account.key() != incentive_token_account.key() @ InvalidTokenAccount,
+        constraint = funder_token_account.mint == incentive_token_account.mint @ InvalidMint,
+        constraint = funder_token_account.owner == funder.key() @ InvalidOwner
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    pub funder: Signer<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> FundIncentive<'info> {
+    fn deposit(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from,
+                to,
+                authority: self.funder.to_account_info().clone(),
+            },
+        )
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FundIncentive<'info>>,
+    amounts: Vec<TokenAmount>,
+    end_time: Option<Seconds>,
+) -> ProgramResult {
+    msg!("FUND INCENTIVE");
+    let funder = ctx.accounts.funder.key();
+
+    // extra reward tokens are passed as (incentive token account, funder token account) pairs
+    let extra_rewards_count = ctx.accounts.incentive.load()?.extra_rewards_count as usize;
+    require!(
+        ctx.remaining_accounts.len() == extra_rewards_count * 2,
+        InvalidRewardAccounts
+    );
+    let mut token_accounts = vec![ctx.accounts.incentive_token_account.key()];
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let incentive_token_account = Account::<TokenAccount>::try_from(&pair[0])?;
+        let funder_token_account = Account::<TokenAccount>::try_from(&pair[1])?;
+        require!(
+            funder_token_account.key() != incentive_token_account.key(),
+            InvalidTokenAccount
+        );
+        require!(
+            funder_token_account.mint == incentive_token_account.mint,
+            InvalidMint
+        );
+        require!(funder_token_account.owner == funder, InvalidOwner);
+        token_accounts.push(incentive_token_account.key());
+    }
+
+    {
+        let mut incentive = ctx.accounts.incentive.load_mut()?;
+        fund(
+            &mut incentive,
+            &token_accounts,
+            funder,
+            &amounts,
+            end_time,
+            Seconds::now(),
+        )?;
+    }
+
+    if !amounts[0].is_zero() {
+        let cpi_ctx = ctx.accounts.deposit(
+            ctx.accounts.funder_token_account.to_account_info(),
+            ctx.accounts.incentive_token_account.to_account_info(),
+        );
+        token::transfer(cpi_ctx, amounts[0].get())?;
+    }
+    for (pair, amount) in ctx.remaining_accounts.chunks(2).zip(amounts[1..].iter()) {
+        if !amount.is_zero() {
+            let cpi_ctx = ctx.accounts.deposit(pair[1].clone(), pair[0].clone());
+            token::transfer(cpi_ctx, amount.get())?;
+        }
+    }
+
+    Ok(())
+}
+
+// amounts and token accounts are given for every reward token, the primary one first
+fn fund(
+    incentive: &mut Incentive,
+    token_accounts: &[Pubkey],
+    funder: Pubkey,
+    amounts: &[TokenAmount],
+    end_time: Option<Seconds>,
+    now: Seconds,
+) -> Result<()> {
+    require!(now < { incentive.end_time }, Ended);
+
+    let extra_rewards_count = incentive.extra_rewards_count as usize;
+    require!(
+        token_accounts.len() == extra_rewards_count + 1 && amounts.len() == extra_rewards_count + 1,
+        InvalidRewardAccounts
+    );
+    let extra_rewards = incentive.extra_rewards;
+    let rewards = std::iter::once((incentive.token_account, incentive.total_reward_unclaimed))
+        .chain(
+            extra_rewards[..extra_rewards_count]
+                .iter()
+                .map(|reward| (reward.token_account, reward.total_reward_unclaimed)),
+        );
+
+    let mut rewards_before = Vec::with_capacity(amounts.len());
+    let mut rewards_after = Vec::with_capacity(amounts.len());
+    for ((token_account, reward_before), (passed_account, amount)) in
+        rewards.zip(token_accounts.iter().zip(amounts.iter()))
+    {
+        require!(*passed_account == token_account, InvalidTokenAccount);
+        rewards_before.push(reward_before);
+        rewards_after.push(
+            reward_before
+                .checked_add(*amount)
+                .map_err(|_| RewardOverflow)?,
+        );
+    }
+
+    if let Some(end_time) = end_time {
+        require!(funder == incentive.founder, InvalidFounder);
+        require!(end_time > { incentive.end_time }, InvalidEndTime);
+        require!(
+            (now + Seconds::new(MAX_DURATION)) >= end_time,
+            TooLongDuration
+        );
+
+        // every reward token is paid over the longer period, so each has to keep its rate
+        let seconds_unclaimed =
+            incentive.end_time - incentive.start_time - incentive.total_seconds_claimed;
+        let new_seconds_unclaimed =
+            end_time - incentive.start_time - incentive.total_seconds_claimed;
+        let is_every_rate_kept =
+            rewards_before
+                .iter()
+                .zip(rewards_after.iter())
+                .all(|(before, after)| {
+                    is_reward_rate_kept(*before, seconds_unclaimed, *after, new_seconds_unclaimed)
+                });
+        require!(is_every_rate_kept, RewardRateDecrease);
+
+        incentive.end_time = end_time;
+        incentive.end_claim_time = end_time + Seconds::new(WEEK);
+    } else {
+        require!(amounts.iter().any(|amount| !amount.is_zero()), ZeroAmount);
+    }
+
+    incentive.total_reward_unclaimed = rewards_after[0];
+    for (i, reward_after) in rewards_after[1..].iter().enumerate() {
+        incentive.extra_rewards[i].total_reward_unclaimed = *reward_after;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fund_extends_incentive_with_extra_reward() {
+        let founder = Pubkey::new_unique();
+        let primary_account = Pubkey::new_unique();
+        let extra_account = Pubkey::new_unique();
+        let token_accounts = [primary_account, extra_account];
+        let mut incentive = Incentive {
+            founder,
+            token_account: primary_account,
+            total_reward_unclaimed: TokenAmount::new(1000),
+            extra_rewards_count: 1,
+            start_time: Seconds::new(0),
+            end_time: Seconds::new(100),
+            end_claim_time: Seconds::new(100 + WEEK),
+            ..Default::default()
+        };
+        incentive.extra_rewards[0] = IncentiveReward {
+            token_account: extra_account,
+            total_reward_unclaimed: TokenAmount::new(500),
+        };
+        let now = Seconds::new(10);
+
+        // extension would dilute the extra reward that isn't funded
+        {
+            let result = fund(
+                &mut incentive,
+                &token_accounts,
+                founder,
+                &[TokenAmount::new(1000), TokenAmount::new(0)],
+                Some(Seconds::new(200)),
+                now,
+            );
+            assert!(result.is_err());
+            assert_eq!({ incentive.end_time }, Seconds::new(100));
+            assert_eq!({ incentive.total_reward_unclaimed }, TokenAmount::new(1000));
+        }
+        // and the other way around
+        {
+            let result = fund(
+                &mut incentive,
+                &token_accounts,
+                founder,
+                &[TokenAmount::new(0), TokenAmount::new(500)],
+                Some(Seconds::new(200)),
+                now,
+            );
+            assert!(result.is_err());
+            assert_eq!({ incentive.end_time }, Seconds::new(100));
+        }
+
+        // both tokens still hold rewards, so both are funded in the same call
+        fund(
+            &mut incentive,
+            &token_accounts,
+            founder,
+            &[TokenAmount::new(1000), TokenAmount::new(500)],
+            Some(Seconds::new(200)),
+            now,
+        )
+        .unwrap();
+        assert_eq!({ incentive.end_time }, Seconds::new(200));
+        assert_eq!({ incentive.end_claim_time }, Seconds::new(200 + WEEK));
+        assert_eq!({ incentive.total_reward_unclaimed }, TokenAmount::new(2000));
+        assert_eq!(
+            { incentive.extra_rewards[0].total_reward_unclaimed },
+            TokenAmount::new(1000)
+        );
+
+        // the extra reward can be topped up alone, by anyone
+        fund(
+            &mut incentive,
+            &token_accounts,
+            Pubkey::new_unique(),
+            &[TokenAmount::new(0), TokenAmount::new(500)],
+            None,
+            now,
+        )
+        .unwrap();
+        assert_eq!(
+            { incentive.extra_rewards[0].total_reward_unclaimed },
+            TokenAmount::new(1500)
+        );
+    }
+
+    #[test]
+    fn test_fund_checks() {
+        let founder = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let incentive = Incentive {
+            founder,
+            token_account,
+            total_reward_unclaimed: TokenAmount::new(1000),
+            end_time: Seconds::new(100),
+            ..Default::default()
+        };
+        let now = Seconds::new(10);
+        let fund_with = |token, funder, amounts: &[u64], end_time, now| {
+            let mut incentive = incentive;
+            let amounts: Vec<TokenAmount> = amounts.iter().map(|a| TokenAmount::new(*a)).collect();
+            fund(&mut incentive, &[token], funder, &amounts, end_time, now)
+        };
+
+        // ended
+        assert!(fund_with(token_account, founder, &[10], None, Seconds::new(100)).is_err());
+        // amount for a missing extra reward
+        assert!(fund_with(token_account, founder, &[10, 10], None, now).is_err());
+        // wrong token account
+        assert!(fund_with(Pubkey::new_unique(), founder, &[10], None, now).is_err());
+        // zero amount without extension
+        assert!(fund_with(token_account, founder, &[0], None, now).is_err());
+        // overflow
+        assert!(fund_with(token_account, founder, &[u64::MAX], None, now).is_err());
+        // only the founder extends
+        let end_time = Some(Seconds::new(200));
+        assert!(fund_with(token_account, Pubkey::new_unique(), &[1000], end_time, now).is_err());
+        assert!(fund_with(token_account, founder, &[1000], end_time, now).is_ok());
+        // end time has to move forward
+        assert!(fund_with(
+            token_account,
+            founder,
+            &[1000],
+            Some(Seconds::new(100)),
+            now
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::structs::{Incentive, LegacyIncentive, LEGACY_INCENTIVE_SIZE};
+use crate::util::migrate_account;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct MigrateIncentive<'info> {
+    #[account(mut,
+        constraint = incentive.owner == program_id @ InvalidMigration
+    )]
+    pub incentive: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<MigrateIncentive>) -> ProgramResult {
+    msg!("MIGRATE INCENTIVE");
+
+    let legacy = migrate_account::<LegacyIncentive>(
+        &ctx.accounts.incentive,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        Incentive::discriminator(),
+        LEGACY_INCENTIVE_SIZE,
+        8 + size_of::<Incentive>(),
+    )?;
+
+    let loader = AccountLoader::<'_, Incentive>::try_from(&ctx.accounts.incentive)?;
+    *loader.load_mut()? = Incentive::from_legacy(legacy);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::decimals::Seconds;
+use crate::structs::{Incentive, LegacyUserStake, UserStake, LEGACY_USER_STAKE_SIZE};
+use crate::util::migrate_account;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use invariant::structs::Position;
+use std::mem::size_of;
+
+// incentive and the position in invariant have to be migrated first
+#[derive(Accounts)]
+pub struct MigrateUserStake<'info> {
+    #[account(mut,
+        constraint = user_stake.owner == program_id @ InvalidMigration
+    )]
+    pub user_stake: AccountInfo<'info>,
+    #[account(mut)]
+    pub incentive: AccountLoader<'info, Incentive>,
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<MigrateUserStake>) -> ProgramResult {
+    msg!("MIGRATE USER STAKE");
+
+    let legacy = migrate_account::<LegacyUserStake>(
+        &ctx.accounts.user_stake,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        UserStake::discriminator(),
+        LEGACY_USER_STAKE_SIZE,
+        8 + size_of::<UserStake>(),
+    )?;
+    require!(
+        legacy.incentive == ctx.accounts.incentive.key()
+            && legacy.position == ctx.accounts.position.key(),
+        InvalidMigration
+    );
+
+    let position = ctx.accounts.position.load()?;
+    let mut user_stake = UserStake::from_legacy(legacy, &position);
+
+    // legacy incentives didn't track staked liquidity, boosts are averaged from the migration on
+    let current_time = Seconds::now();
+    let mut incentive = ctx.accounts.incentive.load_mut()?;
+    incentive.add_stake_liquidity(user_stake.liquidity, user_stake.boost, current_time);
+    user_stake.boosted_seconds_per_liquidity_initial = incentive.boosted_seconds_per_liquidity;
+    user_stake.last_withdraw = current_time;
+
+    let loader = AccountLoader::<'_, UserStake>::try_from(&ctx.accounts.user_stake)?;
+    *loader.load_mut()? = user_stake;
+
+    Ok(())
+}
@@ -1,13 +1,29 @@
+pub mod add_incentive_reward;
+pub mod cancel_incentive;
+pub mod close_invalidated_stake;
 pub mod close_stake_by_owner;
 pub mod create_incentive;
+pub mod create_incentive_list;
 pub mod end_incentive;
+pub mod fund_incentive;
+pub mod migrate_incentive;
+pub mod migrate_user_stake;
 pub mod remove_stake;
+pub mod set_boost_tiers;
 pub mod stake;
 pub mod withdraw;
 
+pub use add_incentive_reward::*;
+pub use cancel_incentive::*;
+pub use close_invalidated_stake::*;
 pub use close_stake_by_owner::*;
 pub use create_incentive::*;
+pub use create_incentive_list::*;
 pub use end_incentive::*;
+pub use fund_incentive::*;
+pub use migrate_incentive::*;
+pub use migrate_user_stake::*;
 pub use remove_stake::*;
+pub use set_boost_tiers::*;
 pub use stake::*;
 pub use withdraw::*;
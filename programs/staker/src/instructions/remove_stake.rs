@@ -22,6 +22,8 @@ pub fn handler(ctx: Context<RemoveStake>) -> ProgramResult {
 
     // decrease number of stakes by 1
     incentive.num_of_stakes -= 1;
+    let user_stake = ctx.accounts.user_stake.load()?;
+    incentive.remove_stake_liquidity(user_stake.liquidity, user_stake.boost, Seconds::now());
 
     Ok(())
 }
@@ -0,0 +1,40 @@
+use crate::decimals::*;
+use crate::structs::*;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetBoostTiers<'info> {
+    #[account(mut,
+        constraint = incentive.load()?.founder == founder.key() @ InvalidFounder
+    )]
+    pub incentive: AccountLoader<'info, Incentive>,
+    pub founder: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetBoostTiers>, boost_tiers: Vec<BoostTier>) -> ProgramResult {
+    msg!("SET BOOST TIERS");
+    let mut incentive = ctx.accounts.incentive.load_mut()?;
+    // stakers have to know the boost curve before they lock
+    require!(Seconds::now() < { incentive.start_time }, AlreadyStarted);
+    require!(boost_tiers.len() <= MAX_BOOST_TIERS, InvalidBoostTiers);
+
+    let mut previous = BoostTier {
+        lock_duration: Seconds::new(0),
+        multiplier: Multiplier::from_integer(1),
+    };
+    for tier in boost_tiers.iter() {
+        require!(
+            tier.lock_duration > previous.lock_duration && tier.multiplier >= previous.multiplier,
+            InvalidBoostTiers
+        );
+        previous = *tier;
+    }
+
+    let mut tiers = [BoostTier::default(); MAX_BOOST_TIERS];
+    tiers[..boost_tiers.len()].copy_from_slice(&boost_tiers);
+    incentive.boost_tiers = tiers;
+    incentive.boost_tiers_count = boost_tiers.len() as u8;
+
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use crate::decimals::*;
 use crate::structs::*;
-use crate::util::get_current_slot;
 use crate::ErrorCode::*;
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
+use anchor_spl::token::Mint;
+use invariant::cpi::accounts::UpdateSecondsPerLiquidity;
 use invariant::program::Invariant;
-use invariant::structs::Position;
+use invariant::structs::{Pool, Position, Tick};
 
 #[derive(Accounts)]
 #[instruction(index: u32)]
@@ -16,7 +17,7 @@ pub struct CreateUserStake<'info> {
         payer = signer,
         bump)]
     pub user_stake: AccountLoader<'info, UserStake>,
-    #[account(
+    #[account(mut,
         seeds = [b"positionv1",
         owner.key.as_ref(),
         &index.to_le_bytes(),],
@@ -28,6 +29,14 @@ pub struct CreateUserStake<'info> {
         constraint = incentive.load()?.pool == position.load()?.pool @ DifferentIncentivePool
     )]
     pub incentive: AccountLoader<'info, Incentive>,
+    #[account(mut,
+        constraint = pool.key() == position.load()?.pool @ DifferentIncentivePool
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    pub lower_tick: AccountLoader<'info, Tick>,
+    pub upper_tick: AccountLoader<'info, Tick>,
+    pub token_x: Account<'info, Mint>,
+    pub token_y: Account<'info, Mint>,
     pub owner: AccountInfo<'info>,
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -38,22 +47,85 @@ pub struct CreateUserStake<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<CreateUserStake>) -> ProgramResult {
+impl<'info> CreateUserStake<'info> {
+    fn update_seconds_per_liquidity(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateSecondsPerLiquidity<'info>> {
+        CpiContext::new(
+            self.invariant.to_account_info(),
+            UpdateSecondsPerLiquidity {
+                pool: self.pool.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                position: self.position.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                owner: self.owner.to_account_info(),
+                signer: self.signer.to_account_info(),
+                rent: self.rent.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        )
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateUserStake>,
+    index: i32,
+    lock_duration: Option<Seconds>,
+) -> ProgramResult {
     msg!("STAKE");
+    let (lower_tick_index, upper_tick_index) = {
+        let position = ctx.accounts.position.load()?;
+        (position.lower_tick_index, position.upper_tick_index)
+    };
+    // refresh seconds per liquidity inside the position before taking the snapshot
+    invariant::cpi::update_seconds_per_liquidity(
+        ctx.accounts.update_seconds_per_liquidity(),
+        lower_tick_index,
+        upper_tick_index,
+        index,
+    )?;
+
     let mut incentive = ctx.accounts.incentive.load_mut()?;
-    require!(Seconds::now() >= { incentive.start_time }, NotStarted);
-    require!(Seconds::now() < { incentive.end_time }, Ended);
+    let current_time = Seconds::now();
+    require!(current_time >= { incentive.start_time }, NotStarted);
+    require!(current_time < { incentive.end_time }, Ended);
     require!(incentive.num_of_stakes < u64::MAX, NoStakes);
 
     let user_stake = &mut ctx.accounts.user_stake.load_init()?;
     let position = ctx.accounts.position.load()?;
-    let update_slot = position.last_slot;
-    let slot = get_current_slot();
-    require!(slot == update_slot, SlotsAreNotEqual);
+    require!(
+        position.lower_tick_index >= incentive.min_tick
+            && position.upper_tick_index <= incentive.max_tick,
+        PositionOutOfBand
+    );
+
+    // locked stakes are boosted only if the liquidity itself can't leave the position
+    let (lock_until, boost) = match lock_duration {
+        Some(lock_duration) => {
+            let lock_until = current_time + lock_duration;
+            require!(
+                { position.unlock_timestamp } >= lock_until.get(),
+                PositionNotLocked
+            );
+            (lock_until, incentive.get_boost(lock_duration))
+        }
+        None => (Seconds::new(0), Multiplier::from_integer(1)),
+    };
 
     **user_stake = UserStake {
         position: ctx.accounts.position.key(),
+        owner: position.owner,
+        lower_tick_index: position.lower_tick_index,
+        upper_tick_index: position.upper_tick_index,
         liquidity: Liquidity::new({ position.liquidity }.get()),
+        lock_until,
+        boost,
+        range_nonce: position.range_nonce,
+        payer: ctx.accounts.signer.key(),
+        boosted_seconds_per_liquidity_initial: SecondsPerLiquidity::new(0),
+        last_withdraw: current_time,
         incentive: ctx.accounts.incentive.key(),
         bump: *ctx.bumps.get("user_stake").unwrap(),
         seconds_per_liquidity_initial: SecondsPerLiquidity::from_decimal(
@@ -63,5 +135,7 @@ pub fn handler(ctx: Context<CreateUserStake>) -> ProgramResult {
     incentive.num_of_stakes += 1;
     let liquidity = user_stake.liquidity;
     require!(!liquidity.is_zero(), ZeroLiquidity);
+    incentive.add_stake_liquidity(liquidity, boost, current_time);
+    user_stake.boosted_seconds_per_liquidity_initial = incentive.boosted_seconds_per_liquidity;
     Ok(())
 }
@@ -4,8 +4,11 @@ use crate::structs::*;
 use crate::util::*;
 use crate::ErrorCode::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TokenAccount, Transfer};
-use invariant::structs::Position;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use invariant::cpi::accounts::UpdateSecondsPerLiquidity;
+use invariant::program::Invariant;
+use invariant::structs::{Pool, Position, Tick};
 
 #[derive(Accounts)]
 #[instruction(index: u32, nonce: u8)]
@@ -20,10 +23,11 @@ pub struct Withdraw<'info> {
     )]
     pub incentive: AccountLoader<'info, Incentive>,
     #[account(mut,
-        constraint = incentive_token_account.owner == staker_authority.key() @ InvalidTokenAccount
+        constraint = incentive_token_account.owner == staker_authority.key() @ InvalidTokenAccount,
+        constraint = incentive.load()?.token_account == incentive_token_account.key() @ InvalidTokenAccount
     )]
     pub incentive_token_account: Account<'info, TokenAccount>,
-    #[account(
+    #[account(mut,
         seeds = [b"positionv1",
         owner.key.as_ref(),
         &index.to_le_bytes(),],
@@ -36,12 +40,27 @@ pub struct Withdraw<'info> {
         constraint = owner_token_account.owner == position.load()?.owner @ InvalidOwner
     )]
     pub owner_token_account: Account<'info, TokenAccount>,
-    #[account(seeds = [b"staker".as_ref()], bump = nonce)]
+    #[account(mut,
+        constraint = pool.key() == position.load()?.pool @ DifferentIncentivePool
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    pub lower_tick: AccountLoader<'info, Tick>,
+    pub upper_tick: AccountLoader<'info, Tick>,
+    pub token_x: Account<'info, Mint>,
+    pub token_y: Account<'info, Mint>,
+    #[account(mut, seeds = [b"staker".as_ref()], bump = nonce)]
     pub staker_authority: AccountInfo<'info>,
     #[account(mut)]
     pub owner: AccountInfo<'info>,
+    #[account(mut, constraint = payer.key() == user_stake.load()?.payer @ InvalidPayer)]
+    pub payer: AccountInfo<'info>,
     #[account(address = token::ID)]
     pub token_program: AccountInfo<'info>,
+    #[account(address = invariant::ID)]
+    pub invariant: Program<'info, Invariant>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> Withdraw<'info> {
@@ -55,22 +74,105 @@ impl<'info> Withdraw<'info> {
             },
         )
     }
+
+    fn update_seconds_per_liquidity(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateSecondsPerLiquidity<'info>> {
+        CpiContext::new(
+            self.invariant.to_account_info(),
+            UpdateSecondsPerLiquidity {
+                pool: self.pool.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                position: self.position.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                owner: self.owner.to_account_info(),
+                signer: self.staker_authority.to_account_info(),
+                rent: self.rent.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        )
+    }
+
+    fn withdraw_extra(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: from.clone(),
+                to: to.clone(),
+                authority: self.staker_authority.to_account_info().clone(),
+            },
+        )
+    }
 }
 
-pub fn handler(ctx: Context<Withdraw>, _index: i32, nonce: u8) -> ProgramResult {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+    index: i32,
+    nonce: u8,
+) -> ProgramResult {
     msg!("WITHDRAW");
 
+    let seeds = &[STAKER_SEED.as_bytes(), &[nonce]];
+    let signer = &[&seeds[..]];
+
+    let (lower_tick_index, upper_tick_index) = {
+        let position = ctx.accounts.position.load()?;
+        (position.lower_tick_index, position.upper_tick_index)
+    };
+    // refresh seconds per liquidity inside the position, signed by the staker authority
+    invariant::cpi::update_seconds_per_liquidity(
+        ctx.accounts
+            .update_seconds_per_liquidity()
+            .with_signer(signer),
+        lower_tick_index,
+        upper_tick_index,
+        index,
+    )?;
+
     let mut incentive = ctx.accounts.incentive.load_mut()?;
+    let current_time = Seconds::now();
+    incentive.update_boosted_seconds(current_time);
+
+    let (is_backed, staked_liquidity, boost) = {
+        let user_stake = ctx.accounts.user_stake.load()?;
+        let position = ctx.accounts.position.load()?;
+        (
+            user_stake.is_backed_by(&position),
+            user_stake.liquidity,
+            user_stake.boost,
+        )
+    };
+    if !is_backed {
+        msg!("STAKE INVALIDATED");
+        require!(incentive.num_of_stakes > 0, NoStakes);
+        incentive.remove_stake_liquidity(staked_liquidity, boost, current_time);
+        // the new owner didn't pay for the stake
+        close(
+            ctx.accounts.user_stake.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+        )
+        .unwrap();
+
+        incentive.num_of_stakes -= 1;
+        return Ok(());
+    }
+
     {
         let user_stake = &mut ctx.accounts.user_stake.load_mut()?;
         let position = ctx.accounts.position.load()?;
 
-        let update_slot = position.last_slot;
-        let slot = get_current_slot();
-
-        require!(slot == update_slot, SlotsAreNotEqual);
         require!(user_stake.liquidity.v != 0, ZeroSecondsStaked);
 
+        let current_liquidity = Liquidity::new({ position.liquidity }.get());
+        let liquidity =
+            user_stake.normalized_liquidity(current_liquidity, &incentive, current_time);
+
         let seconds_per_liquidity_inside =
             SecondsPerLiquidity::new(position.seconds_per_liquidity_inside.v);
 
@@ -78,24 +180,79 @@ pub fn handler(ctx: Context<Withdraw>, _index: i32, nonce: u8) -> ProgramResult
 
         require!(reward_unclaimed != TokenAmount::new(0), ZeroAmount);
 
+        // extra reward tokens are passed as (incentive token account, owner token account) pairs
+        let extra_rewards_count = incentive.extra_rewards_count as usize;
+        require!(
+            ctx.remaining_accounts.len() == extra_rewards_count * 2,
+            InvalidRewardAccounts
+        );
+
+        let total_seconds_claimed = incentive.total_seconds_claimed;
+
         let (seconds_inside, reward) = calculate_reward(
             reward_unclaimed,
-            incentive.total_seconds_claimed,
+            total_seconds_claimed,
             incentive.start_time,
             incentive.end_time,
-            user_stake.liquidity,
+            liquidity,
             user_stake.seconds_per_liquidity_initial,
             seconds_per_liquidity_inside,
-            Seconds::now(),
+            current_time,
         )
         .unwrap();
 
-        incentive.total_seconds_claimed = incentive.total_seconds_claimed + seconds_inside;
+        // every reward token is shared out by the same seconds inside
+        for (i, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+            let extra_reward = incentive.extra_rewards[i];
+            let incentive_token_account = Account::<TokenAccount>::try_from(&pair[0])?;
+            let owner_token_account = Account::<TokenAccount>::try_from(&pair[1])?;
+
+            require!(
+                incentive_token_account.key() == extra_reward.token_account,
+                InvalidTokenAccount
+            );
+            require!(
+                owner_token_account.key() != incentive_token_account.key(),
+                InvalidTokenAccount
+            );
+            require!(owner_token_account.owner == position.owner, InvalidOwner);
+            require!(
+                owner_token_account.mint == incentive_token_account.mint,
+                InvalidMint
+            );
+
+            let (_, extra_amount) = calculate_reward(
+                extra_reward.total_reward_unclaimed,
+                total_seconds_claimed,
+                incentive.start_time,
+                incentive.end_time,
+                liquidity,
+                user_stake.seconds_per_liquidity_initial,
+                seconds_per_liquidity_inside,
+                current_time,
+            )
+            .unwrap();
+
+            incentive.extra_rewards[i].total_reward_unclaimed =
+                extra_reward.total_reward_unclaimed - extra_amount;
+
+            if !extra_amount.is_zero() {
+                let cpi_ctx = ctx
+                    .accounts
+                    .withdraw_extra(&pair[0], &pair[1])
+                    .with_signer(signer);
+                token::transfer(cpi_ctx, extra_amount.get())?;
+            }
+        }
+
+        incentive.total_seconds_claimed = total_seconds_claimed + seconds_inside;
         incentive.total_reward_unclaimed = reward_unclaimed - reward;
         user_stake.seconds_per_liquidity_initial = seconds_per_liquidity_inside;
-
-        let seeds = &[STAKER_SEED.as_bytes(), &[nonce]];
-        let signer = &[&seeds[..]];
+        user_stake.boosted_seconds_per_liquidity_initial = incentive.boosted_seconds_per_liquidity;
+        user_stake.last_withdraw = current_time;
+        user_stake.liquidity = current_liquidity;
+        incentive.remove_stake_liquidity(staked_liquidity, boost, current_time);
+        incentive.add_stake_liquidity(current_liquidity, boost, current_time);
 
         let cpi_ctx = ctx.accounts.withdraw().with_signer(signer);
 
@@ -104,8 +261,10 @@ pub fn handler(ctx: Context<Withdraw>, _index: i32, nonce: u8) -> ProgramResult
         }
     }
 
-    if Seconds::now() > { incentive.end_time } {
+    if current_time > { incentive.end_time } {
         require!(incentive.num_of_stakes > 0, NoStakes);
+        let liquidity = ctx.accounts.user_stake.load()?.liquidity;
+        incentive.remove_stake_liquidity(liquidity, boost, current_time);
         close(
             ctx.accounts.user_stake.to_account_info(),
             ctx.accounts.owner.to_account_info(),
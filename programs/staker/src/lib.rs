@@ -1,16 +1,17 @@
-mod decimals;
-mod errors;
+pub mod decimals;
+pub mod errors;
 mod instructions;
-mod math;
-mod structs;
+pub mod math;
+pub mod structs;
 mod uint;
-mod util;
+pub mod util;
 
 use anchor_lang::prelude::*;
 
 use decimals::*;
 use errors::*;
 use instructions::*;
+use structs::BoostTier;
 
 declare_id!("MJ6WF1tpEJ7Gk8ULqejDJapRfqBwBEp1dH5QvAgYxu9");
 
@@ -19,28 +20,77 @@ pub mod staker {
 
     use super::*;
 
+    pub fn create_incentive_list(ctx: Context<CreateIncentiveList>) -> ProgramResult {
+        instructions::create_incentive_list::handler(ctx)
+    }
+
     pub fn create_incentive(
         ctx: Context<CreateIncentive>,
         nonce: u8,
         reward: TokenAmount,
         start_time: Seconds,
         end_time: Seconds,
+        min_tick: Option<i32>,
+        max_tick: Option<i32>,
+    ) -> ProgramResult {
+        instructions::create_incentive::handler(
+            ctx, nonce, reward, start_time, end_time, min_tick, max_tick,
+        )
+    }
+
+    pub fn add_incentive_reward(
+        ctx: Context<AddIncentiveReward>,
+        nonce: u8,
+        reward: TokenAmount,
     ) -> ProgramResult {
-        instructions::create_incentive::handler(ctx, nonce, reward, start_time, end_time)
+        instructions::add_incentive_reward::handler(ctx, nonce, reward)
     }
 
-    pub fn stake(ctx: Context<CreateUserStake>, _index: i32) -> ProgramResult {
-        instructions::stake::handler(ctx)
+    pub fn fund_incentive<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundIncentive<'info>>,
+        amounts: Vec<TokenAmount>,
+        end_time: Option<Seconds>,
+    ) -> ProgramResult {
+        instructions::fund_incentive::handler(ctx, amounts, end_time)
+    }
+
+    pub fn set_boost_tiers(
+        ctx: Context<SetBoostTiers>,
+        boost_tiers: Vec<BoostTier>,
+    ) -> ProgramResult {
+        instructions::set_boost_tiers::handler(ctx, boost_tiers)
+    }
+
+    pub fn stake(
+        ctx: Context<CreateUserStake>,
+        index: i32,
+        lock_duration: Option<Seconds>,
+    ) -> ProgramResult {
+        instructions::stake::handler(ctx, index, lock_duration)
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, _index: i32, nonce: u8) -> ProgramResult {
-        instructions::withdraw::handler(ctx, _index, nonce)
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        index: i32,
+        nonce: u8,
+    ) -> ProgramResult {
+        instructions::withdraw::handler(ctx, index, nonce)
     }
 
-    pub fn end_incentive(ctx: Context<ReturnFounds>, nonce: u8) -> ProgramResult {
+    pub fn end_incentive<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReturnFounds<'info>>,
+        nonce: u8,
+    ) -> ProgramResult {
         instructions::end_incentive::handler(ctx, nonce)
     }
 
+    pub fn cancel_incentive<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelIncentive<'info>>,
+        nonce: u8,
+    ) -> ProgramResult {
+        instructions::cancel_incentive::handler(ctx, nonce)
+    }
+
     pub fn remove_stake(ctx: Context<RemoveStake>) -> ProgramResult {
         instructions::remove_stake::handler(ctx)
     }
@@ -48,4 +98,16 @@ pub mod staker {
     pub fn close_stake_by_owner(ctx: Context<CloseStakeByOwner>, _index: i32) -> ProgramResult {
         instructions::close_stake_by_owner::handler(ctx, _index)
     }
+
+    pub fn close_invalidated_stake(ctx: Context<CloseInvalidatedStake>) -> ProgramResult {
+        instructions::close_invalidated_stake::handler(ctx)
+    }
+
+    pub fn migrate_incentive(ctx: Context<MigrateIncentive>) -> ProgramResult {
+        instructions::migrate_incentive::handler(ctx)
+    }
+
+    pub fn migrate_user_stake(ctx: Context<MigrateUserStake>) -> ProgramResult {
+        instructions::migrate_user_stake::handler(ctx)
+    }
 }
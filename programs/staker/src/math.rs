@@ -17,22 +17,87 @@ pub fn calculate_reward(
         return Err(ErrorCode::NotStarted.into());
     }
 
-    let seconds_inside = Seconds::from_decimal(
-        seconds_per_liquidity_inside.unchecked_sub(seconds_per_liquidity_inside_initial)
-            * liquidity,
-    );
-
     let total_seconds_unclaimed =
         cmp::max(end_time, current_time) - start_time - total_seconds_claimed;
 
+    // boosted stakes can't claim more seconds than are left
+    let seconds_inside = cmp::min(
+        Seconds::from_decimal(
+            seconds_per_liquidity_inside.unchecked_sub(seconds_per_liquidity_inside_initial)
+                * liquidity,
+        ),
+        total_seconds_unclaimed,
+    );
+
     let result = total_reward_unclaimed * seconds_inside / total_seconds_unclaimed;
     Ok((seconds_inside, result))
 }
 
+// boosts only shift rewards between stakes, boosted liquidity is scaled back by the total to boosted
+// liquidity averaged over the period, so all stakes together can't claim more seconds than have passed
+pub fn normalize_boost(
+    boosted_liquidity: Liquidity,
+    boosted_seconds_per_liquidity: SecondsPerLiquidity,
+    seconds_elapsed: Seconds,
+) -> Liquidity {
+    if seconds_elapsed.is_zero() {
+        return boosted_liquidity;
+    }
+
+    // the average is at most one, the result is capped in case of rounding
+    let normalized = U256::from(boosted_liquidity.get())
+        * U256::from(boosted_seconds_per_liquidity.get())
+        / U256::from(SecondsPerLiquidity::from_integer(seconds_elapsed.get()).get());
+    Liquidity::new(cmp::min(normalized, U256::from(boosted_liquidity.get())).as_u128())
+}
+
+// part of the unclaimed reward already earned by stakers at the time of cancellation, rounded in their favor
+pub fn calculate_earned_reward(
+    total_reward_unclaimed: TokenAmount,
+    total_seconds_claimed: Seconds,
+    start_time: Seconds,
+    end_time: Seconds,
+    current_time: Seconds,
+) -> TokenAmount {
+    if current_time <= start_time {
+        return TokenAmount::new(0);
+    }
+    if current_time >= end_time {
+        return total_reward_unclaimed;
+    }
+
+    let total_seconds_unclaimed = (end_time - start_time)
+        .get()
+        .saturating_sub(total_seconds_claimed.get()) as u128;
+    let seconds_earned = (current_time - start_time)
+        .get()
+        .saturating_sub(total_seconds_claimed.get()) as u128;
+    if total_seconds_unclaimed == 0 {
+        return total_reward_unclaimed;
+    }
+
+    let earned = (total_reward_unclaimed.get() as u128 * seconds_earned + total_seconds_unclaimed
+        - 1)
+        / total_seconds_unclaimed;
+    TokenAmount::new(earned as u64)
+}
+
+// extending an incentive must not lower the reward paid per remaining second
+pub fn is_reward_rate_kept(
+    total_reward_unclaimed: TokenAmount,
+    total_seconds_unclaimed: Seconds,
+    new_total_reward_unclaimed: TokenAmount,
+    new_total_seconds_unclaimed: Seconds,
+) -> bool {
+    new_total_reward_unclaimed.get() as u128 * total_seconds_unclaimed.get() as u128
+        >= total_reward_unclaimed.get() as u128 * new_total_seconds_unclaimed.get() as u128
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::structs::{Incentive, UserStake};
 
     #[test]
     fn test_calculate_reward_1() {
@@ -291,4 +356,272 @@ mod tests {
         assert_eq!(result, TokenAmount::new(4490775));
         assert_eq!(seconds_inside, Seconds::new(1164));
     }
+
+    #[test]
+    fn test_calculate_reward_15() {
+        // several reward tokens share the same seconds inside
+        let (seconds_inside, result) = calculate_reward(
+            TokenAmount::new(1000),
+            Seconds::new(0),
+            Seconds::new(0),
+            Seconds::new(100),
+            Liquidity::from_integer(2_000_000),
+            SecondsPerLiquidity::new(10_000_000),
+            SecondsPerLiquidity::new(35_000_000),
+            Seconds::new(50),
+        )
+        .unwrap();
+        let (extra_seconds_inside, extra_result) = calculate_reward(
+            TokenAmount::new(3000),
+            Seconds::new(0),
+            Seconds::new(0),
+            Seconds::new(100),
+            Liquidity::from_integer(2_000_000),
+            SecondsPerLiquidity::new(10_000_000),
+            SecondsPerLiquidity::new(35_000_000),
+            Seconds::new(50),
+        )
+        .unwrap();
+
+        assert_eq!(seconds_inside, extra_seconds_inside);
+        assert_eq!(result, TokenAmount::new(500));
+        assert_eq!(extra_result, TokenAmount::new(1500));
+    }
+
+    #[test]
+    fn test_is_reward_rate_kept() {
+        // no extension
+        assert!(is_reward_rate_kept(
+            TokenAmount::new(1000),
+            Seconds::new(100),
+            TokenAmount::new(1000),
+            Seconds::new(100),
+        ));
+        // doubled duration with doubled reward
+        assert!(is_reward_rate_kept(
+            TokenAmount::new(1000),
+            Seconds::new(100),
+            TokenAmount::new(2000),
+            Seconds::new(200),
+        ));
+        // doubled duration without enough reward
+        assert!(!is_reward_rate_kept(
+            TokenAmount::new(1000),
+            Seconds::new(100),
+            TokenAmount::new(1999),
+            Seconds::new(200),
+        ));
+        // drained reward can always be extended
+        assert!(is_reward_rate_kept(
+            TokenAmount::new(0),
+            Seconds::new(100),
+            TokenAmount::new(0),
+            Seconds::new(u64::MAX),
+        ));
+    }
+
+    #[test]
+    fn test_calculate_earned_reward() {
+        // before start nothing is earned
+        assert_eq!(
+            calculate_earned_reward(
+                TokenAmount::new(1000),
+                Seconds::new(0),
+                Seconds::new(100),
+                Seconds::new(200),
+                Seconds::new(50),
+            ),
+            TokenAmount::new(0)
+        );
+        // half of the duration
+        assert_eq!(
+            calculate_earned_reward(
+                TokenAmount::new(1000),
+                Seconds::new(0),
+                Seconds::new(100),
+                Seconds::new(200),
+                Seconds::new(150),
+            ),
+            TokenAmount::new(500)
+        );
+        // claimed seconds are already paid out of the reward
+        assert_eq!(
+            calculate_earned_reward(
+                TokenAmount::new(800),
+                Seconds::new(20),
+                Seconds::new(100),
+                Seconds::new(200),
+                Seconds::new(150),
+            ),
+            TokenAmount::new(300)
+        );
+        // rounding up in favor of stakers
+        assert_eq!(
+            calculate_earned_reward(
+                TokenAmount::new(1000),
+                Seconds::new(0),
+                Seconds::new(0),
+                Seconds::new(3),
+                Seconds::new(1),
+            ),
+            TokenAmount::new(334)
+        );
+        // after end everything is earned
+        assert_eq!(
+            calculate_earned_reward(
+                TokenAmount::new(1000),
+                Seconds::new(0),
+                Seconds::new(100),
+                Seconds::new(200),
+                Seconds::new(250),
+            ),
+            TokenAmount::new(1000)
+        );
+    }
+
+    #[test]
+    fn test_calculate_reward_16() {
+        // seconds inside are capped by seconds left, boosts are normalized beforehand by normalize_boost
+        let (seconds_inside, result) = calculate_reward(
+            TokenAmount::new(1000),
+            Seconds::new(40),
+            Seconds::new(0),
+            Seconds::new(100),
+            Liquidity::from_integer(4_000_000),
+            SecondsPerLiquidity::new(10_000_000),
+            SecondsPerLiquidity::new(35_000_000),
+            Seconds::new(50),
+        )
+        .unwrap();
+        assert_eq!(seconds_inside, Seconds::new(60));
+        assert_eq!(result, TokenAmount::new(1000));
+    }
+
+    #[test]
+    fn test_normalize_boost() {
+        // no time has passed since the last withdraw
+        assert_eq!(
+            normalize_boost(
+                Liquidity::from_integer(10),
+                SecondsPerLiquidity::new(0),
+                Seconds::new(0)
+            ),
+            Liquidity::from_integer(10)
+        );
+        // single stake boosted 2x for the whole period is scaled back to its own liquidity
+        assert_eq!(
+            normalize_boost(
+                Liquidity::from_integer(20),
+                SecondsPerLiquidity::from_integer(50),
+                Seconds::new(100)
+            ),
+            Liquidity::from_integer(10)
+        );
+        // boosted stakes made up half of the period, the stake was alone for the other half
+        assert_eq!(
+            normalize_boost(
+                Liquidity::from_integer(20),
+                SecondsPerLiquidity::from_integer(75),
+                Seconds::new(100)
+            ),
+            Liquidity::from_integer(15)
+        );
+        // never more than the boosted liquidity
+        assert_eq!(
+            normalize_boost(
+                Liquidity::from_integer(20),
+                SecondsPerLiquidity::from_integer(101),
+                Seconds::new(100)
+            ),
+            Liquidity::from_integer(20)
+        );
+        // no overflow on max values
+        assert_eq!(
+            normalize_boost(
+                Liquidity::new(u128::MAX),
+                SecondsPerLiquidity::from_integer(u64::MAX),
+                Seconds::new(u64::MAX)
+            ),
+            Liquidity::new(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn test_calculate_reward_boosted_stakes() {
+        // two stakes of 1_000_000 inside the whole pool liquidity for half of the incentive,
+        // the first one boosted 3x, both withdraw and close at the same time
+        let mut incentive = Incentive {
+            total_reward_unclaimed: TokenAmount::new(1000),
+            start_time: Seconds::new(0),
+            end_time: Seconds::new(100),
+            ..Default::default()
+        };
+        let mut stake = |boost| {
+            let liquidity = Liquidity::from_integer(1_000_000);
+            incentive.add_stake_liquidity(liquidity, boost, Seconds::new(0));
+            UserStake {
+                liquidity,
+                boost,
+                boosted_seconds_per_liquidity_initial: incentive.boosted_seconds_per_liquidity,
+                last_withdraw: Seconds::new(0),
+                ..Default::default()
+            }
+        };
+        let first = stake(Multiplier::from_integer(3));
+        let second = stake(Multiplier::from_integer(1));
+
+        let withdraw = |incentive: &mut Incentive, user_stake: &UserStake| {
+            let current_time = Seconds::new(50);
+            incentive.update_boosted_seconds(current_time);
+            let liquidity =
+                user_stake.normalized_liquidity(user_stake.liquidity, incentive, current_time);
+            let reward_unclaimed = incentive.total_reward_unclaimed;
+            let total_seconds_claimed = incentive.total_seconds_claimed;
+            let (seconds_inside, reward) = calculate_reward(
+                reward_unclaimed,
+                total_seconds_claimed,
+                incentive.start_time,
+                incentive.end_time,
+                liquidity,
+                SecondsPerLiquidity::new(0),
+                SecondsPerLiquidity::new(25_000_000),
+                current_time,
+            )
+            .unwrap();
+            incentive.total_seconds_claimed = total_seconds_claimed + seconds_inside;
+            incentive.total_reward_unclaimed = reward_unclaimed - reward;
+            incentive.remove_stake_liquidity(user_stake.liquidity, user_stake.boost, current_time);
+            (liquidity, reward)
+        };
+
+        // boosts are scaled back by half, closing the other stake first doesn't change that
+        {
+            let mut incentive = incentive;
+            let first = withdraw(&mut incentive, &first);
+            let second = withdraw(&mut incentive, &second);
+            assert_eq!(
+                first,
+                (Liquidity::from_integer(1_500_000), TokenAmount::new(370))
+            );
+            assert_eq!(
+                second,
+                (Liquidity::from_integer(500_000), TokenAmount::new(120))
+            );
+        }
+        {
+            let mut incentive = incentive;
+            let second = withdraw(&mut incentive, &second);
+            let first = withdraw(&mut incentive, &first);
+            assert_eq!(
+                first,
+                (Liquidity::from_integer(1_500_000), TokenAmount::new(370))
+            );
+            assert_eq!(
+                second,
+                (Liquidity::from_integer(500_000), TokenAmount::new(120))
+            );
+            // together they earn at most what half of the incentive pays
+            assert!(first.1 + second.1 <= TokenAmount::new(500));
+        }
+    }
 }
@@ -1,5 +1,40 @@
-use crate::decimals::{Seconds, TokenAmount};
+use crate::decimals::*;
 use anchor_lang::prelude::*;
+use invariant::structs::MAX_TICK;
+
+pub const MAX_EXTRA_REWARDS: usize = 2;
+pub const MAX_BOOST_TIERS: usize = 4;
+
+#[zero_copy]
+#[derive(PartialEq, Default, Debug)]
+pub struct IncentiveReward {
+    pub token_account: Pubkey,
+    pub total_reward_unclaimed: TokenAmount,
+}
+
+#[zero_copy]
+#[derive(PartialEq, Default, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct BoostTier {
+    pub lock_duration: Seconds,
+    pub multiplier: Multiplier,
+}
+
+// accounts created before extra rewards, tick bands and boosts were added, discriminator included
+pub const LEGACY_INCENTIVE_SIZE: usize = 153;
+
+#[derive(Clone, Copy, PartialEq, Default, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyIncentive {
+    pub founder: Pubkey,
+    pub token_account: Pubkey,
+    pub total_reward_unclaimed: TokenAmount,
+    pub total_seconds_claimed: Seconds,
+    pub start_time: Seconds,
+    pub end_time: Seconds,
+    pub end_claim_time: Seconds,
+    pub num_of_stakes: u64,
+    pub pool: Pubkey,
+    pub nonce: u8,
+}
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -8,11 +43,235 @@ pub struct Incentive {
     pub founder: Pubkey,
     pub token_account: Pubkey,
     pub total_reward_unclaimed: TokenAmount,
+    pub extra_rewards: [IncentiveReward; MAX_EXTRA_REWARDS],
+    pub extra_rewards_count: u8,
     pub total_seconds_claimed: Seconds,
     pub start_time: Seconds,
     pub end_time: Seconds,
     pub end_claim_time: Seconds,
     pub num_of_stakes: u64,
     pub pool: Pubkey,
+    pub min_tick: i32,
+    pub max_tick: i32,
+    pub boost_tiers: [BoostTier; MAX_BOOST_TIERS],
+    pub boost_tiers_count: u8,
+    pub total_liquidity: Liquidity,         // liquidity of all stakes
+    pub total_boosted_liquidity: Liquidity, // same, weighted by boosts
+    pub boosted_seconds_per_liquidity: SecondsPerLiquidity, // total to boosted liquidity over time
+    pub last_boost_update: Seconds,
+    pub index: u32,
     pub nonce: u8,
+    pub bump: u8,
+}
+
+impl Incentive {
+    // legacy incentives aren't derived from the incentive list, so index and bump stay unset,
+    // stake totals are rebuilt as their stakes get migrated
+    pub fn from_legacy(legacy: LegacyIncentive) -> Self {
+        Incentive {
+            founder: legacy.founder,
+            token_account: legacy.token_account,
+            total_reward_unclaimed: legacy.total_reward_unclaimed,
+            total_seconds_claimed: legacy.total_seconds_claimed,
+            start_time: legacy.start_time,
+            end_time: legacy.end_time,
+            end_claim_time: legacy.end_claim_time,
+            num_of_stakes: legacy.num_of_stakes,
+            pool: legacy.pool,
+            min_tick: -MAX_TICK,
+            max_tick: MAX_TICK,
+            last_boost_update: legacy.start_time,
+            nonce: legacy.nonce,
+            ..Default::default()
+        }
+    }
+
+    // boost of the longest tier the lock duration qualifies for
+    pub fn get_boost(&self, lock_duration: Seconds) -> Multiplier {
+        let boost_tiers = self.boost_tiers;
+        boost_tiers[..self.boost_tiers_count as usize]
+            .iter()
+            .filter(|tier| tier.lock_duration <= lock_duration)
+            .map(|tier| tier.multiplier)
+            .max()
+            .unwrap_or_else(|| Multiplier::from_integer(1))
+    }
+
+    // ratio of total to boosted liquidity summed up over time, a stake paid from it is scaled by
+    // every stake it shared the incentive with, not only by the ones still staked when it claims
+    pub fn boosted_seconds_per_liquidity_at(&self, current_time: Seconds) -> SecondsPerLiquidity {
+        let elapsed = current_time
+            .get()
+            .saturating_sub({ self.last_boost_update }.get());
+        let elapsed = SecondsPerLiquidity::from_integer(elapsed).get();
+        // nothing is staked, so nothing is scaled
+        let delta = match { self.total_boosted_liquidity }.get() {
+            0 => elapsed,
+            total_boosted_liquidity => (U256::from(elapsed)
+                * U256::from({ self.total_liquidity }.get())
+                / U256::from(total_boosted_liquidity))
+            .as_u128(),
+        };
+        SecondsPerLiquidity::new({ self.boosted_seconds_per_liquidity }.get() + delta)
+    }
+
+    // has to run before the stake totals change
+    pub fn update_boosted_seconds(&mut self, current_time: Seconds) {
+        self.boosted_seconds_per_liquidity = self.boosted_seconds_per_liquidity_at(current_time);
+        self.last_boost_update = current_time;
+    }
+
+    pub fn add_stake_liquidity(
+        &mut self,
+        liquidity: Liquidity,
+        boost: Multiplier,
+        current_time: Seconds,
+    ) {
+        self.update_boosted_seconds(current_time);
+        self.total_liquidity = self.total_liquidity + liquidity;
+        self.total_boosted_liquidity = self.total_boosted_liquidity + liquidity.big_mul(boost);
+    }
+
+    pub fn remove_stake_liquidity(
+        &mut self,
+        liquidity: Liquidity,
+        boost: Multiplier,
+        current_time: Seconds,
+    ) {
+        self.update_boosted_seconds(current_time);
+        self.total_liquidity = self.total_liquidity - liquidity;
+        self.total_boosted_liquidity = self.total_boosted_liquidity - liquidity.big_mul(boost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_boost() {
+        let mut incentive = Incentive::default();
+        assert_eq!(
+            incentive.get_boost(Seconds::new(1000)),
+            Multiplier::from_integer(1)
+        );
+
+        incentive.boost_tiers[0] = BoostTier {
+            lock_duration: Seconds::new(100),
+            multiplier: Multiplier::from_scale(15, 1),
+        };
+        incentive.boost_tiers[1] = BoostTier {
+            lock_duration: Seconds::new(1000),
+            multiplier: Multiplier::from_integer(2),
+        };
+        incentive.boost_tiers_count = 2;
+
+        assert_eq!(
+            incentive.get_boost(Seconds::new(0)),
+            Multiplier::from_integer(1)
+        );
+        assert_eq!(
+            incentive.get_boost(Seconds::new(99)),
+            Multiplier::from_integer(1)
+        );
+        assert_eq!(
+            incentive.get_boost(Seconds::new(100)),
+            Multiplier::from_scale(15, 1)
+        );
+        assert_eq!(
+            incentive.get_boost(Seconds::new(999)),
+            Multiplier::from_scale(15, 1)
+        );
+        assert_eq!(
+            incentive.get_boost(Seconds::new(5000)),
+            Multiplier::from_integer(2)
+        );
+    }
+
+    #[test]
+    fn test_stake_liquidity() {
+        let mut incentive = Incentive::default();
+
+        incentive.add_stake_liquidity(
+            Liquidity::from_integer(100),
+            Multiplier::from_integer(1),
+            Seconds::new(0),
+        );
+        incentive.add_stake_liquidity(
+            Liquidity::from_integer(50),
+            Multiplier::from_scale(15, 1),
+            Seconds::new(0),
+        );
+        assert_eq!({ incentive.total_liquidity }, Liquidity::from_integer(150));
+        assert_eq!(
+            { incentive.total_boosted_liquidity },
+            Liquidity::from_integer(175)
+        );
+
+        incentive.remove_stake_liquidity(
+            Liquidity::from_integer(50),
+            Multiplier::from_scale(15, 1),
+            Seconds::new(70),
+        );
+        assert_eq!({ incentive.total_liquidity }, Liquidity::from_integer(100));
+        assert_eq!(
+            { incentive.total_boosted_liquidity },
+            Liquidity::from_integer(100)
+        );
+        // seconds are counted with the totals from before the change
+        assert_eq!(
+            { incentive.boosted_seconds_per_liquidity },
+            SecondsPerLiquidity::from_integer(60)
+        );
+        assert_eq!({ incentive.last_boost_update }, Seconds::new(70));
+        assert_eq!(
+            incentive.boosted_seconds_per_liquidity_at(Seconds::new(100)),
+            SecondsPerLiquidity::from_integer(90)
+        );
+    }
+
+    #[test]
+    fn test_from_legacy() {
+        let legacy = LegacyIncentive {
+            founder: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            total_reward_unclaimed: TokenAmount::new(1000),
+            total_seconds_claimed: Seconds::new(10),
+            start_time: Seconds::new(100),
+            end_time: Seconds::new(200),
+            end_claim_time: Seconds::new(300),
+            num_of_stakes: 2,
+            pool: Pubkey::new_unique(),
+            nonce: 255,
+        };
+        // legacy layout is read as borsh
+        let data = legacy.try_to_vec().unwrap();
+        assert_eq!(LEGACY_INCENTIVE_SIZE, 8 + data.len());
+        assert_eq!(
+            LegacyIncentive::deserialize(&mut &data[..]).unwrap(),
+            legacy
+        );
+
+        let incentive = Incentive::from_legacy(legacy);
+        assert_eq!({ incentive.founder }, legacy.founder);
+        assert_eq!({ incentive.token_account }, legacy.token_account);
+        assert_eq!({ incentive.total_reward_unclaimed }, TokenAmount::new(1000));
+        assert_eq!({ incentive.total_seconds_claimed }, Seconds::new(10));
+        assert_eq!({ incentive.start_time }, Seconds::new(100));
+        assert_eq!({ incentive.end_time }, Seconds::new(200));
+        assert_eq!({ incentive.end_claim_time }, Seconds::new(300));
+        assert_eq!({ incentive.num_of_stakes }, 2);
+        assert_eq!({ incentive.pool }, legacy.pool);
+        assert_eq!(incentive.nonce, 255);
+        // whole pool is eligible and no stake is boosted
+        assert_eq!({ incentive.min_tick }, -MAX_TICK);
+        assert_eq!({ incentive.max_tick }, MAX_TICK);
+        assert_eq!(incentive.extra_rewards_count, 0);
+        assert_eq!(
+            incentive.get_boost(Seconds::new(u64::MAX)),
+            Multiplier::from_integer(1)
+        );
+        assert_eq!({ incentive.total_liquidity }, Liquidity::new(0));
+        assert_eq!({ incentive.last_boost_update }, Seconds::new(100));
+    }
 }
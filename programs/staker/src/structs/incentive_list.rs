@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+// incentives of a founder in a pool are derived from the pool, the founder and their index in the list
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct IncentiveList {
+    pub head: u32,
+    pub bump: u8,
+}
@@ -1,5 +1,7 @@
 pub mod incentive;
+pub mod incentive_list;
 pub mod user_stake;
 
 pub use incentive::*;
+pub use incentive_list::*;
 pub use user_stake::*;
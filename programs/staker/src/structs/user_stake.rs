@@ -1,12 +1,198 @@
-use crate::decimals::{Liquidity, SecondsPerLiquidity};
+use crate::decimals::*;
+use crate::math::normalize_boost;
+use crate::structs::Incentive;
 use anchor_lang::prelude::*;
+use invariant::structs::Position;
+use std::cmp;
+
+// accounts created before stakes were bound to the position state, discriminator included
+pub const LEGACY_USER_STAKE_SIZE: usize = 105;
+
+#[derive(Clone, Copy, PartialEq, Default, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserStake {
+    pub incentive: Pubkey,
+    pub position: Pubkey,
+    pub seconds_per_liquidity_initial: SecondsPerLiquidity,
+    pub liquidity: Liquidity,
+    pub bump: u8,
+}
+
 #[account(zero_copy)]
 #[repr(packed)]
 #[derive(PartialEq, Default, Debug)]
 pub struct UserStake {
     pub incentive: Pubkey,
     pub position: Pubkey,
+    pub owner: Pubkey,
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
     pub seconds_per_liquidity_initial: SecondsPerLiquidity,
     pub liquidity: Liquidity,
+    pub lock_until: Seconds,
+    pub boost: Multiplier,
+    pub range_nonce: u64,
+    pub payer: Pubkey, // gets the rent back when the stake is invalidated
+    pub boosted_seconds_per_liquidity_initial: SecondsPerLiquidity,
+    pub last_withdraw: Seconds, // or the stake itself if not withdrawn yet
     pub bump: u8,
 }
+
+impl UserStake {
+    // legacy stakes are bound to the position as it is at the migration, they weren't locked
+    // and whoever paid for them isn't known, so the rent goes back to the owner
+    pub fn from_legacy(legacy: LegacyUserStake, position: &Position) -> Self {
+        UserStake {
+            incentive: legacy.incentive,
+            position: legacy.position,
+            owner: position.owner,
+            lower_tick_index: position.lower_tick_index,
+            upper_tick_index: position.upper_tick_index,
+            seconds_per_liquidity_initial: legacy.seconds_per_liquidity_initial,
+            liquidity: legacy.liquidity,
+            lock_until: Seconds::new(0),
+            boost: Multiplier::from_integer(1),
+            range_nonce: position.range_nonce,
+            payer: position.owner,
+            boosted_seconds_per_liquidity_initial: SecondsPerLiquidity::new(0),
+            last_withdraw: Seconds::new(0),
+            bump: legacy.bump,
+        }
+    }
+
+    // transferred or re-ranged position doesn't back the stake anymore,
+    // a rebalance back to the same range still changes the nonce
+    pub fn is_backed_by(&self, position: &Position) -> bool {
+        self.owner == position.owner
+            && self.lower_tick_index == position.lower_tick_index
+            && self.upper_tick_index == position.upper_tick_index
+            && self.range_nonce == position.range_nonce
+    }
+
+    // only liquidity at both ends of the period is known, liquidity removed and added back
+    // in between isn't seen, so the lower of the two is counted
+    pub fn settled_liquidity(&self, current_liquidity: Liquidity) -> Liquidity {
+        cmp::min(self.liquidity, current_liquidity).big_mul(self.boost)
+    }
+
+    // settled liquidity scaled back by boosts of all stakes sharing the incentive since the last withdraw
+    pub fn normalized_liquidity(
+        &self,
+        current_liquidity: Liquidity,
+        incentive: &Incentive,
+        current_time: Seconds,
+    ) -> Liquidity {
+        normalize_boost(
+            self.settled_liquidity(current_liquidity),
+            incentive
+                .boosted_seconds_per_liquidity_at(current_time)
+                .unchecked_sub(self.boosted_seconds_per_liquidity_initial),
+            Seconds::new(
+                current_time
+                    .get()
+                    .saturating_sub({ self.last_withdraw }.get()),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_backed_by() {
+        let owner = Pubkey::new_unique();
+        let user_stake = UserStake {
+            owner,
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            ..Default::default()
+        };
+        let position = Position {
+            owner,
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            ..Default::default()
+        };
+        assert!(user_stake.is_backed_by(&position));
+
+        // transferred
+        let transferred = Position {
+            owner: Pubkey::new_unique(),
+            ..position
+        };
+        assert!(!user_stake.is_backed_by(&transferred));
+
+        // rebalanced
+        let rebalanced = Position {
+            lower_tick_index: -20,
+            ..position
+        };
+        assert!(!user_stake.is_backed_by(&rebalanced));
+
+        // rebalanced back to the same range
+        let rebalanced = Position {
+            range_nonce: 1,
+            ..position
+        };
+        assert!(!user_stake.is_backed_by(&rebalanced));
+    }
+
+    #[test]
+    fn test_settled_liquidity() {
+        let user_stake = UserStake {
+            liquidity: Liquidity::from_integer(100),
+            boost: Multiplier::from_integer(2),
+            ..Default::default()
+        };
+        // added liquidity doesn't count until the next snapshot
+        assert_eq!(
+            user_stake.settled_liquidity(Liquidity::from_integer(150)),
+            Liquidity::from_integer(200)
+        );
+        // decreased liquidity counts only what is left
+        assert_eq!(
+            user_stake.settled_liquidity(Liquidity::from_integer(50)),
+            Liquidity::from_integer(100)
+        );
+    }
+
+    #[test]
+    fn test_from_legacy() {
+        let legacy = LegacyUserStake {
+            incentive: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            seconds_per_liquidity_initial: SecondsPerLiquidity::from_integer(3),
+            liquidity: Liquidity::from_integer(100),
+            bump: 254,
+        };
+        // legacy layout is read as borsh
+        let data = legacy.try_to_vec().unwrap();
+        assert_eq!(LEGACY_USER_STAKE_SIZE, 8 + data.len());
+        assert_eq!(LegacyUserStake::deserialize(&mut &data[..]).unwrap(), legacy);
+
+        let position = Position {
+            owner: Pubkey::new_unique(),
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            range_nonce: 2,
+            ..Default::default()
+        };
+        let user_stake = UserStake::from_legacy(legacy, &position);
+        assert_eq!({ user_stake.incentive }, legacy.incentive);
+        assert_eq!({ user_stake.position }, legacy.position);
+        assert_eq!(
+            { user_stake.seconds_per_liquidity_initial },
+            SecondsPerLiquidity::from_integer(3)
+        );
+        assert_eq!({ user_stake.liquidity }, Liquidity::from_integer(100));
+        assert_eq!(user_stake.bump, 254);
+        assert!(user_stake.is_backed_by(&position));
+        assert_eq!({ user_stake.payer }, position.owner);
+        assert_eq!({ user_stake.lock_until }, Seconds::new(0));
+        assert_eq!(
+            user_stake.settled_liquidity(Liquidity::from_integer(100)),
+            Liquidity::from_integer(100)
+        );
+    }
+}
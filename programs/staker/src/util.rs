@@ -1,14 +1,14 @@
 use anchor_lang::__private::ErrorCode;
 use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
 use std::io::Write;
 
 use anchor_lang::prelude::*;
 
-pub const STAKER_SEED: &str = "staker";
+use crate::errors::ErrorCode::InvalidMigration;
 
-pub fn get_current_slot() -> u64 {
-    Clock::get().unwrap().slot
-}
+pub const STAKER_SEED: &str = "staker";
 
 pub fn close<'info>(
     info: AccountInfo<'info>,
@@ -29,3 +29,34 @@ pub fn close<'info>(
         .map_err(|_| ErrorCode::AccountDidNotSerialize)?;
     Ok(())
 }
+
+// resizes an account of a legacy layout, the returned copy of it has to be rewritten in the new one
+pub fn migrate_account<'info, T: AnchorDeserialize>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    discriminator: [u8; 8],
+    legacy_size: usize,
+    size: usize,
+) -> crate::Result<T> {
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == legacy_size && data[..8] == discriminator,
+            InvalidMigration
+        );
+        // packed layout of the legacy fields is the same as their borsh encoding
+        T::deserialize(&mut &data[8..]).map_err(|_| InvalidMigration)?
+    };
+
+    let lamports_required = Rent::get()?.minimum_balance(size);
+    if lamports_required > info.lamports() {
+        invoke(
+            &system_instruction::transfer(payer.key, info.key, lamports_required - info.lamports()),
+            &[payer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+
+    info.realloc(size, false)?;
+    Ok(legacy)
+}
@@ -0,0 +1 @@
+pub use staker::errors::ErrorCode as StakerErrorCode;
@@ -0,0 +1,12 @@
+// accounts, decimals, errors and reward math are shared with the program itself
+pub mod decimals;
+pub mod errors;
+pub mod math;
+pub mod structs;
+pub mod utils;
+
+pub use staker::util::STAKER_SEED;
+pub use staker::ID;
+pub const INCENTIVE_SEED: &str = "incentivev1";
+pub const INCENTIVE_LIST_SEED: &str = "incentivelistv1";
+pub const ANCHOR_DISCRIMINATOR_SIZE: usize = 8;
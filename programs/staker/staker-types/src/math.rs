@@ -0,0 +1,168 @@
+use crate::{
+    decimals::*,
+    structs::{Incentive, UserStake},
+};
+use invariant::structs::Position;
+use staker::errors::Result;
+
+pub use staker::math::{calculate_reward, normalize_boost};
+
+// rewards `withdraw` would pay right now, the primary token first followed by extra reward tokens
+pub fn estimate_rewards(
+    incentive: &Incentive,
+    user_stake: &UserStake,
+    position: &Position,
+    seconds_per_liquidity_inside: SecondsPerLiquidity,
+    current_time: Seconds,
+) -> Result<Vec<TokenAmount>> {
+    let extra_rewards = incentive.extra_rewards;
+    let extra_rewards = &extra_rewards[..incentive.extra_rewards_count as usize];
+
+    // withdraw closes stakes the position doesn't back anymore without paying anything
+    if !user_stake.is_backed_by(position) {
+        return Ok(vec![TokenAmount::new(0); extra_rewards.len() + 1]);
+    }
+
+    let current_liquidity = Liquidity::new({ position.liquidity }.get());
+    let liquidity = user_stake.normalized_liquidity(current_liquidity, incentive, current_time);
+
+    let rewards_unclaimed = std::iter::once(incentive.total_reward_unclaimed).chain(
+        extra_rewards
+            .iter()
+            .map(|extra_reward| extra_reward.total_reward_unclaimed),
+    );
+
+    rewards_unclaimed
+        .map(|reward_unclaimed| {
+            calculate_reward(
+                reward_unclaimed,
+                incentive.total_seconds_claimed,
+                incentive.start_time,
+                incentive.end_time,
+                liquidity,
+                user_stake.seconds_per_liquidity_initial,
+                seconds_per_liquidity_inside,
+                current_time,
+            )
+            .map(|(_, reward)| reward)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::IncentiveReward;
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_estimate_rewards() {
+        let mut incentive = Incentive {
+            total_reward_unclaimed: TokenAmount::new(1000),
+            start_time: Seconds::new(0),
+            end_time: Seconds::new(100),
+            ..Default::default()
+        };
+        let owner = Pubkey::new_unique();
+        let user_stake = UserStake {
+            owner,
+            liquidity: Liquidity::from_integer(1_000_000),
+            boost: Multiplier::from_integer(2),
+            seconds_per_liquidity_initial: SecondsPerLiquidity::new(10_000_000),
+            ..Default::default()
+        };
+        let mut position = Position {
+            owner,
+            ..Default::default()
+        };
+        position.liquidity.v = Liquidity::from_integer(1_000_000).get();
+        let seconds_per_liquidity_inside = SecondsPerLiquidity::new(35_000_000);
+
+        // primary token only
+        {
+            let rewards = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(50),
+            )
+            .unwrap();
+            assert_eq!(rewards, vec![TokenAmount::new(500)]);
+        }
+        // extra reward tokens share the same seconds inside
+        {
+            incentive.extra_rewards[0] = IncentiveReward {
+                token_account: Pubkey::new_unique(),
+                total_reward_unclaimed: TokenAmount::new(3000),
+            };
+            incentive.extra_rewards_count = 1;
+
+            let rewards = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(50),
+            )
+            .unwrap();
+            assert_eq!(rewards, vec![TokenAmount::new(500), TokenAmount::new(1500)]);
+        }
+        // liquidity removed since the snapshot
+        {
+            let mut position = Position { ..position };
+            position.liquidity.v = Liquidity::from_integer(500_000).get();
+            let rewards = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(50),
+            )
+            .unwrap();
+            assert_eq!(rewards, vec![TokenAmount::new(250), TokenAmount::new(750)]);
+        }
+        // transferred position doesn't back the stake
+        {
+            let position = Position {
+                owner: Pubkey::new_unique(),
+                ..position
+            };
+            let rewards = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(50),
+            )
+            .unwrap();
+            assert_eq!(rewards, vec![TokenAmount::new(0), TokenAmount::new(0)]);
+        }
+        // boost of the only stake is normalized away
+        {
+            incentive.total_liquidity = Liquidity::from_integer(1_000_000);
+            incentive.total_boosted_liquidity = Liquidity::from_integer(2_000_000);
+
+            let rewards = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(50),
+            )
+            .unwrap();
+            assert_eq!(rewards, vec![TokenAmount::new(250), TokenAmount::new(750)]);
+        }
+        // not started yet
+        {
+            let result = estimate_rewards(
+                &incentive,
+                &user_stake,
+                &position,
+                seconds_per_liquidity_inside,
+                Seconds::new(0),
+            );
+            assert!(result.is_err());
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::__private::bytemuck::{try_from_bytes, Pod};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+use crate::{ANCHOR_DISCRIMINATOR_SIZE, ID, INCENTIVE_LIST_SEED, INCENTIVE_SEED, STAKER_SEED};
+
+pub fn get_staker_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKER_SEED.as_bytes()], &ID)
+}
+
+pub fn get_incentive_list_address(pool: Pubkey, founder: Pubkey) -> Pubkey {
+    let (incentive_list_address, _) = Pubkey::find_program_address(
+        &[
+            INCENTIVE_LIST_SEED.as_bytes(),
+            pool.as_ref(),
+            founder.as_ref(),
+        ],
+        &ID,
+    );
+    incentive_list_address
+}
+
+pub fn get_incentive_address(pool: Pubkey, founder: Pubkey, index: u32) -> Pubkey {
+    let (incentive_address, _) = Pubkey::find_program_address(
+        &[
+            INCENTIVE_SEED.as_bytes(),
+            pool.as_ref(),
+            founder.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &ID,
+    );
+    incentive_address
+}
+
+// incentives of a founder in a pool are indexed from 0 to the head of their list
+pub fn get_incentive_addresses(pool: Pubkey, founder: Pubkey, head: u32) -> Vec<Pubkey> {
+    (0..head)
+        .map(|index| get_incentive_address(pool, founder, index))
+        .collect()
+}
+
+pub fn get_user_stake_address(incentive: Pubkey, pool: Pubkey, position_id: u128) -> Pubkey {
+    let (user_stake_address, _) = Pubkey::find_program_address(
+        &[
+            STAKER_SEED.as_bytes(),
+            incentive.as_ref(),
+            pool.as_ref(),
+            &position_id.to_le_bytes(),
+        ],
+        &ID,
+    );
+    user_stake_address
+}
+
+// decodes raw account data, returns None for accounts of a different type
+pub fn deserialize_account<T: Pod + Discriminator>(data: &[u8]) -> Option<T> {
+    let end = ANCHOR_DISCRIMINATOR_SIZE + size_of::<T>();
+    if data.len() < end || data[..ANCHOR_DISCRIMINATOR_SIZE] != T::discriminator() {
+        return None;
+    }
+    try_from_bytes::<T>(&data[ANCHOR_DISCRIMINATOR_SIZE..end])
+        .ok()
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{Incentive, IncentiveList};
+
+    #[test]
+    fn test_get_incentive_addresses() {
+        let pool = Pubkey::new_unique();
+        let founder = Pubkey::new_unique();
+
+        let addresses = get_incentive_addresses(pool, founder, 3);
+        assert_eq!(addresses.len(), 3);
+        assert_eq!(addresses[2], get_incentive_address(pool, founder, 2));
+        assert_ne!(addresses[0], addresses[1]);
+        assert_ne!(
+            get_incentive_address(pool, founder, 0),
+            get_incentive_address(Pubkey::new_unique(), founder, 0)
+        );
+        // every founder counts their incentives separately
+        assert_ne!(
+            get_incentive_address(pool, founder, 0),
+            get_incentive_address(pool, Pubkey::new_unique(), 0)
+        );
+        assert_ne!(
+            get_incentive_list_address(pool, founder),
+            get_incentive_list_address(pool, Pubkey::new_unique())
+        );
+    }
+
+    #[test]
+    fn test_deserialize_account() {
+        let mut data = IncentiveList::discriminator().to_vec();
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.push(254);
+
+        let incentive_list = deserialize_account::<IncentiveList>(&data).unwrap();
+        assert_eq!({ incentive_list.head }, 7);
+        assert_eq!(incentive_list.bump, 254);
+
+        assert!(deserialize_account::<Incentive>(&data).is_none());
+        assert!(deserialize_account::<IncentiveList>(&data[..4]).is_none());
+    }
+}
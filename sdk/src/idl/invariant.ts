@@ -154,6 +154,37 @@ export type Invariant = {
         }
       ]
     },
+    {
+      "name": "createPoolStats",
+      "accounts": [
+        {
+          "name": "poolStats",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "swap",
       "accounts": [
@@ -167,6 +198,11 @@ export type Invariant = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "poolStats",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tickmap",
           "isMut": true,
@@ -524,6 +560,11 @@ export type Invariant = {
         {
           "name": "owner",
           "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
           "isSigner": true
         },
         {
@@ -583,91 +624,60 @@ export type Invariant = {
       ]
     },
     {
-      "name": "transferPositionOwnership",
+      "name": "rebalancePosition",
       "accounts": [
         {
-          "name": "ownerList",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "recipientList",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "newPosition",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "removedPosition",
+          "name": "poolStats",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lastPosition",
+          "name": "tickmap",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "oldLowerTick",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "recipient",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "index",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "claimFee",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "oldUpperTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "position",
+          "name": "newLowerTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lowerTick",
+          "name": "newUpperTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "upperTick",
+          "name": "owner",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
@@ -724,133 +734,107 @@ export type Invariant = {
         {
           "name": "upperTickIndex",
           "type": "i32"
-        }
-      ]
-    },
-    {
-      "name": "updateSecondsPerLiquidity",
-      "accounts": [
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "lowerTick",
-          "isMut": false,
-          "isSigner": false
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
         },
         {
-          "name": "upperTick",
-          "isMut": false,
-          "isSigner": false
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
         },
         {
-          "name": "position",
-          "isMut": true,
-          "isSigner": false
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
         },
         {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
+          "name": "swapXToY",
+          "type": "bool"
         },
         {
-          "name": "tokenY",
-          "isMut": false,
-          "isSigner": false
+          "name": "swapAmount",
+          "type": "u64"
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "swapSqrtPriceLimit",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
+    },
+    {
+      "name": "lockPosition",
+      "accounts": [
         {
-          "name": "signer",
+          "name": "position",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "rent",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "lowerTickIndex",
-          "type": "i32"
-        },
-        {
-          "name": "upperTickIndex",
-          "type": "i32"
+          "name": "index",
+          "type": "u32"
         },
         {
-          "name": "index",
-          "type": "i32"
+          "name": "unlockTimestamp",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "withdrawProtocolFee",
+      "name": "migratePool",
       "accounts": [
-        {
-          "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenY",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "accountX",
-          "isMut": true,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateTick",
+      "accounts": [
         {
-          "name": "accountY",
+          "name": "tick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
-          "isMut": true,
+          "name": "pool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveY",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authority",
-          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "programAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -858,278 +842,1030 @@ export type Invariant = {
       "args": []
     },
     {
-      "name": "changeProtocolFee",
+      "name": "migratePosition",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
+          "name": "position",
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "pool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenX",
+          "name": "lowerTick",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "upperTick",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
+          "name": "payer",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "programAuthority",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "protocolFee",
-          "type": {
-            "defined": "FixedPoint"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "changeFeeReceiver",
+      "name": "transferPositionOwnership",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
+          "name": "ownerList",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "recipientList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "newPosition",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "removedPosition",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "feeReceiver",
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "feeTier",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "fee",
-            "type": {
-              "defined": "FixedPoint"
-            }
-          },
-          {
-            "name": "tickSpacing",
-            "type": "u16"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "name": "oracle",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "data",
-            "type": {
-              "array": [
-                {
-                  "defined": "Record"
-                },
-                256
-              ]
-            }
-          },
-          {
-            "name": "head",
-            "type": "u16"
-          },
-          {
-            "name": "amount",
-            "type": "u16"
-          },
-          {
-            "name": "size",
-            "type": "u16"
-          }
-        ]
-      }
+      "name": "approvePositionOperator",
+      "accounts": [
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "operator",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "permissions",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "pool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "tokenX",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenY",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenXReserve",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenYReserve",
-            "type": "publicKey"
-          },
-          {
-            "name": "positionIterator",
-            "type": "u128"
-          },
-          {
-            "name": "tickSpacing",
-            "type": "u16"
-          },
-          {
-            "name": "fee",
-            "type": {
-              "defined": "FixedPoint"
-            }
-          },
-          {
-            "name": "protocolFee",
-            "type": {
-              "defined": "FixedPoint"
-            }
-          },
-          {
-            "name": "liquidity",
-            "type": {
-              "defined": "Liquidity"
-            }
-          },
-          {
-            "name": "sqrtPrice",
-            "type": {
-              "defined": "Price"
-            }
-          },
-          {
-            "name": "currentTickIndex",
-            "type": "i32"
-          },
-          {
-            "name": "tickmap",
-            "type": "publicKey"
-          },
-          {
-            "name": "feeGrowthGlobalX",
-            "type": {
-              "defined": "FeeGrowth"
-            }
-          },
-          {
-            "name": "feeGrowthGlobalY",
-            "type": {
-              "defined": "FeeGrowth"
-            }
-          },
-          {
-            "name": "feeProtocolTokenX",
-            "type": "u64"
-          },
-          {
-            "name": "feeProtocolTokenY",
-            "type": "u64"
-          },
-          {
-            "name": "secondsPerLiquidityGlobal",
-            "type": {
-              "defined": "FixedPoint"
-            }
-          },
-          {
-            "name": "startTimestamp",
-            "type": "u64"
-          },
-          {
-            "name": "lastTimestamp",
-            "type": "u64"
-          },
-          {
-            "name": "feeReceiver",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracleAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracleInitialized",
-            "type": "bool"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "revokePositionOperator",
+      "accounts": [
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "name": "position",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "owner",
-            "type": "publicKey"
-          },
-          {
-            "name": "pool",
-            "type": "publicKey"
-          },
-          {
-            "name": "id",
-            "type": "u128"
-          },
-          {
-            "name": "liquidity",
-            "type": {
-              "defined": "Liquidity"
-            }
-          },
-          {
-            "name": "lowerTickIndex",
+      "name": "claimFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "batchClaimFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimReward",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountReward",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "compoundFee",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "updateSecondsPerLiquidity",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "index",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "initializeReward",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setRewardEmission",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        },
+        {
+          "name": "emissionPerSecond",
+          "type": {
+            "defined": "FixedPoint"
+          }
+        },
+        {
+          "name": "emissionEnd",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawProtocolFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "changeProtocolFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "protocolFee",
+          "type": {
+            "defined": "FixedPoint"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeFeeReceiver",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "feeReceiver",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "feeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracle",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "data",
+            "type": {
+              "array": [
+                {
+                  "defined": "Record"
+                },
+                256
+              ]
+            }
+          },
+          {
+            "name": "head",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u16"
+          },
+          {
+            "name": "size",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenXReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenYReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "positionIterator",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "protocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "currentTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "tickmap",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeGrowthGlobalX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthGlobalY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "secondsPerLiquidityGlobal",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "startTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleInitialized",
+            "type": "bool"
+          },
+          {
+            "name": "rewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "PoolReward"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsLastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "volumeX",
+            "type": "u128"
+          },
+          {
+            "name": "volumeY",
+            "type": "u128"
+          },
+          {
+            "name": "feesX",
+            "type": "u128"
+          },
+          {
+            "name": "feesY",
+            "type": "u128"
+          },
+          {
+            "name": "swapCount",
+            "type": "u64"
+          },
+          {
+            "name": "lastSwapTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "position",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "id",
+            "type": "u128"
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "lowerTickIndex",
             "type": "i32"
           },
           {
@@ -1170,6 +1906,44 @@ export type Invariant = {
               "defined": "FixedPoint"
             }
           },
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "operatorPermissions",
+            "type": "u8"
+          },
+          {
+            "name": "unlockTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "rewardGrowthInside",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsOwed",
+            "type": {
+              "array": [
+                {
+                  "defined": "FixedPoint"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rangeNonce",
+            "type": "u64"
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -1274,6 +2048,17 @@ export type Invariant = {
             "name": "secondsOutside",
             "type": "u64"
           },
+          {
+            "name": "rewardGrowthOutside",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
+            }
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -1365,6 +2150,48 @@ export type Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "PoolReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionPerSecond",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "growthGlobal",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "emissionEnd",
+            "type": "u64"
+          },
+          {
+            "name": "unclaimed",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1484,130 +2311,760 @@ export type Invariant = {
       "msg": "Provided token account is different than expected"
     },
     {
-      "code": 6023,
-      "name": "InvalidAdmin",
-      "msg": "Admin address is different than expected"
+      "code": 6023,
+      "name": "InvalidAdmin",
+      "msg": "Admin address is different than expected"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidAuthority",
+      "msg": "Provided authority is different than expected"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidOwner",
+      "msg": "Provided token owner is different than expected"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidMint",
+      "msg": "Provided token account mint is different than expected mint token"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidTickmap",
+      "msg": "Provided tickmap is different than expected"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTickmapOwner",
+      "msg": "Provided tickmap owner is different than program ID"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidListOwner",
+      "msg": "Recipient list address and owner list address should be different"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidTickSpacing",
+      "msg": "Invalid tick spacing"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidBatchAccounts",
+      "msg": "Remaining accounts should be position, lower tick and upper tick triplets"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidOperatorPermissions",
+      "msg": "Invalid operator permissions"
+    },
+    {
+      "code": 6033,
+      "name": "PositionLocked",
+      "msg": "Position is locked"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidUnlockTimestamp",
+      "msg": "Unlock timestamp can only be extended"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidRewardIndex",
+      "msg": "Invalid reward index"
+    },
+    {
+      "code": 6036,
+      "name": "RewardAlreadyInitialized",
+      "msg": "Reward is already initialized"
+    },
+    {
+      "code": 6037,
+      "name": "SwapStepOverflow",
+      "msg": "Swap step computation overflowed"
+    },
+    {
+      "code": 6038,
+      "name": "PriceOverflow",
+      "msg": "Price computation overflowed"
+    },
+    {
+      "code": 6039,
+      "name": "TokenAmountOverflow",
+      "msg": "Token amount computation overflowed"
+    },
+    {
+      "code": 6040,
+      "name": "FeeOverflow",
+      "msg": "Fee computation overflowed"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidPositionPool",
+      "msg": "Position does not belong to the pool"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidMigration",
+      "msg": "Account can't be migrated"
+    },
+    {
+      "code": 6043,
+      "name": "RewardOverflow",
+      "msg": "Reward computation overflowed"
+    },
+    {
+      "code": 6044,
+      "name": "InsufficientRewardReserve",
+      "msg": "Reward reserve doesn't cover the emission"
+    },
+    {
+      "code": 6045,
+      "name": "RewardsNotClaimed",
+      "msg": "Position has unclaimed rewards"
+    },
+    {
+      "code": 6046,
+      "name": "InsufficientOwedFees",
+      "msg": "Owed fees don't cover the amount"
+    },
+    {
+      "code": 6047,
+      "name": "InsufficientUnclaimedRewards",
+      "msg": "Claim exceeds the unclaimed rewards of the pool"
+    }
+  ]
+};
+
+export const IDL: Invariant = {
+  "version": "0.1.0",
+  "name": "invariant",
+  "instructions": [
+    {
+      "name": "createState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "createFeeTier",
+      "accounts": [
+        {
+          "name": "feeTier",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "fee",
+          "type": "u128"
+        },
+        {
+          "name": "tickSpacing",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createPool",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenYReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initTick",
+          "type": "i32"
+        }
+      ]
     },
     {
-      "code": 6024,
-      "name": "InvalidAuthority",
-      "msg": "Provided authority is different than expected"
+      "name": "createPoolStats",
+      "accounts": [
+        {
+          "name": "poolStats",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6025,
-      "name": "InvalidOwner",
-      "msg": "Provided token owner is different than expected"
+      "name": "swap",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolStats",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "byAmountIn",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        }
+      ]
     },
     {
-      "code": 6026,
-      "name": "InvalidMint",
-      "msg": "Provided token account mint is different than expected mint token"
+      "name": "initializeOracle",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6027,
-      "name": "InvalidTickmap",
-      "msg": "Provided tickmap is different than expected"
+      "name": "createTick",
+      "accounts": [
+        {
+          "name": "tick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "i32"
+        }
+      ]
     },
     {
-      "code": 6028,
-      "name": "InvalidTickmapOwner",
-      "msg": "Provided tickmap owner is different than program ID"
+      "name": "createPositionList",
+      "accounts": [
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6029,
-      "name": "InvalidListOwner",
-      "msg": "Recipient list address and owner list address should be different"
+      "name": "createPosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
+        },
+        {
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
     },
     {
-      "code": 6030,
-      "name": "InvalidTickSpacing",
-      "msg": "Invalid tick spacing"
-    }
-  ]
-};
-
-export const IDL: Invariant = {
-  "version": "0.1.0",
-  "name": "invariant",
-  "instructions": [
-    {
-      "name": "createState",
+      "name": "removePosition",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "removedPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": true,
+          "name": "signer",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "programAuthority",
+          "name": "tokenX",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "accountX",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "nonce",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "createFeeTier",
-      "accounts": [
+        },
         {
-          "name": "feeTier",
+          "name": "accountY",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "state",
-          "isMut": false,
+          "name": "reserveX",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "reserveY",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "fee",
-          "type": "u128"
+          "name": "index",
+          "type": "u32"
         },
         {
-          "name": "tickSpacing",
-          "type": "u16"
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
         }
       ]
     },
     {
-      "name": "createPool",
+      "name": "rebalancePosition",
       "accounts": [
         {
           "name": "state",
@@ -1615,89 +3072,63 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "feeTier",
-          "isMut": false,
+          "name": "pool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "poolStats",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "tickmap",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "oldLowerTick",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenXReserve",
+          "name": "oldUpperTick",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "tokenYReserve",
+          "name": "newLowerTick",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "newUpperTick",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "authority",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "owner",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "signer",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "initTick",
-          "type": "i32"
-        }
-      ]
-    },
-    {
-      "name": "swap",
-      "accounts": [
-        {
-          "name": "state",
+          "name": "tokenX",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tickmap",
-          "isMut": true,
+          "name": "tokenY",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1720,11 +3151,6 @@ export const IDL: Invariant = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
-        },
         {
           "name": "programAuthority",
           "isMut": false,
@@ -1738,25 +3164,78 @@ export const IDL: Invariant = {
       ],
       "args": [
         {
-          "name": "xToY",
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
+        },
+        {
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "swapXToY",
           "type": "bool"
         },
         {
-          "name": "amount",
+          "name": "swapAmount",
           "type": "u64"
         },
         {
-          "name": "byAmountIn",
-          "type": "bool"
+          "name": "swapSqrtPriceLimit",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
+    },
+    {
+      "name": "lockPosition",
+      "accounts": [
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "sqrtPriceLimit",
-          "type": "u128"
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "unlockTimestamp",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "initializeOracle",
+      "name": "migratePool",
       "accounts": [
         {
           "name": "pool",
@@ -1764,30 +3243,36 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "oracle",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenX",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateTick",
+      "accounts": [
+        {
+          "name": "tick",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "pool",
           "isMut": false,
           "isSigner": false
         },
         {
           "name": "payer",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1797,10 +3282,10 @@ export const IDL: Invariant = {
       "args": []
     },
     {
-      "name": "createTick",
+      "name": "migratePosition",
       "accounts": [
         {
-          "name": "tick",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
@@ -1810,8 +3295,13 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "tickmap",
-          "isMut": true,
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1820,12 +3310,48 @@ export const IDL: Invariant = {
           "isSigner": true
         },
         {
-          "name": "tokenX",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transferPositionOwnership",
+      "accounts": [
+        {
+          "name": "ownerList",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "recipientList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "removedPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
           "isMut": false,
           "isSigner": false
         },
@@ -1843,74 +3369,79 @@ export const IDL: Invariant = {
       "args": [
         {
           "name": "index",
-          "type": "i32"
+          "type": "u32"
         }
       ]
     },
     {
-      "name": "createPositionList",
+      "name": "approvePositionOperator",
       "accounts": [
         {
-          "name": "positionList",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "owner",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "signer",
-          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "rent",
+          "name": "operator",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "permissions",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "revokePositionOperator",
+      "accounts": [
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "name": "createPosition",
+      "name": "claimFee",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "position",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "positionList",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
-        },
         {
           "name": "lowerTick",
           "isMut": true,
@@ -1922,10 +3453,15 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "tickmap",
-          "isMut": true,
+          "name": "owner",
+          "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "tokenX",
           "isMut": false,
@@ -1965,19 +3501,13 @@ export const IDL: Invariant = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
         {
           "name": "lowerTickIndex",
           "type": "i32"
@@ -1985,29 +3515,11 @@ export const IDL: Invariant = {
         {
           "name": "upperTickIndex",
           "type": "i32"
-        },
-        {
-          "name": "liquidityDelta",
-          "type": {
-            "defined": "Liquidity"
-          }
-        },
-        {
-          "name": "slippageLimitLower",
-          "type": {
-            "defined": "Price"
-          }
-        },
-        {
-          "name": "slippageLimitUpper",
-          "type": {
-            "defined": "Price"
-          }
         }
       ]
     },
     {
-      "name": "removePosition",
+      "name": "batchClaimFee",
       "accounts": [
         {
           "name": "state",
@@ -2015,27 +3527,78 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "removedPosition",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "positionList",
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lastPosition",
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimReward",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
@@ -2051,7 +3614,12 @@ export const IDL: Invariant = {
         },
         {
           "name": "owner",
-          "isMut": true,
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
           "isSigner": true
         },
         {
@@ -2065,32 +3633,85 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "accountX",
+          "name": "rewardReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountY",
+          "name": "accountReward",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "compoundFee",
+      "accounts": [
+        {
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveY",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "programAuthority",
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         }
@@ -2111,43 +3732,48 @@ export const IDL: Invariant = {
       ]
     },
     {
-      "name": "transferPositionOwnership",
+      "name": "updateSecondsPerLiquidity",
       "accounts": [
         {
-          "name": "ownerList",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "recipientList",
-          "isMut": true,
+          "name": "lowerTick",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "newPosition",
-          "isMut": true,
+          "name": "upperTick",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "removedPosition",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lastPosition",
-          "isMut": true,
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "recipient",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "rent",
           "isMut": false,
@@ -2160,14 +3786,22 @@ export const IDL: Invariant = {
         }
       ],
       "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
         {
           "name": "index",
-          "type": "u32"
+          "type": "i32"
         }
       ]
     },
     {
-      "name": "claimFee",
+      "name": "initializeReward",
       "accounts": [
         {
           "name": "state",
@@ -2180,104 +3814,71 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "position",
-          "isMut": true,
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "lowerTick",
-          "isMut": true,
+          "name": "tokenY",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "upperTick",
-          "isMut": true,
+          "name": "rewardToken",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": false,
+          "name": "rewardReserve",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenY",
+          "name": "rewardAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountX",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "accountY",
+          "name": "admin",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "reserveX",
-          "isMut": true,
+          "name": "programAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveY",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "programAuthority",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "index",
-          "type": "u32"
-        },
-        {
-          "name": "lowerTickIndex",
-          "type": "i32"
-        },
-        {
-          "name": "upperTickIndex",
-          "type": "i32"
+          "name": "rewardIndex",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "updateSecondsPerLiquidity",
+      "name": "setRewardEmission",
       "accounts": [
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "lowerTick",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "upperTick",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "position",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "tokenX",
           "isMut": false,
@@ -2289,38 +3890,30 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "signer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "rent",
+          "name": "rewardReserve",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "lowerTickIndex",
-          "type": "i32"
+          "name": "rewardIndex",
+          "type": "u8"
         },
         {
-          "name": "upperTickIndex",
-          "type": "i32"
+          "name": "emissionPerSecond",
+          "type": {
+            "defined": "FixedPoint"
+          }
         },
         {
-          "name": "index",
-          "type": "i32"
+          "name": "emissionEnd",
+          "type": "u64"
         }
       ]
     },
@@ -2626,6 +4219,61 @@ export const IDL: Invariant = {
             "name": "oracleInitialized",
             "type": "bool"
           },
+          {
+            "name": "rewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "PoolReward"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsLastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "volumeX",
+            "type": "u128"
+          },
+          {
+            "name": "volumeY",
+            "type": "u128"
+          },
+          {
+            "name": "feesX",
+            "type": "u128"
+          },
+          {
+            "name": "feesY",
+            "type": "u128"
+          },
+          {
+            "name": "swapCount",
+            "type": "u64"
+          },
+          {
+            "name": "lastSwapTimestamp",
+            "type": "u64"
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -2698,6 +4346,44 @@ export const IDL: Invariant = {
               "defined": "FixedPoint"
             }
           },
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "operatorPermissions",
+            "type": "u8"
+          },
+          {
+            "name": "unlockTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "rewardGrowthInside",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsOwed",
+            "type": {
+              "array": [
+                {
+                  "defined": "FixedPoint"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rangeNonce",
+            "type": "u64"
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -2802,6 +4488,17 @@ export const IDL: Invariant = {
             "name": "secondsOutside",
             "type": "u64"
           },
+          {
+            "name": "rewardGrowthOutside",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
+            }
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -2893,6 +4590,48 @@ export const IDL: Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "PoolReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionPerSecond",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "growthGlobal",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "emissionEnd",
+            "type": "u64"
+          },
+          {
+            "name": "unclaimed",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -3050,6 +4789,91 @@ export const IDL: Invariant = {
       "code": 6030,
       "name": "InvalidTickSpacing",
       "msg": "Invalid tick spacing"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidBatchAccounts",
+      "msg": "Remaining accounts should be position, lower tick and upper tick triplets"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidOperatorPermissions",
+      "msg": "Invalid operator permissions"
+    },
+    {
+      "code": 6033,
+      "name": "PositionLocked",
+      "msg": "Position is locked"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidUnlockTimestamp",
+      "msg": "Unlock timestamp can only be extended"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidRewardIndex",
+      "msg": "Invalid reward index"
+    },
+    {
+      "code": 6036,
+      "name": "RewardAlreadyInitialized",
+      "msg": "Reward is already initialized"
+    },
+    {
+      "code": 6037,
+      "name": "SwapStepOverflow",
+      "msg": "Swap step computation overflowed"
+    },
+    {
+      "code": 6038,
+      "name": "PriceOverflow",
+      "msg": "Price computation overflowed"
+    },
+    {
+      "code": 6039,
+      "name": "TokenAmountOverflow",
+      "msg": "Token amount computation overflowed"
+    },
+    {
+      "code": 6040,
+      "name": "FeeOverflow",
+      "msg": "Fee computation overflowed"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidPositionPool",
+      "msg": "Position does not belong to the pool"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidMigration",
+      "msg": "Account can't be migrated"
+    },
+    {
+      "code": 6043,
+      "name": "RewardOverflow",
+      "msg": "Reward computation overflowed"
+    },
+    {
+      "code": 6044,
+      "name": "InsufficientRewardReserve",
+      "msg": "Reward reserve doesn't cover the emission"
+    },
+    {
+      "code": 6045,
+      "name": "RewardsNotClaimed",
+      "msg": "Position has unclaimed rewards"
+    },
+    {
+      "code": 6046,
+      "name": "InsufficientOwedFees",
+      "msg": "Owed fees don't cover the amount"
+    },
+    {
+      "code": 6047,
+      "name": "InsufficientUnclaimedRewards",
+      "msg": "Claim exceeds the unclaimed rewards of the pool"
     }
   ]
 };
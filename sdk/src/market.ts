@@ -44,6 +44,7 @@ const POSITION_SEED = 'positionv1'
 const TICK_SEED = 'tickv1'
 const POSITION_LIST_SEED = 'positionlistv1'
 const STATE_SEED = 'statev1'
+const POOL_STATS_SEED = 'poolstatsv1'
 export const TICK_CROSSES_PER_IX = 19
 export const FEE_TIER = 'feetierv1'
 export const DEFAULT_PUBLIC_KEY = new PublicKey(0)
@@ -132,6 +133,7 @@ export class Market {
         systemProgram: SystemProgram.programId
       }
     })
+    const createStatsIx = await this.createPoolStatsInstruction({ pair, payer: payerPubkey })
 
     const transaction = new Transaction({
       feePayer: payerPubkey
@@ -148,6 +150,7 @@ export class Market {
         })
       )
       .add(createIx)
+      .add(createStatsIx)
 
     return {
       transaction,
@@ -181,6 +184,11 @@ export class Market {
     return (await this.program.account.pool.fetch(address)) as PoolStructure
   }
 
+  async getPoolStats(pair: Pair) {
+    const { poolStatsAddress } = await this.getPoolStatsAddress(pair)
+    return (await this.program.account.poolStats.fetch(poolStatsAddress)) as PoolStats
+  }
+
   public async onPoolChange(
     tokenX: PublicKey,
     tokenY: PublicKey,
@@ -473,6 +481,20 @@ export class Market {
     }
   }
 
+  async getPoolStatsAddress(pair: Pair) {
+    const poolAddress = await pair.getAddress(this.program.programId)
+
+    const [poolStatsAddress, poolStatsBump] = await PublicKey.findProgramAddress(
+      [Buffer.from(utils.bytes.utf8.encode(POOL_STATS_SEED)), poolAddress.toBuffer()],
+      this.program.programId
+    )
+
+    return {
+      poolStatsAddress,
+      poolStatsBump
+    }
+  }
+
   async getPositionListAddress(owner: PublicKey) {
     const [positionListAddress, positionListBump] = await PublicKey.findProgramAddress(
       [Buffer.from(utils.bytes.utf8.encode(POSITION_LIST_SEED)), owner.toBuffer()],
@@ -621,6 +643,33 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  // pools created before the stats existed get them through this permissionless instruction
+  async createPoolStatsInstruction({ pair, payer }: CreatePoolStats) {
+    payer = payer ?? this.wallet.publicKey
+    const { poolStatsAddress } = await this.getPoolStatsAddress(pair)
+
+    return this.program.instruction.createPoolStats({
+      accounts: {
+        poolStats: poolStatsAddress,
+        pool: await pair.getAddress(this.program.programId),
+        payer,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async createPoolStatsTransaction(createPoolStats: CreatePoolStats) {
+    const ix = await this.createPoolStatsInstruction(createPoolStats)
+    return new Transaction().add(ix)
+  }
+
+  async createPoolStats(createPoolStats: CreatePoolStats, signer: Keypair) {
+    const tx = await this.createPoolStatsTransaction(createPoolStats)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async createPositionListInstruction(owner?: PublicKey) {
     owner = owner ?? this.wallet.publicKey
     const { positionListAddress } = await this.getPositionListAddress(owner)
@@ -937,10 +986,11 @@ export class Market {
     } = swap
     const owner = swap.owner ?? this.wallet.publicKey
 
-    const [pool, tickmap, poolAddress] = await Promise.all([
+    const [pool, tickmap, poolAddress, { poolStatsAddress }] = await Promise.all([
       this.getPool(pair),
       this.getTickmap(pair),
-      pair.getAddress(this.program.programId)
+      pair.getAddress(this.program.programId),
+      this.getPoolStatsAddress(pair)
     ])
 
     const priceLimit = calculatePriceAfterSlippage(estimatedPriceAfterSwap, slippage, !xToY).v
@@ -987,6 +1037,7 @@ export class Market {
       accounts: {
         state: this.stateAddress,
         pool: poolAddress,
+        poolStats: poolStatsAddress,
         tickmap: pool.tickmap,
         reserveX: pool.tokenXReserve,
         reserveY: pool.tokenYReserve,
@@ -1027,6 +1078,7 @@ export class Market {
   async claimFeeInstruction(claimFee: ClaimFee) {
     const { pair, userTokenX, userTokenY, index } = claimFee
     const owner = claimFee.owner ?? this.wallet.publicKey
+    const signer = claimFee.signer ?? owner
 
     const state = await this.getPool(pair)
     const { positionAddress } = await this.getPositionAddress(owner, index)
@@ -1052,6 +1104,7 @@ export class Market {
           lowerTick: lowerTickAddress,
           upperTick: upperTickAddress,
           owner,
+          signer,
           tokenX: pair.tokenX,
           tokenY: pair.tokenY,
           accountX: userTokenX,
@@ -1076,6 +1129,52 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  async compoundFeeInstruction(compoundFee: CompoundFee) {
+    const { pair, index } = compoundFee
+    const owner = compoundFee.owner ?? this.wallet.publicKey
+    const signer = compoundFee.signer ?? owner
+
+    const { positionAddress } = await this.getPositionAddress(owner, index)
+    const position = await this.getPosition(owner, index)
+    const { tickAddress: lowerTickAddress } = await this.getTickAddress(
+      pair,
+      position.lowerTickIndex
+    )
+    const { tickAddress: upperTickAddress } = await this.getTickAddress(
+      pair,
+      position.upperTickIndex
+    )
+
+    return this.program.instruction.compoundFee(
+      index,
+      position.lowerTickIndex,
+      position.upperTickIndex,
+      {
+        accounts: {
+          pool: await pair.getAddress(this.program.programId),
+          position: positionAddress,
+          lowerTick: lowerTickAddress,
+          upperTick: upperTickAddress,
+          owner,
+          signer,
+          tokenX: pair.tokenX,
+          tokenY: pair.tokenY
+        }
+      }
+    )
+  }
+
+  async compoundFeeTransaction(compoundFee: CompoundFee) {
+    const ix = await this.compoundFeeInstruction(compoundFee)
+    return new Transaction().add(ix)
+  }
+
+  async compoundFee(compoundFee: CompoundFee, signer: Keypair) {
+    const tx = await this.compoundFeeTransaction(compoundFee)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async withdrawProtocolFeeInstruction(withdrawProtocolFee: WithdrawProtocolFee) {
     const { pair, accountX, accountY } = withdrawProtocolFee
     const admin = withdrawProtocolFee.admin ?? this.wallet.publicKey
@@ -1114,6 +1213,7 @@ export class Market {
   async removePositionInstruction(removePosition: RemovePosition): Promise<TransactionInstruction> {
     const { pair, index, userTokenX, userTokenY } = removePosition
     const owner = removePosition.owner ?? this.wallet.publicKey
+    const signer = removePosition.signer ?? owner
 
     const positionList = await this.getPositionList(owner)
     const { positionListAddress } = await this.getPositionListAddress(owner)
@@ -1143,6 +1243,7 @@ export class Market {
         accounts: {
           state: this.stateAddress,
           owner: owner,
+          signer,
           removedPosition: removedPositionAddress,
           positionList: positionListAddress,
           lastPosition: lastPositionAddress,
@@ -1174,6 +1275,214 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  async rebalancePositionInstruction(rebalancePosition: RebalancePosition) {
+    const {
+      pair,
+      index,
+      lowerTick,
+      upperTick,
+      liquidityDelta,
+      knownPrice,
+      slippage,
+      userTokenX,
+      userTokenY,
+      swap
+    } = rebalancePosition
+    const owner = rebalancePosition.owner ?? this.wallet.publicKey
+    const signer = rebalancePosition.signer ?? owner
+
+    const [pool, poolAddress, { poolStatsAddress }, position] = await Promise.all([
+      this.getPool(pair),
+      pair.getAddress(this.program.programId),
+      this.getPoolStatsAddress(pair),
+      this.getPosition(owner, index)
+    ])
+    const { positionAddress } = await this.getPositionAddress(owner, index)
+
+    const slippageLimitLower = calculatePriceAfterSlippage(knownPrice, slippage, false)
+    const slippageLimitUpper = calculatePriceAfterSlippage(knownPrice, slippage, true)
+
+    const [oldLowerTick, oldUpperTick, newLowerTick, newUpperTick] = await Promise.all(
+      [position.lowerTickIndex, position.upperTickIndex, lowerTick, upperTick].map(
+        async tickIndex => (await this.getTickAddress(pair, tickIndex)).tickAddress
+      )
+    )
+
+    // ticks crossed by the optional swap are passed the same way as in swap
+    let remainingAccounts: PublicKey[] = []
+    if (swap !== undefined) {
+      const tickmap = await this.getTickmap(pair)
+      const indexesInDirection = findClosestTicks(
+        tickmap.bitmap,
+        pool.currentTickIndex,
+        pool.tickSpacing,
+        TICK_CROSSES_PER_IX,
+        Infinity,
+        swap.xToY ? 'down' : 'up'
+      )
+      const indexesInReverse = findClosestTicks(
+        tickmap.bitmap,
+        pool.currentTickIndex,
+        pool.tickSpacing,
+        1,
+        Infinity,
+        swap.xToY ? 'up' : 'down'
+      )
+      remainingAccounts = await Promise.all(
+        indexesInDirection.concat(indexesInReverse).map(async tickIndex => {
+          const { tickAddress } = await this.getTickAddress(pair, tickIndex)
+          return tickAddress
+        })
+      )
+    }
+
+    return this.program.instruction.rebalancePosition(
+      index,
+      lowerTick,
+      upperTick,
+      liquidityDelta,
+      slippageLimitLower,
+      slippageLimitUpper,
+      swap?.xToY ?? false,
+      swap?.amount ?? new BN(0),
+      swap?.priceLimit ?? { v: new BN(0) },
+      {
+        remainingAccounts: remainingAccounts.map(pubkey => {
+          return { pubkey, isWritable: true, isSigner: false }
+        }),
+        accounts: {
+          state: this.stateAddress,
+          position: positionAddress,
+          pool: poolAddress,
+          poolStats: poolStatsAddress,
+          tickmap: pool.tickmap,
+          oldLowerTick,
+          oldUpperTick,
+          newLowerTick,
+          newUpperTick,
+          owner,
+          signer,
+          tokenX: pair.tokenX,
+          tokenY: pair.tokenY,
+          accountX: userTokenX,
+          accountY: userTokenY,
+          reserveX: pool.tokenXReserve,
+          reserveY: pool.tokenYReserve,
+          programAuthority: this.programAuthority,
+          tokenProgram: TOKEN_PROGRAM_ID
+        }
+      }
+    )
+  }
+
+  async rebalancePositionTransaction(rebalancePosition: RebalancePosition) {
+    const { pair, lowerTick, upperTick } = rebalancePosition
+    const payer = rebalancePosition.signer ?? rebalancePosition.owner ?? this.wallet.publicKey
+    const tx = new Transaction().add(computeUnitsInstruction(1_400_000, payer))
+
+    // ticks of the new range have to exist before liquidity is moved there
+    for (const index of [lowerTick, upperTick]) {
+      try {
+        await this.getTick(pair, index)
+      } catch (e) {
+        tx.add(await this.createTickInstruction({ pair, index, payer }))
+      }
+    }
+
+    return tx.add(await this.rebalancePositionInstruction(rebalancePosition))
+  }
+
+  async rebalancePosition(rebalancePosition: RebalancePosition, signer: Keypair) {
+    const tx = await this.rebalancePositionTransaction(rebalancePosition)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async migratePoolInstruction(migratePool: MigratePool) {
+    const { pair } = migratePool
+    const payer = migratePool.payer ?? this.wallet.publicKey
+    const poolAddress = await pair.getAddress(this.program.programId)
+
+    return this.program.instruction.migratePool({
+      accounts: {
+        pool: poolAddress,
+        payer,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async migratePoolTransaction(migratePool: MigratePool) {
+    const ix = await this.migratePoolInstruction(migratePool)
+    return new Transaction().add(ix)
+  }
+
+  async migratePool(migratePool: MigratePool, signer: Keypair) {
+    const tx = await this.migratePoolTransaction(migratePool)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async migrateTickInstruction(migrateTick: MigrateTick) {
+    const { pair, index } = migrateTick
+    const payer = migrateTick.payer ?? this.wallet.publicKey
+    const poolAddress = await pair.getAddress(this.program.programId)
+    const { tickAddress } = await this.getTickAddress(pair, index)
+
+    return this.program.instruction.migrateTick({
+      accounts: {
+        tick: tickAddress,
+        pool: poolAddress,
+        payer,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async migrateTickTransaction(migrateTick: MigrateTick) {
+    const ix = await this.migrateTickInstruction(migrateTick)
+    return new Transaction().add(ix)
+  }
+
+  async migrateTick(migrateTick: MigrateTick, signer: Keypair) {
+    const tx = await this.migrateTickTransaction(migrateTick)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  // legacy positions can't be decoded, so their tick indexes have to be passed in
+  async migratePositionInstruction(migratePosition: MigratePosition) {
+    const { pair, index, lowerTickIndex, upperTickIndex } = migratePosition
+    const owner = migratePosition.owner ?? this.wallet.publicKey
+    const payer = migratePosition.payer ?? owner
+    const { positionAddress } = await this.getPositionAddress(owner, index)
+    const poolAddress = await pair.getAddress(this.program.programId)
+    const { tickAddress: lowerTickAddress } = await this.getTickAddress(pair, lowerTickIndex)
+    const { tickAddress: upperTickAddress } = await this.getTickAddress(pair, upperTickIndex)
+
+    return this.program.instruction.migratePosition({
+      accounts: {
+        position: positionAddress,
+        pool: poolAddress,
+        lowerTick: lowerTickAddress,
+        upperTick: upperTickAddress,
+        payer,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async migratePositionTransaction(migratePosition: MigratePosition) {
+    const ix = await this.migratePositionInstruction(migratePosition)
+    return new Transaction().add(ix)
+  }
+
+  async migratePosition(migratePosition: MigratePosition, signer: Keypair) {
+    const tx = await this.migratePositionTransaction(migratePosition)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async transferPositionOwnershipInstruction(
     transferPositionOwnership: TransferPositionOwnership
   ): Promise<TransactionInstruction> {
@@ -1487,6 +1796,17 @@ export interface PoolStructure {
   bump: number
 }
 
+export interface PoolStats {
+  pool: PublicKey
+  volumeX: BN
+  volumeY: BN
+  feesX: BN
+  feesY: BN
+  swapCount: BN
+  lastSwapTimestamp: BN
+  bump: number
+}
+
 export interface PoolData {
   currentTickIndex: number
   tickSpacing: number
@@ -1599,13 +1919,24 @@ export interface CreatePoolTx {
 export interface CreatePool extends CreatePoolTx {
   payer: Keypair
 }
+export interface CreatePoolStats {
+  pair: Pair
+  payer?: PublicKey
+}
 export interface ClaimFee {
   pair: Pair
   owner?: PublicKey
+  signer?: PublicKey
   userTokenX: PublicKey
   userTokenY: PublicKey
   index: number
 }
+export interface CompoundFee {
+  pair: Pair
+  owner?: PublicKey
+  signer?: PublicKey
+  index: number
+}
 export interface Swap {
   pair: Pair
   owner?: PublicKey
@@ -1650,10 +1981,46 @@ export interface WithdrawProtocolFee {
 export interface RemovePosition {
   pair: Pair
   owner?: PublicKey
+  signer?: PublicKey
   index: number
   userTokenX: PublicKey
   userTokenY: PublicKey
 }
+export interface RebalancePosition {
+  pair: Pair
+  owner?: PublicKey
+  signer?: PublicKey
+  index: number
+  lowerTick: number
+  upperTick: number
+  liquidityDelta: Decimal
+  knownPrice: Decimal
+  slippage: Decimal
+  userTokenX: PublicKey
+  userTokenY: PublicKey
+  swap?: {
+    xToY: boolean
+    amount: BN
+    priceLimit: Decimal
+  }
+}
+export interface MigratePool {
+  pair: Pair
+  payer?: PublicKey
+}
+export interface MigrateTick {
+  pair: Pair
+  payer?: PublicKey
+  index: number
+}
+export interface MigratePosition {
+  pair: Pair
+  owner?: PublicKey
+  payer?: PublicKey
+  index: number
+  lowerTickIndex: number
+  upperTickIndex: number
+}
 export interface TransferPositionOwnership {
   owner?: PublicKey
   recipient?: PublicKey
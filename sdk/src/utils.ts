@@ -96,7 +96,8 @@ export enum INVARIANT_ERRORS {
   INVALID_TICKMAP = '0x178b',
   INVALID_TICKMAP_OWNER = '0x178c',
   INVALID_LIST_OWNER = '0x178d',
-  INVALID_TICK_SPACING = '0x178e'
+  INVALID_TICK_SPACING = '0x178e',
+  INVALID_POSITION_POOL = '0x1799'
 }
 
 export interface SimulateSwapPrice {
@@ -2,13 +2,54 @@ export type Staker = {
   "version": "0.1.0",
   "name": "staker",
   "instructions": [
+    {
+      "name": "createIncentiveList",
+      "accounts": [
+        {
+          "name": "incentiveList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createIncentive",
       "accounts": [
         {
           "name": "incentive",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "incentiveList",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "incentiveTokenAccount",
@@ -83,6 +124,153 @@ export type Staker = {
           "type": {
             "defined": "Seconds"
           }
+        },
+        {
+          "name": "minTick",
+          "type": {
+            "option": "i32"
+          }
+        },
+        {
+          "name": "maxTick",
+          "type": {
+            "option": "i32"
+          }
+        }
+      ]
+    },
+    {
+      "name": "addIncentiveReward",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveTokenAccount",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "founderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakerAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u8"
+        },
+        {
+          "name": "reward",
+          "type": {
+            "defined": "TokenAmount"
+          }
+        }
+      ]
+    },
+    {
+      "name": "fundIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "funderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "funder",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amounts",
+          "type": {
+            "vec": {
+              "defined": "TokenAmount"
+            }
+          }
+        },
+        {
+          "name": "endTime",
+          "type": {
+            "option": {
+              "defined": "Seconds"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setBoostTiers",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "boostTiers",
+          "type": {
+            "vec": {
+              "defined": "BoostTier"
+            }
+          }
         }
       ]
     },
@@ -96,7 +284,7 @@ export type Staker = {
         },
         {
           "name": "position",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -104,6 +292,31 @@ export type Staker = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "owner",
           "isMut": false,
@@ -134,6 +347,14 @@ export type Staker = {
         {
           "name": "index",
           "type": "i32"
+        },
+        {
+          "name": "lockDuration",
+          "type": {
+            "option": {
+              "defined": "Seconds"
+            }
+          }
         }
       ]
     },
@@ -157,7 +378,7 @@ export type Staker = {
         },
         {
           "name": "position",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -166,19 +387,64 @@ export type Staker = {
           "isSigner": false
         },
         {
-          "name": "stakerAuthority",
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakerAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "owner",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "invariant",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -239,7 +505,7 @@ export type Staker = {
       ]
     },
     {
-      "name": "removeStake",
+      "name": "cancelIncentive",
       "accounts": [
         {
           "name": "incentive",
@@ -247,15 +513,56 @@ export type Staker = {
           "isSigner": false
         },
         {
-          "name": "userStake",
+          "name": "incentiveTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "founder",
+          "name": "founderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakerAuthority",
           "isMut": false,
-          "isSigner": true
-        }
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeStake",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userStake",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": true
+        }
       ],
       "args": []
     },
@@ -289,6 +596,84 @@ export type Staker = {
           "type": "i32"
         }
       ]
+    },
+    {
+      "name": "closeInvalidatedStake",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userStake",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateUserStake",
+      "accounts": [
+        {
+          "name": "userStake",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     }
   ],
   "accounts": [
@@ -311,6 +696,21 @@ export type Staker = {
               "defined": "TokenAmount"
             }
           },
+          {
+            "name": "extraRewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "IncentiveReward"
+                },
+                2
+              ]
+            }
+          },
+          {
+            "name": "extraRewardsCount",
+            "type": "u8"
+          },
           {
             "name": "totalSecondsClaimed",
             "type": {
@@ -343,9 +743,80 @@ export type Staker = {
             "name": "pool",
             "type": "publicKey"
           },
+          {
+            "name": "minTick",
+            "type": "i32"
+          },
+          {
+            "name": "maxTick",
+            "type": "i32"
+          },
+          {
+            "name": "boostTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": "BoostTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "boostTiersCount",
+            "type": "u8"
+          },
+          {
+            "name": "totalLiquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "totalBoostedLiquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "boostedSecondsPerLiquidity",
+            "type": {
+              "defined": "SecondsPerLiquidity"
+            }
+          },
+          {
+            "name": "lastBoostUpdate",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          },
           {
             "name": "nonce",
             "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "incentiveList",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "head",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
@@ -363,6 +834,18 @@ export type Staker = {
             "name": "position",
             "type": "publicKey"
           },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
           {
             "name": "secondsPerLiquidityInitial",
             "type": {
@@ -375,6 +858,38 @@ export type Staker = {
               "defined": "Liquidity"
             }
           },
+          {
+            "name": "lockUntil",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "boost",
+            "type": {
+              "defined": "Multiplier"
+            }
+          },
+          {
+            "name": "rangeNonce",
+            "type": "u64"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "boostedSecondsPerLiquidityInitial",
+            "type": {
+              "defined": "SecondsPerLiquidity"
+            }
+          },
+          {
+            "name": "lastWithdraw",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -420,6 +935,18 @@ export type Staker = {
         ]
       }
     },
+    {
+      "name": "Multiplier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
+          }
+        ]
+      }
+    },
     {
       "name": "Seconds",
       "type": {
@@ -431,6 +958,44 @@ export type Staker = {
           }
         ]
       }
+    },
+    {
+      "name": "IncentiveReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalRewardUnclaimed",
+            "type": {
+              "defined": "TokenAmount"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BoostTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "multiplier",
+            "type": {
+              "defined": "Multiplier"
+            }
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -548,22 +1113,138 @@ export type Staker = {
       "code": 6022,
       "name": "DifferentIncentivePool",
       "msg": "Incentive pool is different from stake pool"
-    }
-  ]
-};
-
-export const IDL: Staker = {
-  "version": "0.1.0",
-  "name": "staker",
-  "instructions": [
+    },
     {
-      "name": "createIncentive",
-      "accounts": [
-        {
-          "name": "incentive",
+      "code": 6023,
+      "name": "AlreadyStarted",
+      "msg": "Incentive already started"
+    },
+    {
+      "code": 6024,
+      "name": "TooManyRewards",
+      "msg": "Too many reward tokens in incentive"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidRewardAccounts",
+      "msg": "Invalid reward token accounts passed as remaining accounts"
+    },
+    {
+      "code": 6026,
+      "name": "RewardRateDecrease",
+      "msg": "Extension would lower the reward rate for stakers"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidEndTime",
+      "msg": "New end time has to be later than the current one"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTickBand",
+      "msg": "Incentive tick band is invalid"
+    },
+    {
+      "code": 6029,
+      "name": "PositionOutOfBand",
+      "msg": "Position range is outside of incentive tick band"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidBoostTiers",
+      "msg": "Boost tiers have to be sorted and multipliers can't be lower than one"
+    },
+    {
+      "code": 6031,
+      "name": "PositionNotLocked",
+      "msg": "Position has to stay locked in invariant for the whole stake lock"
+    },
+    {
+      "code": 6032,
+      "name": "StakeLocked",
+      "msg": "Stake is still locked"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidPayer",
+      "msg": "Provided payer is different than the one who paid for the stake"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidPosition",
+      "msg": "Provided position is different than the staked one"
+    },
+    {
+      "code": 6035,
+      "name": "StakeStillBacked",
+      "msg": "Stake is still backed by its position"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidMigration",
+      "msg": "Account can't be migrated"
+    },
+    {
+      "code": 6037,
+      "name": "RewardOverflow",
+      "msg": "Reward amount overflowed"
+    }
+  ]
+};
+
+export const IDL: Staker = {
+  "version": "0.1.0",
+  "name": "staker",
+  "instructions": [
+    {
+      "name": "createIncentiveList",
+      "accounts": [
+        {
+          "name": "incentiveList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveList",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "incentiveTokenAccount",
           "isMut": true,
@@ -637,6 +1318,153 @@ export const IDL: Staker = {
           "type": {
             "defined": "Seconds"
           }
+        },
+        {
+          "name": "minTick",
+          "type": {
+            "option": "i32"
+          }
+        },
+        {
+          "name": "maxTick",
+          "type": {
+            "option": "i32"
+          }
+        }
+      ]
+    },
+    {
+      "name": "addIncentiveReward",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveTokenAccount",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "founderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakerAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u8"
+        },
+        {
+          "name": "reward",
+          "type": {
+            "defined": "TokenAmount"
+          }
+        }
+      ]
+    },
+    {
+      "name": "fundIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "funderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "funder",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amounts",
+          "type": {
+            "vec": {
+              "defined": "TokenAmount"
+            }
+          }
+        },
+        {
+          "name": "endTime",
+          "type": {
+            "option": {
+              "defined": "Seconds"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setBoostTiers",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founder",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "boostTiers",
+          "type": {
+            "vec": {
+              "defined": "BoostTier"
+            }
+          }
         }
       ]
     },
@@ -650,7 +1478,7 @@ export const IDL: Staker = {
         },
         {
           "name": "position",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -658,6 +1486,31 @@ export const IDL: Staker = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "owner",
           "isMut": false,
@@ -688,6 +1541,14 @@ export const IDL: Staker = {
         {
           "name": "index",
           "type": "i32"
+        },
+        {
+          "name": "lockDuration",
+          "type": {
+            "option": {
+              "defined": "Seconds"
+            }
+          }
         }
       ]
     },
@@ -711,12 +1572,107 @@ export const IDL: Staker = {
         },
         {
           "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakerAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invariant",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ownerTokenAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "i32"
+        },
+        {
+          "name": "nonce",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "endIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "founderTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentiveToken",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -725,9 +1681,9 @@ export const IDL: Staker = {
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
+          "name": "founder",
+          "isMut": false,
+          "isSigner": true
         },
         {
           "name": "tokenProgram",
@@ -736,10 +1692,6 @@ export const IDL: Staker = {
         }
       ],
       "args": [
-        {
-          "name": "index",
-          "type": "i32"
-        },
         {
           "name": "nonce",
           "type": "u8"
@@ -747,7 +1699,7 @@ export const IDL: Staker = {
       ]
     },
     {
-      "name": "endIncentive",
+      "name": "cancelIncentive",
       "accounts": [
         {
           "name": "incentive",
@@ -764,11 +1716,6 @@ export const IDL: Staker = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "incentiveToken",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "stakerAuthority",
           "isMut": false,
@@ -843,6 +1790,84 @@ export const IDL: Staker = {
           "type": "i32"
         }
       ]
+    },
+    {
+      "name": "closeInvalidatedStake",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userStake",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateIncentive",
+      "accounts": [
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateUserStake",
+      "accounts": [
+        {
+          "name": "userStake",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "incentive",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     }
   ],
   "accounts": [
@@ -865,6 +1890,21 @@ export const IDL: Staker = {
               "defined": "TokenAmount"
             }
           },
+          {
+            "name": "extraRewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "IncentiveReward"
+                },
+                2
+              ]
+            }
+          },
+          {
+            "name": "extraRewardsCount",
+            "type": "u8"
+          },
           {
             "name": "totalSecondsClaimed",
             "type": {
@@ -897,9 +1937,80 @@ export const IDL: Staker = {
             "name": "pool",
             "type": "publicKey"
           },
+          {
+            "name": "minTick",
+            "type": "i32"
+          },
+          {
+            "name": "maxTick",
+            "type": "i32"
+          },
+          {
+            "name": "boostTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": "BoostTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "boostTiersCount",
+            "type": "u8"
+          },
+          {
+            "name": "totalLiquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "totalBoostedLiquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "boostedSecondsPerLiquidity",
+            "type": {
+              "defined": "SecondsPerLiquidity"
+            }
+          },
+          {
+            "name": "lastBoostUpdate",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          },
           {
             "name": "nonce",
             "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "incentiveList",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "head",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
@@ -917,6 +2028,18 @@ export const IDL: Staker = {
             "name": "position",
             "type": "publicKey"
           },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
           {
             "name": "secondsPerLiquidityInitial",
             "type": {
@@ -929,6 +2052,38 @@ export const IDL: Staker = {
               "defined": "Liquidity"
             }
           },
+          {
+            "name": "lockUntil",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "boost",
+            "type": {
+              "defined": "Multiplier"
+            }
+          },
+          {
+            "name": "rangeNonce",
+            "type": "u64"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "boostedSecondsPerLiquidityInitial",
+            "type": {
+              "defined": "SecondsPerLiquidity"
+            }
+          },
+          {
+            "name": "lastWithdraw",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -974,6 +2129,18 @@ export const IDL: Staker = {
         ]
       }
     },
+    {
+      "name": "Multiplier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
+          }
+        ]
+      }
+    },
     {
       "name": "Seconds",
       "type": {
@@ -985,6 +2152,44 @@ export const IDL: Staker = {
           }
         ]
       }
+    },
+    {
+      "name": "IncentiveReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalRewardUnclaimed",
+            "type": {
+              "defined": "TokenAmount"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BoostTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": {
+              "defined": "Seconds"
+            }
+          },
+          {
+            "name": "multiplier",
+            "type": {
+              "defined": "Multiplier"
+            }
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1102,6 +2307,81 @@ export const IDL: Staker = {
       "code": 6022,
       "name": "DifferentIncentivePool",
       "msg": "Incentive pool is different from stake pool"
+    },
+    {
+      "code": 6023,
+      "name": "AlreadyStarted",
+      "msg": "Incentive already started"
+    },
+    {
+      "code": 6024,
+      "name": "TooManyRewards",
+      "msg": "Too many reward tokens in incentive"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidRewardAccounts",
+      "msg": "Invalid reward token accounts passed as remaining accounts"
+    },
+    {
+      "code": 6026,
+      "name": "RewardRateDecrease",
+      "msg": "Extension would lower the reward rate for stakers"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidEndTime",
+      "msg": "New end time has to be later than the current one"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTickBand",
+      "msg": "Incentive tick band is invalid"
+    },
+    {
+      "code": 6029,
+      "name": "PositionOutOfBand",
+      "msg": "Position range is outside of incentive tick band"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidBoostTiers",
+      "msg": "Boost tiers have to be sorted and multipliers can't be lower than one"
+    },
+    {
+      "code": 6031,
+      "name": "PositionNotLocked",
+      "msg": "Position has to stay locked in invariant for the whole stake lock"
+    },
+    {
+      "code": 6032,
+      "name": "StakeLocked",
+      "msg": "Stake is still locked"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidPayer",
+      "msg": "Provided payer is different than the one who paid for the stake"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidPosition",
+      "msg": "Provided position is different than the staked one"
+    },
+    {
+      "code": 6035,
+      "name": "StakeStillBacked",
+      "msg": "Stake is still backed by its position"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidMigration",
+      "msg": "Account can't be migrated"
+    },
+    {
+      "code": 6037,
+      "name": "RewardOverflow",
+      "msg": "Reward amount overflowed"
     }
   ]
 };
@@ -14,7 +14,7 @@ import {
   Keypair,
   sendAndConfirmRawTransaction
 } from '@solana/web3.js'
-import { STAKER_SEED } from './utils'
+import { INCENTIVE_LIST_SEED, INCENTIVE_SEED, STAKER_SEED } from './utils'
 import { bs58 } from '@project-serum/anchor/dist/cjs/utils/bytes'
 
 export class Staker {
@@ -55,18 +55,32 @@ export class Staker {
   }
 
   // frontend methods
+  public async createIncentiveList(pool: PublicKey, founder: PublicKey) {
+    const createListIx = await this.createIncentiveListIx(pool, founder)
+    const tx = new Transaction().add(createListIx)
+    const stringTx = await this.signAndSend(tx)
+
+    return stringTx
+  }
+
+  // creates the incentive list as well for the first incentive of the founder in the pool
   public async createIncentive(createIncentive: CreateIncentive) {
-    const incentiveAccount = Keypair.generate()
+    const { pool, founder } = createIncentive
     const incentiveTokenAccount = Keypair.generate()
-    const incentive = incentiveAccount.publicKey
+    const incentive = await this.getNextIncentiveAddress(pool, founder)
     const incentiveToken = incentiveTokenAccount.publicKey
     const createIx = await this.createIncentiveIx(
       createIncentive,
-      incentiveAccount.publicKey,
+      incentive,
       incentiveTokenAccount.publicKey
     )
-    const tx = new Transaction().add(createIx)
-    const stringTx = await this.signAndSend(tx, [incentiveAccount, incentiveTokenAccount])
+    const tx = new Transaction()
+    const incentiveList = await this.getIncentiveListAddress(pool, founder)
+    if ((await this.connection.getAccountInfo(incentiveList)) === null) {
+      tx.add(await this.createIncentiveListIx(pool, founder))
+    }
+    tx.add(createIx)
+    const stringTx = await this.signAndSend(tx, [incentiveTokenAccount])
 
     return { stringTx, incentive, incentiveToken }
   }
@@ -76,9 +90,8 @@ export class Staker {
     update: UpdateSecondsPerLiquidity,
     createStake: CreateStake
   ) {
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await this.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await this.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
     const stringTx = await this.signAndSend(tx)
     const [stake] = await this.getUserStakeAddressAndBump(
       createStake.incentive,
@@ -90,9 +103,8 @@ export class Staker {
   }
 
   public async withdraw(market: Market, update: UpdateSecondsPerLiquidity, withdraw: Withdraw) {
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const withdrawIx = await this.withdrawIx(withdraw)
-    const tx = new Transaction().add(updateIx).add(withdrawIx)
+    const withdrawIx = await this.withdrawIx(market, update, withdraw)
+    const tx = new Transaction().add(withdrawIx)
     const stringTx = await this.signAndSend(tx)
 
     return stringTx
@@ -133,6 +145,16 @@ export class Staker {
     return stringTx
   }
 
+  public async closeInvalidatedStake(incentive: PublicKey, pool: PublicKey, id: BN) {
+    const [userStakeAddress] = await this.getUserStakeAddressAndBump(incentive, pool, id)
+
+    const closeIx = await this.closeInvalidatedStakeIx(userStakeAddress)
+    const tx = new Transaction().add(closeIx)
+    const stringTx = await this.signAndSend(tx)
+
+    return stringTx
+  }
+
   public async removeAllStakes(incentive: PublicKey, founder: PublicKey) {
     const stakes = await this.getAllIncentiveStakes(incentive)
     let tx = new Transaction()
@@ -152,8 +174,41 @@ export class Staker {
     return await this.signAndSendAll(txs)
   }
 
+  public async migrateIncentive(incentive: PublicKey, payer?: PublicKey) {
+    const migrateIx = await this.migrateIncentiveIx(incentive, payer)
+    const tx = new Transaction().add(migrateIx)
+    const stringTx = await this.signAndSend(tx)
+
+    return stringTx
+  }
+
+  public async migrateUserStake(migrateUserStake: MigrateUserStake) {
+    const migrateIx = await this.migrateUserStakeIx(migrateUserStake)
+    const tx = new Transaction().add(migrateIx)
+    const stringTx = await this.signAndSend(tx)
+
+    return stringTx
+  }
+
   // instructions
 
+  // anyone can create the list, it has to exist before the first incentive of the founder in the pool
+  public async createIncentiveListIx(pool: PublicKey, founder: PublicKey, payer?: PublicKey) {
+    const incentiveList = await this.getIncentiveListAddress(pool, founder)
+
+    return this.program.instruction.createIncentiveList({
+      accounts: {
+        incentiveList,
+        pool,
+        founder,
+        payer: payer ?? this.wallet.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  // incentive has to be the next address of the founder in the pool, see getNextIncentiveAddress
   public async createIncentiveIx(
     {
       reward,
@@ -163,19 +218,26 @@ export class Staker {
       pool,
       incentiveToken,
       founderTokenAccount,
-      invariant
+      invariant,
+      minTick,
+      maxTick
     }: CreateIncentive,
     incentive: PublicKey,
     incentiveTokenAccount: PublicKey
   ) {
+    const incentiveList = await this.getIncentiveListAddress(pool, founder)
+
     return this.program.instruction.createIncentive(
       this.programAuthority.nonce,
       reward,
       startTime,
       endTime,
+      minTick ?? null,
+      maxTick ?? null,
       {
         accounts: {
           incentive: incentive,
+          incentiveList,
           pool,
           incentiveTokenAccount,
           incentiveToken,
@@ -191,54 +253,76 @@ export class Staker {
     )
   }
 
-  public async createStakeIx({
-    pool,
-    id,
-    position,
-    incentive,
-    owner,
-    signer,
-    index,
-    invariant
-  }: CreateStake) {
+  // seconds per liquidity of the position are refreshed by the staker itself
+  public async createStakeIx(
+    market: Market,
+    update: UpdateSecondsPerLiquidity,
+    { pool, id, position, incentive, owner, signer, index, invariant, lockDuration }: CreateStake
+  ) {
     const [userStakeAddress] = await this.getUserStakeAddressAndBump(incentive, pool, id)
+    const { pair, lowerTickIndex, upperTickIndex } = update
+    const { tickAddress: lowerTick } = await market.getTickAddress(pair, lowerTickIndex)
+    const { tickAddress: upperTick } = await market.getTickAddress(pair, upperTickIndex)
 
-    return this.program.instruction.stake(index, {
+    return this.program.instruction.stake(index, lockDuration ?? null, {
       accounts: {
         userStake: userStakeAddress,
         position,
         incentive,
+        pool,
+        lowerTick,
+        upperTick,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
         owner,
         signer: signer ?? owner,
-        systemProgram: SystemProgram.programId,
         invariant,
+        systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY
       }
     })
   }
 
-  public async withdrawIx({
-    incentive,
-    pool,
-    id,
-    incentiveTokenAccount,
-    ownerTokenAcc,
-    position,
-    owner,
-    index
-  }: Withdraw) {
+  public async withdrawIx(
+    market: Market,
+    update: UpdateSecondsPerLiquidity,
+    {
+      incentive,
+      pool,
+      id,
+      incentiveTokenAccount,
+      ownerTokenAcc,
+      position,
+      owner,
+      payer,
+      index,
+      invariant
+    }: Withdraw
+  ) {
     const [userStakeAddress] = await this.getUserStakeAddressAndBump(incentive, pool, id)
+    const { pair, lowerTickIndex, upperTickIndex } = update
+    const { tickAddress: lowerTick } = await market.getTickAddress(pair, lowerTickIndex)
+    const { tickAddress: upperTick } = await market.getTickAddress(pair, upperTickIndex)
 
     return this.program.instruction.withdraw(index, this.programAuthority.nonce, {
       accounts: {
         userStake: userStakeAddress,
         incentive,
         incentiveTokenAccount: incentiveTokenAccount,
-        ownerTokenAccount: ownerTokenAcc,
         position,
+        ownerTokenAccount: ownerTokenAcc,
+        pool,
+        lowerTick,
+        upperTick,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
         stakerAuthority: this.programAuthority.authority,
         owner,
-        tokenProgram: TOKEN_PROGRAM_ID
+        payer: payer ?? owner,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        invariant,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY
       }
     })
   }
@@ -290,6 +374,44 @@ export class Staker {
     })
   }
 
+  // anyone can close a stake whose position was removed, transferred or re-ranged
+  public async closeInvalidatedStakeIx(userStake: PublicKey) {
+    const stake = await this.program.account.userStake.fetch(userStake)
+
+    return this.program.instruction.closeInvalidatedStake({
+      accounts: {
+        incentive: stake.incentive,
+        userStake,
+        position: stake.position,
+        payer: stake.payer
+      }
+    })
+  }
+
+  public async migrateIncentiveIx(incentive: PublicKey, payer?: PublicKey) {
+    return this.program.instruction.migrateIncentive({
+      accounts: {
+        incentive,
+        payer: payer ?? this.wallet.publicKey,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  // legacy stakes can't be decoded, so the staked position has to be passed in,
+  // the incentive and the position in invariant have to be migrated first
+  public async migrateUserStakeIx({ userStake, incentive, position, payer }: MigrateUserStake) {
+    return this.program.instruction.migrateUserStake({
+      accounts: {
+        userStake,
+        incentive,
+        position,
+        payer: payer ?? this.wallet.publicKey,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
   // getters
   async getProgramAuthority() {
     const [authority, nonce] = await PublicKey.findProgramAddress(
@@ -303,6 +425,52 @@ export class Staker {
     }
   }
 
+  public async getIncentiveListAddress(pool: PublicKey, founder: PublicKey) {
+    const [incentiveList] = await PublicKey.findProgramAddress(
+      [Buffer.from(INCENTIVE_LIST_SEED), pool.toBuffer(), founder.toBuffer()],
+      this.programId
+    )
+    return incentiveList
+  }
+
+  public async getIncentiveAddress(pool: PublicKey, founder: PublicKey, index: number) {
+    const indexBuf = Buffer.alloc(4)
+    indexBuf.writeUInt32LE(index)
+    const [incentive] = await PublicKey.findProgramAddress(
+      [Buffer.from(INCENTIVE_SEED), pool.toBuffer(), founder.toBuffer(), indexBuf],
+      this.programId
+    )
+    return incentive
+  }
+
+  public async getIncentiveListHead(pool: PublicKey, founder: PublicKey) {
+    const incentiveList = await this.getIncentiveListAddress(pool, founder)
+    const listAccount = await this.connection.getAccountInfo(incentiveList)
+    return listAccount === null
+      ? 0
+      : (await this.program.account.incentiveList.fetch(incentiveList)).head
+  }
+
+  // address the next incentive of the founder in the pool will be created at
+  public async getNextIncentiveAddress(pool: PublicKey, founder: PublicKey) {
+    return await this.getIncentiveAddress(
+      pool,
+      founder,
+      await this.getIncentiveListHead(pool, founder)
+    )
+  }
+
+  // incentives of the founder in the pool are indexed from 0 to the head of their list
+  public async getIncentiveAddresses(pool: PublicKey, founder: PublicKey) {
+    const head = await this.getIncentiveListHead(pool, founder)
+    return await Promise.all(
+      Array.from(
+        { length: head },
+        async (_, index) => await this.getIncentiveAddress(pool, founder, index)
+      )
+    )
+  }
+
   public async getIncentive(incentivePubKey: PublicKey) {
     return (await this.program.account.incentive.fetch(incentivePubKey)) as IncentiveStructure
   }
@@ -393,6 +561,8 @@ export interface CreateIncentive {
   incentiveToken: PublicKey
   founderTokenAccount: PublicKey
   invariant: PublicKey
+  minTick?: number
+  maxTick?: number
 }
 export interface CreateStake {
   pool: PublicKey
@@ -403,6 +573,7 @@ export interface CreateStake {
   signer?: PublicKey
   index: number
   invariant: PublicKey
+  lockDuration?: Decimal
 }
 export interface Stake {
   incentive: PublicKey
@@ -419,7 +590,9 @@ export interface Withdraw {
   ownerTokenAcc: PublicKey
   position: PublicKey
   owner: PublicKey
+  payer?: PublicKey // paid for the stake, defaults to owner
   index: number
+  invariant: PublicKey
 }
 
 export interface EndIncentive {
@@ -439,6 +612,13 @@ export interface CloseStake {
   index: number
 }
 
+export interface MigrateUserStake {
+  userStake: PublicKey
+  incentive: PublicKey
+  position: PublicKey
+  payer?: PublicKey
+}
+
 export interface IncentiveStructure {
   founder: PublicKey
   tokenAccount: PublicKey
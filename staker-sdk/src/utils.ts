@@ -26,6 +26,8 @@ export const toDecimal = (x: number, decimals: number = 0): Decimal => {
 }
 
 export const STAKER_SEED = 'staker'
+export const INCENTIVE_SEED = 'incentivev1'
+export const INCENTIVE_LIST_SEED = 'incentivelistv1'
 
 export const fromInteger = (integer: number): { v: BN } => {
   return { v: new BN(integer).mul(DENOMINATOR) }
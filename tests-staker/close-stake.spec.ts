@@ -26,7 +26,7 @@ describe('Remove Stake tests', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const incentiveAccount = Keypair.generate()
+  let incentiveAccount: PublicKey
   const founderAccount = Keypair.generate()
   const positionOwner = Keypair.generate()
   const admin = Keypair.generate()
@@ -48,8 +48,7 @@ describe('Remove Stake tests', () => {
 
     await Promise.all([
       await connection.requestAirdrop(mintAuthority.publicKey, 1e9),
-      await connection.requestAirdrop(positionOwner.publicKey, 1e9),
-      await connection.requestAirdrop(incentiveAccount.publicKey, 10e9)
+      await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     ])
 
     // create token
@@ -105,6 +104,7 @@ describe('Remove Stake tests', () => {
     await market.createPool(createPoolVars)
 
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
     invariant = anchor.workspace.Invariant.programId
   })
 
@@ -127,19 +127,16 @@ describe('Remove Stake tests', () => {
       invariant
     }
 
+    incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      tx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection)
 
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
@@ -193,40 +190,39 @@ describe('Remove Stake tests', () => {
       id: positionId,
       index,
       position,
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       owner: positionOwner.publicKey,
       signer: positionOwner.publicKey,
       invariant
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const stakeTx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const stakeTx = new Transaction().add(stakeIx)
 
     await signAndSend(stakeTx, [positionOwner], staker.connection)
 
-    const stake = await staker.getStake(incentiveAccount.publicKey, poolAddress, positionId)
+    const stake = await staker.getStake(incentiveAccount, poolAddress, positionId)
     const positionStructAfter = await market.getPosition(positionOwner.publicKey, index)
     const liquidity: Decimal = { v: new BN(liquidityDelta.v) }
 
-    assert.ok(stake.incentive.equals(incentiveAccount.publicKey))
+    assert.ok(stake.incentive.equals(incentiveAccount))
     assert.ok(
       eqDecimal(stake.secondsPerLiquidityInitial, positionStructAfter.secondsPerLiquidityInside)
     )
     assert.ok(eqDecimal(stake.liquidity, liquidity))
 
-    const incentiveBefore = await staker.getIncentive(incentiveAccount.publicKey)
+    const incentiveBefore = await staker.getIncentive(incentiveAccount)
     assert.ok(incentiveBefore.numOfStakes.eq(new BN('1')))
 
     const [userStakeAddress] = await staker.getUserStakeAddressAndBump(
-      incentiveAccount.publicKey,
+      incentiveAccount,
       pool,
       positionId
     )
 
     const closeStakeIx = await staker.closeStakeByOwnerIx(
       userStakeAddress,
-      incentiveAccount.publicKey,
+      incentiveAccount,
       position,
       positionOwner.publicKey,
       index
@@ -234,7 +230,7 @@ describe('Remove Stake tests', () => {
     const closeTx = new Transaction().add(closeStakeIx)
     await signAndSend(closeTx, [positionOwner], staker.connection)
 
-    const incentiveAfter = await staker.getIncentive(incentiveAccount.publicKey)
+    const incentiveAfter = await staker.getIncentive(incentiveAccount)
     assert.ok(incentiveAfter.numOfStakes.eq(new BN('0')))
   })
 })
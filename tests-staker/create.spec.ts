@@ -76,10 +76,10 @@ describe('Create incentive tests', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
   })
 
   it('Create incentive ', async () => {
-    const incentiveAccount = Keypair.generate()
     const seconds = new Date().valueOf() / 1000
     const currentTime = new BN(Math.floor(seconds))
     const reward: Decimal = { v: new BN(10) }
@@ -98,20 +98,17 @@ describe('Create incentive tests', () => {
       invariant
     }
 
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
-    await signAndSend(
-      tx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection)
 
-    const createdIncentive = await staker.getIncentive(incentiveAccount.publicKey)
+    const createdIncentive = await staker.getIncentive(incentiveAccount)
     assert.ok(createdIncentive.totalRewardUnclaimed.v.eq(reward.v))
     assert.ok(eqDecimal(createdIncentive.totalSecondsClaimed, totalSecondsClaimed))
     assert.ok(createdIncentive.startTime.v.eq(startTime.v))
@@ -120,7 +117,6 @@ describe('Create incentive tests', () => {
   })
 
   it('Fail on zero amount', async () => {
-    const incentiveAccount = Keypair.generate()
     const seconds = new Date().valueOf() / 1000
     const currentTime = new BN(Math.floor(seconds))
     const reward: Decimal = { v: new BN(0) }
@@ -138,22 +134,22 @@ describe('Create incentive tests', () => {
       invariant
     }
 
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
     await assertThrowsAsync(
-      signAndSend(tx, [founderAccount, incentiveAccount, incentiveTokenAccount], staker.connection),
+      signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection),
       ERRORS_STAKER.ZERO_AMOUNT
     )
   })
 
   it('Fail, incentive starts more than one hour in past ', async () => {
-    const incentiveAccount = Keypair.generate()
     const seconds = new Date().valueOf() / 1000
     const currentTime = new BN(Math.floor(seconds))
     const reward: Decimal = { v: new BN(1000) }
@@ -170,22 +166,22 @@ describe('Create incentive tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
     await assertThrowsAsync(
-      signAndSend(tx, [founderAccount, incentiveAccount, incentiveTokenAccount], staker.connection),
+      signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection),
       ERRORS_STAKER.START_IN_PAST
     )
   })
 
   it('Fail, too long incentive time', async () => {
-    const incentiveAccount = Keypair.generate()
     const seconds = new Date().valueOf() / 1000
     const currentTime = new BN(Math.floor(seconds))
     const reward: Decimal = { v: new BN(1000) }
@@ -203,21 +199,21 @@ describe('Create incentive tests', () => {
       invariant
     }
 
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
     await assertThrowsAsync(
-      signAndSend(tx, [founderAccount, incentiveAccount, incentiveTokenAccount], staker.connection),
+      signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection),
       ERRORS_STAKER.TO_LONG_DURATION
     )
   })
   it('Check if amount on incentive token account after donate is correct', async () => {
-    const incentiveAccount = Keypair.generate()
     const seconds = new Date().valueOf() / 1000
     const currentTime = new BN(Math.floor(seconds))
     const reward: Decimal = { v: new BN(1000) }
@@ -235,19 +231,16 @@ describe('Create incentive tests', () => {
       invariant
     }
 
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const tx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      tx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(tx, [founderAccount, incentiveTokenAccount], staker.connection)
 
     const balance = (await incentiveToken.getAccountInfo(incentiveTokenAccount.publicKey)).amount
     assert.ok(balance.eq(new BN(reward.v)))
@@ -77,12 +77,11 @@ describe('End incentive tests', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
   })
 
   it('End incentive ', async () => {
-    const incentiveAccount = Keypair.generate()
 
-    await connection.requestAirdrop(incentiveAccount.publicKey, 10e9)
     await new Promise(resolve => {
       setTimeout(() => {
         resolve(null)
@@ -108,22 +107,19 @@ describe('End incentive tests', () => {
       invariant
     }
 
+    const incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createIx = await staker.createIncentiveIx(
       createIncentiveVars,
-      incentiveAccount.publicKey,
+      incentiveAccount,
       incentiveTokenAccount.publicKey
     )
     const createTx = new Transaction().add(createIx)
-    await signAndSend(
-      createTx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
 
     await sleep(18000)
 
     const endIncentive: EndIncentive = {
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       incentiveToken: incentiveToken.publicKey,
       founderTokenAccount: founderTokenAccount,
@@ -137,6 +133,6 @@ describe('End incentive tests', () => {
     assert.ok(balanceAfter.eq(balanceBefore))
 
     // check if incentive account exist, should not
-    await assertThrowsAsync(staker.getIncentive(incentiveAccount.publicKey))
+    await assertThrowsAsync(staker.getIncentive(incentiveAccount))
   })
 })
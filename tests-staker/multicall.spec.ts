@@ -20,9 +20,9 @@ describe('Multicall test', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const firstIncentiveAccount = Keypair.generate()
+  let firstIncentiveAccount: PublicKey
   const firstPositionOwner = Keypair.generate()
-  const secondIncentiveAccount = Keypair.generate()
+  let secondIncentiveAccount: PublicKey
   const secondPositionOwner = Keypair.generate()
   const firstFounderAccount = Keypair.generate()
   const secondFounderAccount = Keypair.generate()
@@ -58,8 +58,6 @@ describe('Multicall test', () => {
       connection.requestAirdrop(mintAuthority.publicKey, 1e9),
       connection.requestAirdrop(firstPositionOwner.publicKey, 1e9),
       connection.requestAirdrop(secondPositionOwner.publicKey, 1e9),
-      connection.requestAirdrop(firstIncentiveAccount.publicKey, 1e9),
-      connection.requestAirdrop(secondIncentiveAccount.publicKey, 1e9),
       connection.requestAirdrop(firstFounderAccount.publicKey, 1e9),
       connection.requestAirdrop(secondFounderAccount.publicKey, 1e9)
     ])
@@ -111,6 +109,8 @@ describe('Multicall test', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, firstFounderAccount.publicKey)
+    await staker.createIncentiveList(pool, secondFounderAccount.publicKey)
   })
 
   it('Multicall', async () => {
@@ -131,22 +131,18 @@ describe('Multicall test', () => {
       founderTokenAccount: firstFounderTokenAccount,
       invariant
     }
+    firstIncentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const firstIncentiveTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        firstIncentiveAccount.publicKey,
+        firstIncentiveAccount,
         firstIncentiveTokenAccount.publicKey
       )
     )
 
     await signAndSend(
       firstIncentiveTx,
-      [
-        firstFounderAccount,
-        firstIncentiveAccount,
-        firstIncentiveTokenAccount,
-        firstIncentiveTokenAccount
-      ],
+      [firstFounderAccount, firstIncentiveTokenAccount, firstIncentiveTokenAccount],
       staker.connection
     )
 
@@ -160,22 +156,21 @@ describe('Multicall test', () => {
       founderTokenAccount: secondFounderTokenAccount,
       invariant
     }
+    secondIncentiveAccount = await staker.getNextIncentiveAddress(
+      pool,
+      createIncentiveVars2.founder
+    )
     const secondIncentiveTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars2,
-        secondIncentiveAccount.publicKey,
+        secondIncentiveAccount,
         secondIncentiveTokenAccount.publicKey
       )
     )
 
     await signAndSend(
       secondIncentiveTx,
-      [
-        secondFounderAccount,
-        secondIncentiveAccount,
-        secondIncentiveTokenAccount,
-        secondIncentiveTokenAccount
-      ],
+      [secondFounderAccount, secondIncentiveTokenAccount, secondIncentiveTokenAccount],
       staker.connection
     )
     // create first position
@@ -256,7 +251,6 @@ describe('Multicall test', () => {
       upperTickIndex: firstUpperTick,
       index
     }
-    const firstUpdateIx = await market.updateSecondsPerLiquidityInstruction(firstUpdate)
     const secondUpdate: UpdateSecondsPerLiquidity = {
       pair,
       owner: secondPositionOwner.publicKey,
@@ -264,7 +258,6 @@ describe('Multicall test', () => {
       upperTickIndex: secondUpperTick,
       index
     }
-    const secondUpdateIx = await market.updateSecondsPerLiquidityInstruction(secondUpdate)
 
     const firstPositionStructBefore = await market.getPosition(firstPositionOwner.publicKey, index)
     const firstPositionId = firstPositionStructBefore.id
@@ -280,14 +273,14 @@ describe('Multicall test', () => {
       id: firstPositionId,
       index,
       position: firstPosition,
-      incentive: firstIncentiveAccount.publicKey,
+      incentive: firstIncentiveAccount,
       owner: firstPositionOwner.publicKey,
       signer: firstPositionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const firstStakeIx = await staker.createStakeIx(firstCreateStake)
-    const firstTx = new Transaction().add(firstUpdateIx).add(firstStakeIx)
+    const firstStakeIx = await staker.createStakeIx(market, firstUpdate, firstCreateStake)
+    const firstTx = new Transaction().add(firstStakeIx)
     await signAndSend(firstTx, [firstPositionOwner], staker.connection)
 
     // stake second position on first incentive, second case
@@ -296,14 +289,14 @@ describe('Multicall test', () => {
       id: secondPositionId,
       index,
       position: secondPosition,
-      incentive: firstIncentiveAccount.publicKey,
+      incentive: firstIncentiveAccount,
       owner: secondPositionOwner.publicKey,
       signer: secondPositionOwner.publicKey,
       invariant
     }
 
-    const secondStakeIx = await staker.createStakeIx(secondCreateStake)
-    const secondTx = new Transaction().add(secondUpdateIx).add(secondStakeIx)
+    const secondStakeIx = await staker.createStakeIx(market, secondUpdate, secondCreateStake)
+    const secondTx = new Transaction().add(secondStakeIx)
     await signAndSend(secondTx, [secondPositionOwner], staker.connection)
 
     // stake first position on second incentive, third case
@@ -312,14 +305,14 @@ describe('Multicall test', () => {
       id: firstPositionId,
       index,
       position: firstPosition,
-      incentive: secondIncentiveAccount.publicKey,
+      incentive: secondIncentiveAccount,
       owner: firstPositionOwner.publicKey,
       signer: firstPositionOwner.publicKey,
       invariant
     }
 
-    const thirdStakeIx = await staker.createStakeIx(thirdCreateStake)
-    const thirdTx = new Transaction().add(firstUpdateIx).add(thirdStakeIx)
+    const thirdStakeIx = await staker.createStakeIx(market, firstUpdate, thirdCreateStake)
+    const thirdTx = new Transaction().add(thirdStakeIx)
     await signAndSend(thirdTx, [firstPositionOwner], staker.connection)
 
     // stake second position on second incentive, fourth case
@@ -328,14 +321,14 @@ describe('Multicall test', () => {
       id: secondPositionId,
       index,
       position: secondPosition,
-      incentive: secondIncentiveAccount.publicKey,
+      incentive: secondIncentiveAccount,
       owner: secondPositionOwner.publicKey,
       signer: secondPositionOwner.publicKey,
       invariant
     }
 
-    const fourthStakeIx = await staker.createStakeIx(fourthCreateStake)
-    const fourthTx = new Transaction().add(secondUpdateIx).add(fourthStakeIx)
+    const fourthStakeIx = await staker.createStakeIx(market, secondUpdate, fourthCreateStake)
+    const fourthTx = new Transaction().add(fourthStakeIx)
     await signAndSend(fourthTx, [secondPositionOwner], staker.connection)
 
     // swap
@@ -363,18 +356,19 @@ describe('Multicall test', () => {
     await sleep(20000)
     // withdraw first case
     const firstWithdraw: Withdraw = {
-      incentive: firstIncentiveAccount.publicKey,
+      incentive: firstIncentiveAccount,
       pool,
       id: firstPositionId,
       position: firstPosition,
       owner: firstPositionOwner.publicKey,
       incentiveTokenAccount: firstIncentiveTokenAccount.publicKey,
       ownerTokenAcc: firstOwnerTokenAccount,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const firstWithdrawIx = await staker.withdrawIx(firstWithdraw)
-    const firstWithdrawTx = new Transaction().add(firstUpdateIx).add(firstWithdrawIx)
+    const firstWithdrawIx = await staker.withdrawIx(market, firstUpdate, firstWithdraw)
+    const firstWithdrawTx = new Transaction().add(firstWithdrawIx)
     await signAndSend(firstWithdrawTx, [firstPositionOwner], staker.connection)
 
     const balanceAfterFirst = (await incentiveToken.getAccountInfo(firstOwnerTokenAccount)).amount
@@ -382,54 +376,57 @@ describe('Multicall test', () => {
 
     // withdraw second case
     const secondWithdraw: Withdraw = {
-      incentive: firstIncentiveAccount.publicKey,
+      incentive: firstIncentiveAccount,
       pool,
       id: secondPositionId,
       position: secondPosition,
       owner: secondPositionOwner.publicKey,
       incentiveTokenAccount: firstIncentiveTokenAccount.publicKey,
       ownerTokenAcc: secondOwnerTokenAccount,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const secondWithdrawIx = await staker.withdrawIx(secondWithdraw)
-    const secondWithdrawTx = new Transaction().add(secondUpdateIx).add(secondWithdrawIx)
+    const secondWithdrawIx = await staker.withdrawIx(market, secondUpdate, secondWithdraw)
+    const secondWithdrawTx = new Transaction().add(secondWithdrawIx)
     await signAndSend(secondWithdrawTx, [secondPositionOwner], staker.connection)
 
     const balanceAfterSecond = (await incentiveToken.getAccountInfo(secondOwnerTokenAccount)).amount
     assert.ok(almostEqual(balanceAfterSecond, new BN('1500'), epsilon))
     // withdraw third case
     const thirdWithdraw: Withdraw = {
-      incentive: secondIncentiveAccount.publicKey,
+      incentive: secondIncentiveAccount,
       pool,
       id: firstPositionId,
       position: firstPosition,
       owner: firstPositionOwner.publicKey,
       incentiveTokenAccount: secondIncentiveTokenAccount.publicKey,
       ownerTokenAcc: firstOwnerTokenAccount,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const thirdWithdrawIx = await staker.withdrawIx(thirdWithdraw)
-    const thirdWithdrawTx = new Transaction().add(firstUpdateIx).add(thirdWithdrawIx)
+    const thirdWithdrawIx = await staker.withdrawIx(market, firstUpdate, thirdWithdraw)
+    const thirdWithdrawTx = new Transaction().add(thirdWithdrawIx)
     await signAndSend(thirdWithdrawTx, [firstPositionOwner], staker.connection)
 
     const balanceAfterThird = (await incentiveToken.getAccountInfo(firstOwnerTokenAccount)).amount
     assert.ok(almostEqual(balanceAfterThird, new BN('1000'), epsilon))
     // withdraw fourth case
     const fourthWithdraw: Withdraw = {
-      incentive: secondIncentiveAccount.publicKey,
+      incentive: secondIncentiveAccount,
       pool,
       id: secondPositionId,
       position: secondPosition,
       owner: secondPositionOwner.publicKey,
       incentiveTokenAccount: secondIncentiveTokenAccount.publicKey,
       ownerTokenAcc: secondOwnerTokenAccount,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const fourthWithdrawIx = await staker.withdrawIx(fourthWithdraw)
-    const fourthWithdrawTx = new Transaction().add(secondUpdateIx).add(fourthWithdrawIx)
+    const fourthWithdrawIx = await staker.withdrawIx(market, secondUpdate, fourthWithdraw)
+    const fourthWithdrawTx = new Transaction().add(fourthWithdrawIx)
     await signAndSend(fourthWithdrawTx, [secondPositionOwner], staker.connection)
 
     const balanceAfterFourth = (await incentiveToken.getAccountInfo(secondOwnerTokenAccount)).amount
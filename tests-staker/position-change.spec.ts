@@ -3,7 +3,7 @@ import { Provider, BN } from '@project-serum/anchor'
 import { Market, Pair, sleep, PRICE_DENOMINATOR, LIQUIDITY_DENOMINATOR } from '@invariant-labs/sdk'
 import { Network } from '../staker-sdk/src'
 import { Keypair, PublicKey, Transaction } from '@solana/web3.js'
-import { createToken, getTime, signAndSend, almostEqual } from './testUtils'
+import { createToken, getTime, signAndSend, assertThrowsAsync } from './testUtils'
 import { createToken as createTkn, initMarket } from '../tests/testUtils'
 import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import { toDecimal } from '../staker-sdk/lib/utils'
@@ -20,12 +20,11 @@ describe('Withdraw with transfer position ownership', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const incentiveAccount = Keypair.generate()
+  let incentiveAccount: PublicKey
   const positionOwner = Keypair.generate()
   const founderAccount = Keypair.generate()
   const positionRecipient = Keypair.generate()
   const admin = Keypair.generate()
-  const epsilon = new BN(100)
   let nonce: number
   let staker: Staker
   let market: Market
@@ -47,8 +46,7 @@ describe('Withdraw with transfer position ownership', () => {
 
     await Promise.all([
       connection.requestAirdrop(mintAuthority.publicKey, 1e9),
-      connection.requestAirdrop(positionOwner.publicKey, 1e9),
-      connection.requestAirdrop(incentiveAccount.publicKey, 10e9)
+      connection.requestAirdrop(positionOwner.publicKey, 1e9)
     ])
 
     // create token
@@ -96,6 +94,7 @@ describe('Withdraw with transfer position ownership', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
   })
 
   it('Withdraw', async () => {
@@ -115,19 +114,16 @@ describe('Withdraw with transfer position ownership', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      createTx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     const upperTick = 10
@@ -183,14 +179,13 @@ describe('Withdraw with transfer position ownership', () => {
       id: positionId,
       index,
       position,
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
@@ -255,22 +250,29 @@ describe('Withdraw with transfer position ownership', () => {
     }
     // withdraw
     const withdraw: Withdraw = {
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       pool: poolAddress,
       id: recipientPositionId,
       position: recipientPositionAddress,
       owner: positionRecipient.publicKey,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       ownerTokenAcc: positionRecipientTokenAccount,
-      index
+      payer: positionOwner.publicKey,
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
-    const updateRecipientIx = await market.updateSecondsPerLiquidityInstruction(updateRecipient)
-    const withdrawIx = await staker.withdrawIx(withdraw)
-    const withdrawTx = new Transaction().add(updateRecipientIx).add(withdrawIx)
+    const payerLamportsBefore = await connection.getBalance(positionOwner.publicKey)
+    const withdrawIx = await staker.withdrawIx(market, updateRecipient, withdraw)
+    const withdrawTx = new Transaction().add(withdrawIx)
     await signAndSend(withdrawTx, [positionRecipient], staker.connection)
 
-    // should be around half of reward
+    // transferred position doesn't back the stake, it's closed without reward
     const balanceAfter = (await incentiveToken.getAccountInfo(positionRecipientTokenAccount)).amount
-    assert.ok(almostEqual(balanceAfter, new BN('500'), epsilon))
+    assert.ok(balanceAfter.eqn(0))
+    await assertThrowsAsync(staker.getStake(incentiveAccount, poolAddress, positionId))
+
+    // rent goes back to the one who paid for the stake
+    const payerLamportsAfter = await connection.getBalance(positionOwner.publicKey)
+    assert.ok(payerLamportsAfter > payerLamportsBefore)
   })
 })
@@ -25,7 +25,7 @@ describe('Remove all takes', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const incentiveAccount = Keypair.generate()
+  let incentiveAccount: PublicKey
   const founderAccount = Keypair.generate()
   const positionOwner = Keypair.generate()
   const admin = Keypair.generate()
@@ -47,8 +47,7 @@ describe('Remove all takes', () => {
 
     await Promise.all([
       await connection.requestAirdrop(mintAuthority.publicKey, 1e9),
-      await connection.requestAirdrop(positionOwner.publicKey, 1e9),
-      await connection.requestAirdrop(incentiveAccount.publicKey, 10e9)
+      await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     ])
 
     // create token
@@ -104,6 +103,7 @@ describe('Remove all takes', () => {
     await market.createPool(createPoolVars)
 
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
     invariant = anchor.workspace.Invariant.programId
   })
 
@@ -127,18 +127,15 @@ describe('Remove all takes', () => {
       invariant
     }
 
+    incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
-    await signAndSend(
-      createTx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
 
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
@@ -160,19 +157,19 @@ describe('Remove all takes', () => {
       positionOwner,
       userTokenXAccount,
       userTokenYAccount,
-      incentiveAccount.publicKey,
+      incentiveAccount,
       numOfStakes
     )
     const end = new Date().valueOf() / 1000
 
-    const incentiveBefore = await staker.getIncentive(incentiveAccount.publicKey)
+    const incentiveBefore = await staker.getIncentive(incentiveAccount)
     assert.ok(incentiveBefore.numOfStakes.eq(new BN(numOfStakes)))
 
     // wait for the end of incentive
     const delay = (duration - (end - begin) + 5) * 1000
     await sleep(delay)
 
-    const stakes = await staker.getAllIncentiveStakes(incentiveAccount.publicKey)
+    const stakes = await staker.getAllIncentiveStakes(incentiveAccount)
 
     let tx = new Transaction()
     const stringTx: string[] = []
@@ -182,7 +179,7 @@ describe('Remove all takes', () => {
     for (let i = 0; i < stakes.length; i++) {
       const removeIx = await staker.removeStakeIx(
         stakes[i].publicKey,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         founderAccount.publicKey
       )
       tx.add(removeIx)
@@ -192,7 +189,7 @@ describe('Remove all takes', () => {
       }
     }
 
-    const incentiveAfter = await staker.getIncentive(incentiveAccount.publicKey)
+    const incentiveAfter = await staker.getIncentive(incentiveAccount)
     assert.ok(incentiveAfter.numOfStakes.eq(new BN('0')))
   })
 })
@@ -26,8 +26,8 @@ describe('Stake tests', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const incentiveAccount = Keypair.generate()
-  const secondIncentiveAccount = Keypair.generate()
+  let incentiveAccount: PublicKey
+  let secondIncentiveAccount: PublicKey
   const founderAccount = Keypair.generate()
   const positionOwner = Keypair.generate()
   const admin = Keypair.generate()
@@ -53,9 +53,7 @@ describe('Stake tests', () => {
 
     await Promise.all([
       connection.requestAirdrop(mintAuthority.publicKey, 1e9),
-      connection.requestAirdrop(positionOwner.publicKey, 1e9),
-      connection.requestAirdrop(incentiveAccount.publicKey, 10e9),
-      connection.requestAirdrop(secondIncentiveAccount.publicKey, 1e9)
+      connection.requestAirdrop(positionOwner.publicKey, 1e9)
     ])
 
     // create token
@@ -104,6 +102,7 @@ describe('Stake tests', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
   })
 
   it('Stake', async () => {
@@ -124,18 +123,15 @@ describe('Stake tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
-    await signAndSend(
-      createTx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
 
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
@@ -189,22 +185,21 @@ describe('Stake tests', () => {
       id: positionId,
       index,
       position,
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
-    const stake = await staker.getStake(incentiveAccount.publicKey, poolAddress, positionId)
+    const stake = await staker.getStake(incentiveAccount, poolAddress, positionId)
     const positionStructAfter = await market.getPosition(positionOwner.publicKey, index)
     const liquidity: Decimal = { v: new BN(liquidityDelta.v) }
 
-    assert.ok(stake.incentive.equals(incentiveAccount.publicKey))
+    assert.ok(stake.incentive.equals(incentiveAccount))
     assert.ok(
       eqDecimal(stake.secondsPerLiquidityInitial, positionStructAfter.secondsPerLiquidityInside)
     )
@@ -263,14 +258,13 @@ describe('Stake tests', () => {
       id: position.id,
       index: newPositionIndex,
       position: positionAddress,
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await assertThrowsAsync(
       signAndSend(tx, [positionOwner], staker.connection),
@@ -295,18 +289,15 @@ describe('Stake tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    secondIncentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        secondIncentiveAccount.publicKey,
+        secondIncentiveAccount,
         secondIncentiveTokenAccount.publicKey
       )
     )
-    await signAndSend(
-      createTx,
-      [founderAccount, secondIncentiveAccount, secondIncentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, secondIncentiveTokenAccount], staker.connection)
 
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
@@ -359,23 +350,22 @@ describe('Stake tests', () => {
       id: positionId,
       index,
       position,
-      incentive: secondIncentiveAccount.publicKey,
+      incentive: secondIncentiveAccount,
       owner: positionOwner.publicKey,
       signer: admin.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [admin], staker.connection)
 
-    const stake = await staker.getStake(secondIncentiveAccount.publicKey, poolAddress, positionId)
+    const stake = await staker.getStake(secondIncentiveAccount, poolAddress, positionId)
     const positionStructAfter = await market.getPosition(positionOwner.publicKey, index)
     const liquidity: Decimal = { v: new BN(liquidityDelta.v) }
 
-    assert.ok(stake.incentive.equals(secondIncentiveAccount.publicKey))
+    assert.ok(stake.incentive.equals(secondIncentiveAccount))
     assert.ok(
       eqDecimal(stake.secondsPerLiquidityInitial, positionStructAfter.secondsPerLiquidityInside)
     )
@@ -152,9 +152,8 @@ export const createSomePositionsAndStakes = async (
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
   }
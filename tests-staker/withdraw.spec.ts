@@ -22,11 +22,10 @@ describe('Withdraw tests', () => {
   // @ts-expect-error
   const wallet = provider.wallet.payer as Account
   const mintAuthority = Keypair.generate()
-  const incentiveAccount = Keypair.generate()
-  const secondsIncentiveAccount = Keypair.generate()
-  const thirdIncentiveAccount = Keypair.generate()
-  const fourthIncentiveAccount = Keypair.generate()
-  const fifthIncentiveAccount = Keypair.generate()
+  let incentiveAccount: PublicKey
+  let secondsIncentiveAccount: PublicKey
+  let thirdIncentiveAccount: PublicKey
+  let fourthIncentiveAccount: PublicKey
   const positionOwner = Keypair.generate()
   const founderAccount = Keypair.generate()
   const admin = Keypair.generate()
@@ -51,12 +50,7 @@ describe('Withdraw tests', () => {
 
     await Promise.all([
       connection.requestAirdrop(mintAuthority.publicKey, 1e9),
-      connection.requestAirdrop(positionOwner.publicKey, 1e9),
-      connection.requestAirdrop(incentiveAccount.publicKey, 10e9),
-      connection.requestAirdrop(secondsIncentiveAccount.publicKey, 10e9),
-      connection.requestAirdrop(thirdIncentiveAccount.publicKey, 10e9),
-      connection.requestAirdrop(fourthIncentiveAccount.publicKey, 10e9),
-      connection.requestAirdrop(fifthIncentiveAccount.publicKey, 10e9)
+      connection.requestAirdrop(positionOwner.publicKey, 1e9)
     ])
 
     // create token
@@ -104,6 +98,7 @@ describe('Withdraw tests', () => {
   it('#init()', async () => {
     await initMarket(market, [pair], admin)
     pool = await pair.getAddress(anchor.workspace.Invariant.programId)
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
   })
 
   it('Withdraw - basic case', async () => {
@@ -124,19 +119,16 @@ describe('Withdraw tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    incentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        incentiveAccount.publicKey,
+        incentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      createTx,
-      [founderAccount, incentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     const upperTick = 10
@@ -192,14 +184,13 @@ describe('Withdraw tests', () => {
       id: positionId,
       index,
       position,
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
@@ -234,18 +225,19 @@ describe('Withdraw tests', () => {
 
     // withdraw
     const withdraw: Withdraw = {
-      incentive: incentiveAccount.publicKey,
+      incentive: incentiveAccount,
       pool: poolAddress,
       id: positionId,
       position,
       owner: positionOwner.publicKey,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       ownerTokenAcc: ownerTokenAcc,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const withdrawIx = await staker.withdrawIx(withdraw)
-    const withdrawTx = new Transaction().add(updateIx).add(withdrawIx)
+    const withdrawIx = await staker.withdrawIx(market, update, withdraw)
+    const withdrawTx = new Transaction().add(withdrawIx)
     await signAndSend(withdrawTx, [positionOwner], staker.connection)
 
     // should be around half of reward
@@ -286,19 +278,20 @@ describe('Withdraw tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
+    secondsIncentiveAccount = await staker.getNextIncentiveAddress(
+      pool,
+      createIncentiveVars.founder
+    )
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        secondsIncentiveAccount.publicKey,
+        secondsIncentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      createTx,
-      [founderAccount, secondsIncentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     const upperTick = 10
@@ -354,14 +347,13 @@ describe('Withdraw tests', () => {
       id: positionId,
       index,
       position,
-      incentive: secondsIncentiveAccount.publicKey,
+      incentive: secondsIncentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
@@ -406,23 +398,32 @@ describe('Withdraw tests', () => {
 
     // withdraw
     const withdraw: Withdraw = {
-      incentive: secondsIncentiveAccount.publicKey,
+      incentive: secondsIncentiveAccount,
       pool: poolAddress,
       id: positionId,
       position,
       owner: positionOwner.publicKey,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       ownerTokenAcc: ownerTokenAcc,
-      index
+      index,
+      invariant: anchor.workspace.Invariant.programId
     }
 
-    const withdrawIx = await staker.withdrawIx(withdraw)
-    const withdrawTx = new Transaction().add(updateIx).add(withdrawIx)
+    const withdrawIx = await staker.withdrawIx(market, update, withdraw)
+    const withdrawTx = new Transaction().add(withdrawIx)
 
     await assertThrowsAsync(
       signAndSend(withdrawTx, [positionOwner], staker.connection),
       ERRORS.ACCOUNT_OWNED_BY_WRONG_PROGRAM
     )
+
+    // stake of the removed position can still be closed, its liquidity no longer counts
+    const incentiveBefore = await staker.getIncentive(secondsIncentiveAccount)
+    await staker.closeInvalidatedStake(secondsIncentiveAccount, poolAddress, positionId)
+
+    const incentiveAfter = await staker.getIncentive(secondsIncentiveAccount)
+    assert.ok(incentiveAfter.numOfStakes.eq(incentiveBefore.numOfStakes.subn(1)))
+    await assertThrowsAsync(staker.getStake(secondsIncentiveAccount, poolAddress, positionId))
   })
   it('Withdraw - move position index', async () => {
     const founderAccount = Keypair.generate()
@@ -458,19 +459,17 @@ describe('Withdraw tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
+    thirdIncentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        thirdIncentiveAccount.publicKey,
+        thirdIncentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      createTx,
-      [founderAccount, thirdIncentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     const upperTick = 20
@@ -546,14 +545,13 @@ describe('Withdraw tests', () => {
       id: positionId,
       index: secondPositionIndex,
       position,
-      incentive: thirdIncentiveAccount.publicKey,
+      incentive: thirdIncentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
@@ -606,14 +604,15 @@ describe('Withdraw tests', () => {
 
     // withdraw
     const withdraw: Withdraw = {
-      incentive: thirdIncentiveAccount.publicKey,
+      incentive: thirdIncentiveAccount,
       pool: poolAddress,
       id: secondPosition.id,
       position: secondPositionAddress,
       owner: positionOwner.publicKey,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       ownerTokenAcc: ownerTokenAcc,
-      index: firstPositionIndex
+      index: firstPositionIndex,
+      invariant: anchor.workspace.Invariant.programId
     }
 
     // update after
@@ -625,10 +624,9 @@ describe('Withdraw tests', () => {
       upperTickIndex: upperTick,
       index: firstPositionIndex
     }
-    const updateAfterIx = await market.updateSecondsPerLiquidityInstruction(updateAfter)
 
-    const withdrawIx = await staker.withdrawIx(withdraw)
-    const withdrawTx = new Transaction().add(updateAfterIx).add(withdrawIx)
+    const withdrawIx = await staker.withdrawIx(market, updateAfter, withdraw)
+    const withdrawTx = new Transaction().add(withdrawIx)
     await signAndSend(withdrawTx, [positionOwner], staker.connection)
   })
   it('Withdraw - by other account', async () => {
@@ -665,19 +663,17 @@ describe('Withdraw tests', () => {
       founderTokenAccount: founderTokenAccount,
       invariant
     }
+    await staker.createIncentiveList(pool, founderAccount.publicKey)
+    fourthIncentiveAccount = await staker.getNextIncentiveAddress(pool, createIncentiveVars.founder)
     const createTx = new Transaction().add(
       await staker.createIncentiveIx(
         createIncentiveVars,
-        fourthIncentiveAccount.publicKey,
+        fourthIncentiveAccount,
         incentiveTokenAccount.publicKey
       )
     )
 
-    await signAndSend(
-      createTx,
-      [founderAccount, fourthIncentiveAccount, incentiveTokenAccount],
-      staker.connection
-    )
+    await signAndSend(createTx, [founderAccount, incentiveTokenAccount], staker.connection)
     // create position
     await connection.requestAirdrop(positionOwner.publicKey, 1e9)
     const upperTick = 20
@@ -753,14 +749,13 @@ describe('Withdraw tests', () => {
       id: positionId,
       index: secondPositionIndex,
       position,
-      incentive: fourthIncentiveAccount.publicKey,
+      incentive: fourthIncentiveAccount,
       owner: positionOwner.publicKey,
       invariant: anchor.workspace.Invariant.programId
     }
 
-    const updateIx = await market.updateSecondsPerLiquidityInstruction(update)
-    const stakeIx = await staker.createStakeIx(createStake)
-    const tx = new Transaction().add(updateIx).add(stakeIx)
+    const stakeIx = await staker.createStakeIx(market, update, createStake)
+    const tx = new Transaction().add(stakeIx)
 
     await signAndSend(tx, [positionOwner], staker.connection)
 
@@ -813,14 +808,15 @@ describe('Withdraw tests', () => {
 
     // withdraw
     const withdraw: Withdraw = {
-      incentive: fourthIncentiveAccount.publicKey,
+      incentive: fourthIncentiveAccount,
       pool: poolAddress,
       id: secondPosition.id,
       position: secondPositionAddress,
       owner: positionOwner.publicKey,
       incentiveTokenAccount: incentiveTokenAccount.publicKey,
       ownerTokenAcc: ownerTokenAcc,
-      index: firstPositionIndex
+      index: firstPositionIndex,
+      invariant: anchor.workspace.Invariant.programId
     }
 
     // update after
@@ -832,10 +828,9 @@ describe('Withdraw tests', () => {
       upperTickIndex: upperTick,
       index: firstPositionIndex
     }
-    const updateAfterIx = await market.updateSecondsPerLiquidityInstruction(updateAfter)
 
-    const withdrawIx = await staker.withdrawIx(withdraw)
-    const withdrawTx = new Transaction().add(updateAfterIx).add(withdrawIx)
+    const withdrawIx = await staker.withdrawIx(market, updateAfter, withdraw)
+    const withdrawTx = new Transaction().add(withdrawIx)
     await signAndSend(withdrawTx, [founderAccount], staker.connection)
   })
 })
@@ -180,6 +180,7 @@ describe('claim', () => {
           lowerTick: incorrectLowerTickAddress,
           upperTick: incorrectUpperTickAddress,
           owner: positionOwner.publicKey,
+          signer: positionOwner.publicKey,
           tokenX: pair.tokenX,
           tokenY: pair.tokenY,
           accountX: userTokenXAccount,
@@ -0,0 +1,126 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { assert } from 'chai'
+import { assertThrowsAsync, createToken, initMarket } from './testUtils'
+import { Market, Pair, LIQUIDITY_DENOMINATOR, Network, INVARIANT_ERRORS } from '@invariant-labs/sdk'
+import { fromFee, toDecimal, tou64 } from '@invariant-labs/sdk/src/utils'
+import { CompoundFee, Decimal, FeeTier, InitPosition, Swap } from '@invariant-labs/sdk/src/market'
+
+describe('compound fee', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  // @ts-expect-error
+  const wallet = provider.wallet.payer as Keypair
+  const mintAuthority = Keypair.generate()
+  const admin = Keypair.generate()
+  const positionOwner = Keypair.generate()
+  const swapper = Keypair.generate()
+  const feeTier: FeeTier = { fee: fromFee(new BN(600)), tickSpacing: 10 }
+  const otherFeeTier: FeeTier = { fee: fromFee(new BN(3000)), tickSpacing: 10 }
+  const lowerTick = -20
+  const upperTick = 10
+  const liquidityDelta: Decimal = { v: new BN(1_000_000).mul(LIQUIDITY_DENOMINATOR) }
+  let market: Market
+  let pair: Pair
+  let otherPair: Pair
+  let tokenX: Token
+  let tokenY: Token
+  let userTokenXAccount: PublicKey
+  let userTokenYAccount: PublicKey
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+
+    await Promise.all([
+      connection.requestAirdrop(mintAuthority.publicKey, 1e9),
+      connection.requestAirdrop(admin.publicKey, 1e9),
+      connection.requestAirdrop(positionOwner.publicKey, 1e9),
+      connection.requestAirdrop(swapper.publicKey, 1e9)
+    ])
+    const tokens = await Promise.all([
+      createToken(connection, wallet, mintAuthority),
+      createToken(connection, wallet, mintAuthority)
+    ])
+
+    pair = new Pair(tokens[0].publicKey, tokens[1].publicKey, feeTier)
+    otherPair = new Pair(tokens[0].publicKey, tokens[1].publicKey, otherFeeTier)
+    tokenX = new Token(connection, pair.tokenX, TOKEN_PROGRAM_ID, wallet)
+    tokenY = new Token(connection, pair.tokenY, TOKEN_PROGRAM_ID, wallet)
+  })
+
+  it('#init()', async () => {
+    await initMarket(market, [pair, otherPair], admin)
+  })
+
+  it('#create positions with the same range in both pools', async () => {
+    userTokenXAccount = await tokenX.createAccount(positionOwner.publicKey)
+    userTokenYAccount = await tokenY.createAccount(positionOwner.publicKey)
+    const mintAmount = tou64(new BN(10).pow(new BN(10)))
+    await tokenX.mintTo(userTokenXAccount, mintAuthority.publicKey, [mintAuthority], mintAmount)
+    await tokenY.mintTo(userTokenYAccount, mintAuthority.publicKey, [mintAuthority], mintAmount)
+
+    for (const p of [pair, otherPair]) {
+      const initPositionVars: InitPosition = {
+        pair: p,
+        owner: positionOwner.publicKey,
+        userTokenX: userTokenXAccount,
+        userTokenY: userTokenYAccount,
+        lowerTick,
+        upperTick,
+        liquidityDelta,
+        knownPrice: (await market.getPool(p)).sqrtPrice,
+        slippage: { v: new BN(0) }
+      }
+      await market.initPosition(initPositionVars, positionOwner)
+    }
+
+    // fees are owed only to the position in the first pool
+    const accountX = await tokenX.createAccount(swapper.publicKey)
+    const accountY = await tokenY.createAccount(swapper.publicKey)
+    const amount = new BN(1000)
+    await tokenX.mintTo(accountX, mintAuthority.publicKey, [mintAuthority], tou64(amount))
+
+    const swapVars: Swap = {
+      pair,
+      owner: swapper.publicKey,
+      xToY: true,
+      amount,
+      estimatedPriceAfterSwap: (await market.getPool(pair)).sqrtPrice,
+      slippage: toDecimal(1, 2),
+      accountX,
+      accountY,
+      byAmountIn: true
+    }
+    await market.swap(swapVars, swapper)
+  })
+
+  it('#compoundFee() rejects a position of another pool', async () => {
+    const poolBefore = await market.getPool(otherPair)
+
+    // position 0 lives in the first pool, its fees can't become liquidity of the other one
+    const compoundFeeVars: CompoundFee = {
+      pair: otherPair,
+      owner: positionOwner.publicKey,
+      index: 0
+    }
+    await assertThrowsAsync(
+      market.compoundFee(compoundFeeVars, positionOwner),
+      INVARIANT_ERRORS.INVALID_POSITION_POOL
+    )
+
+    const [poolAfter, position] = await Promise.all([
+      market.getPool(otherPair),
+      market.getPosition(positionOwner.publicKey, 0)
+    ])
+    assert.ok(poolAfter.liquidity.v.eq(poolBefore.liquidity.v))
+    assert.ok(position.liquidity.v.eq(liquidityDelta.v))
+    assert.ok(position.pool.equals(await pair.getAddress(market.program.programId)))
+  })
+})
@@ -0,0 +1,155 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { assert } from 'chai'
+import { createToken, initMarket } from './testUtils'
+import { Market, Pair, LIQUIDITY_DENOMINATOR, Network, calculatePriceSqrt } from '@invariant-labs/sdk'
+import { assertThrowsAsync, fromFee, tou64 } from '@invariant-labs/sdk/src/utils'
+import { getDeltaX, getDeltaY } from '@invariant-labs/sdk/src/math'
+import { Decimal, FeeTier, InitPosition, RebalancePosition } from '@invariant-labs/sdk/src/market'
+
+describe('rebalance position', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  // @ts-expect-error
+  const wallet = provider.wallet.payer as Keypair
+  const mintAuthority = Keypair.generate()
+  const admin = Keypair.generate()
+  const positionOwner = Keypair.generate()
+  const feeTier: FeeTier = { fee: fromFee(new BN(600)), tickSpacing: 10 }
+  const oldLowerTick = -20
+  const oldUpperTick = 10
+  const newLowerTick = -30
+  const newUpperTick = 20
+  const liquidityDelta: Decimal = { v: new BN(1_000_000).mul(LIQUIDITY_DENOMINATOR) }
+  let market: Market
+  let pair: Pair
+  let tokenX: Token
+  let tokenY: Token
+  let userTokenXAccount: PublicKey
+  let userTokenYAccount: PublicKey
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+
+    await Promise.all([
+      connection.requestAirdrop(mintAuthority.publicKey, 1e9),
+      connection.requestAirdrop(admin.publicKey, 1e9),
+      connection.requestAirdrop(positionOwner.publicKey, 1e9)
+    ])
+    const tokens = await Promise.all([
+      createToken(connection, wallet, mintAuthority),
+      createToken(connection, wallet, mintAuthority)
+    ])
+
+    pair = new Pair(tokens[0].publicKey, tokens[1].publicKey, feeTier)
+    tokenX = new Token(connection, pair.tokenX, TOKEN_PROGRAM_ID, wallet)
+    tokenY = new Token(connection, pair.tokenY, TOKEN_PROGRAM_ID, wallet)
+  })
+
+  it('#init()', async () => {
+    await initMarket(market, [pair], admin, 0)
+  })
+
+  it('#create position', async () => {
+    userTokenXAccount = await tokenX.createAccount(positionOwner.publicKey)
+    userTokenYAccount = await tokenY.createAccount(positionOwner.publicKey)
+    const mintAmount = tou64(new BN(10).pow(new BN(10)))
+    await tokenX.mintTo(userTokenXAccount, mintAuthority.publicKey, [mintAuthority], mintAmount)
+    await tokenY.mintTo(userTokenYAccount, mintAuthority.publicKey, [mintAuthority], mintAmount)
+
+    const initPositionVars: InitPosition = {
+      pair,
+      owner: positionOwner.publicKey,
+      userTokenX: userTokenXAccount,
+      userTokenY: userTokenYAccount,
+      lowerTick: oldLowerTick,
+      upperTick: oldUpperTick,
+      liquidityDelta,
+      knownPrice: (await market.getPool(pair)).sqrtPrice,
+      slippage: { v: new BN(0) }
+    }
+    await market.initPosition(initPositionVars, positionOwner)
+
+    assert.ok((await market.getPool(pair)).liquidity.v.eq(liquidityDelta.v))
+  })
+
+  it('#rebalancePosition() moves liquidity to the new range', async () => {
+    const pool = await market.getPool(pair)
+    const [userXBefore, userYBefore, reservesBefore] = await Promise.all([
+      tokenX.getAccountInfo(userTokenXAccount),
+      tokenY.getAccountInfo(userTokenYAccount),
+      market.getReserveBalances(pair, tokenX, tokenY)
+    ])
+
+    const rebalanceVars: RebalancePosition = {
+      pair,
+      owner: positionOwner.publicKey,
+      index: 0,
+      lowerTick: newLowerTick,
+      upperTick: newUpperTick,
+      liquidityDelta,
+      knownPrice: pool.sqrtPrice,
+      slippage: { v: new BN(0) },
+      userTokenX: userTokenXAccount,
+      userTokenY: userTokenYAccount
+    }
+    await market.rebalancePosition(rebalanceVars, positionOwner)
+
+    // withdrawal rounds down and deposit rounds up, no swaps so no fees were collected
+    const withdrawnX = getDeltaX(
+      pool.sqrtPrice,
+      calculatePriceSqrt(oldUpperTick),
+      liquidityDelta,
+      false
+    ) as BN
+    const withdrawnY = getDeltaY(
+      calculatePriceSqrt(oldLowerTick),
+      pool.sqrtPrice,
+      liquidityDelta,
+      false
+    ) as BN
+    const depositedX = getDeltaX(
+      pool.sqrtPrice,
+      calculatePriceSqrt(newUpperTick),
+      liquidityDelta,
+      true
+    ) as BN
+    const depositedY = getDeltaY(
+      calculatePriceSqrt(newLowerTick),
+      pool.sqrtPrice,
+      liquidityDelta,
+      true
+    ) as BN
+    const expectedPaidX = depositedX.sub(withdrawnX)
+    const expectedPaidY = depositedY.sub(withdrawnY)
+
+    const [userXAfter, userYAfter, reservesAfter] = await Promise.all([
+      tokenX.getAccountInfo(userTokenXAccount),
+      tokenY.getAccountInfo(userTokenYAccount),
+      market.getReserveBalances(pair, tokenX, tokenY)
+    ])
+    assert.ok(userXBefore.amount.sub(userXAfter.amount).eq(expectedPaidX))
+    assert.ok(userYBefore.amount.sub(userYAfter.amount).eq(expectedPaidY))
+    assert.ok(reservesAfter.x.sub(reservesBefore.x).eq(expectedPaidX))
+    assert.ok(reservesAfter.y.sub(reservesBefore.y).eq(expectedPaidY))
+
+    const position = await market.getPosition(positionOwner.publicKey, 0)
+    assert.equal(position.lowerTickIndex, newLowerTick)
+    assert.equal(position.upperTickIndex, newUpperTick)
+    assert.ok(position.liquidity.v.eq(liquidityDelta.v))
+    assert.ok((await market.getPool(pair)).liquidity.v.eq(liquidityDelta.v))
+
+    // old ticks are no longer used by any position
+    assert.isFalse(await market.isInitialized(pair, oldLowerTick))
+    assert.isFalse(await market.isInitialized(pair, oldUpperTick))
+    await assertThrowsAsync(market.getTick(pair, oldLowerTick))
+    await assertThrowsAsync(market.getTick(pair, oldUpperTick))
+  })
+})
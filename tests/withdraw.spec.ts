@@ -170,6 +170,7 @@ describe('withdraw', () => {
         accounts: {
           state: (await market.getStateAddress()).address,
           owner: positionOwner.publicKey,
+          signer: positionOwner.publicKey,
           removedPosition: removedPositionAddress,
           positionList: positionListAddress,
           lastPosition: lastPositionAddress,